@@ -18,7 +18,7 @@ pub fn derive_into_plot(input: TokenStream) -> TokenStream {
 
         impl #impl_generics gpui::Element for #type_name #type_generics #where_clause {
             type RequestLayoutState = ();
-            type PrepaintState = ();
+            type PrepaintState = (gpui::Hitbox, Option<gpui::AnyElement>);
 
             fn id(&self) -> Option<gpui::ElementId> {
                 None
@@ -36,7 +36,7 @@ pub fn derive_into_plot(input: TokenStream) -> TokenStream {
                 cx: &mut gpui::App,
             ) -> (gpui::LayoutId, Self::RequestLayoutState) {
                 let style = gpui::Style {
-                    size: gpui::Size::full(),
+                    size: <Self as Plot>::plot_size(self).layout_size(),
                     ..Default::default()
                 };
 
@@ -47,11 +47,28 @@ pub fn derive_into_plot(input: TokenStream) -> TokenStream {
                 &mut self,
                 _: Option<&gpui::GlobalElementId>,
                 _: Option<&gpui::InspectorElementId>,
-                _: gpui::Bounds<gpui::Pixels>,
+                bounds: gpui::Bounds<gpui::Pixels>,
                 _: &mut Self::RequestLayoutState,
-                _: &mut gpui::Window,
-                _: &mut gpui::App,
+                window: &mut gpui::Window,
+                cx: &mut gpui::App,
             ) -> Self::PrepaintState {
+                let hitbox = window.insert_hitbox(bounds, gpui::HitboxBehavior::Normal);
+
+                let mut tooltip = None;
+                if hitbox.is_hovered(window) {
+                    let position = window.mouse_position() - bounds.origin;
+                    tooltip = <Self as Plot>::tooltip(self, bounds, position, window, cx);
+                }
+                if let Some(tooltip) = tooltip.as_mut() {
+                    tooltip.prepaint_as_root(
+                        bounds.origin,
+                        bounds.size.map(gpui::AvailableSpace::Definite),
+                        window,
+                        cx,
+                    );
+                }
+
+                (hitbox, tooltip)
             }
 
             fn paint(
@@ -60,11 +77,28 @@ pub fn derive_into_plot(input: TokenStream) -> TokenStream {
                 _: Option<&gpui::InspectorElementId>,
                 bounds: gpui::Bounds<gpui::Pixels>,
                 _: &mut Self::RequestLayoutState,
-                _: &mut Self::PrepaintState,
+                prepaint: &mut Self::PrepaintState,
                 window: &mut gpui::Window,
                 cx: &mut gpui::App,
             ) {
-                <Self as Plot>::paint(self, bounds, window, cx)
+                <Self as Plot>::paint(self, bounds, window, cx);
+
+                let (hitbox, tooltip) = prepaint;
+                let has_tooltip = tooltip.is_some();
+                if let Some(tooltip) = tooltip.as_mut() {
+                    tooltip.paint(window, cx);
+                }
+
+                // Repaint to follow the pointer, and once more to hide the tooltip on leave.
+                let current_view = window.current_view();
+                window.on_mouse_event({
+                    let hitbox = hitbox.clone();
+                    move |_: &gpui::MouseMoveEvent, phase, window, cx| {
+                        if phase.bubble() && (has_tooltip || hitbox.is_hovered(window)) {
+                            cx.notify(current_view);
+                        }
+                    }
+                });
             }
         }
     };
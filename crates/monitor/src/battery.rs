@@ -0,0 +1,103 @@
+//! The battery status of laptops, read with the `starship-battery` crate.
+//!
+//! Desktops and builds without the `battery` feature report no battery.
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatteryState {
+    Charging,
+    Discharging,
+    Full,
+    Empty,
+    Unknown,
+}
+
+impl std::fmt::Display for BatteryState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            Self::Charging => "Charging",
+            Self::Discharging => "Discharging",
+            Self::Full => "Full",
+            Self::Empty => "Empty",
+            Self::Unknown => "Unknown",
+        };
+        f.write_str(state)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    /// The state of charge in percent.
+    pub charge: f32,
+    pub state: BatteryState,
+    /// The seconds until fully charged, `None` if not charging or unknown.
+    pub time_to_full: Option<u64>,
+    /// The seconds until empty, `None` if not discharging or unknown.
+    pub time_to_empty: Option<u64>,
+}
+
+/// Reads the first battery of the system, if any.
+#[cfg(feature = "battery")]
+pub(crate) struct BatteryReader {
+    manager: Option<starship_battery::Manager>,
+    battery: Option<starship_battery::Battery>,
+}
+
+#[cfg(feature = "battery")]
+impl BatteryReader {
+    pub(crate) fn new() -> Self {
+        let manager = starship_battery::Manager::new().ok();
+        let battery = manager
+            .as_ref()
+            .and_then(|manager| manager.batteries().ok())
+            .and_then(|mut batteries| batteries.find_map(|battery| battery.ok()));
+
+        Self { manager, battery }
+    }
+
+    pub(crate) fn refresh(&mut self) {
+        if let (Some(manager), Some(battery)) = (&self.manager, &mut self.battery) {
+            // Keep the last readings if the refresh fails.
+            _ = manager.refresh(battery);
+        }
+    }
+
+    pub(crate) fn info(&self) -> Option<BatteryInfo> {
+        use starship_battery::{
+            State,
+            units::{ratio::percent, time::second},
+        };
+
+        let battery = self.battery.as_ref()?;
+        let state = match battery.state() {
+            State::Charging => BatteryState::Charging,
+            State::Discharging => BatteryState::Discharging,
+            State::Full => BatteryState::Full,
+            State::Empty => BatteryState::Empty,
+            _ => BatteryState::Unknown,
+        };
+
+        Some(BatteryInfo {
+            charge: battery.state_of_charge().get::<percent>(),
+            state,
+            time_to_full: battery.time_to_full().map(|time| time.get::<second>() as u64),
+            time_to_empty: battery.time_to_empty().map(|time| time.get::<second>() as u64),
+        })
+    }
+}
+
+#[cfg(not(feature = "battery"))]
+pub(crate) struct BatteryReader;
+
+#[cfg(not(feature = "battery"))]
+impl BatteryReader {
+    pub(crate) fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn refresh(&mut self) {}
+
+    pub(crate) fn info(&self) -> Option<BatteryInfo> {
+        None
+    }
+}
@@ -0,0 +1,82 @@
+//! The GPU usage of NVIDIA cards, read with the `nvml-wrapper` crate.
+//!
+//! Machines without the NVIDIA driver and builds without the `gpu` feature report no GPU.
+use serde::{Deserialize, Serialize};
+
+use crate::system_monitor::percent;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub name: String,
+    /// The utilization of the GPU in percent.
+    pub utilization: f32,
+    /// The used video memory in bytes.
+    pub memory_used: u64,
+    /// The total video memory in bytes.
+    pub memory_total: u64,
+    /// The temperature of the GPU in °C, `None` if unknown.
+    pub temperature: Option<f32>,
+}
+
+impl GpuInfo {
+    /// Returns the used share of the video memory in percent, 0 if the total is unknown.
+    pub fn memory_percent(&self) -> f64 {
+        percent(self.memory_used, self.memory_total)
+    }
+}
+
+/// Reads all the GPUs supported by NVML, if the driver is installed.
+#[cfg(feature = "gpu")]
+pub(crate) struct GpuReader {
+    nvml: Option<nvml_wrapper::Nvml>,
+}
+
+#[cfg(feature = "gpu")]
+impl GpuReader {
+    pub(crate) fn new() -> Self {
+        Self {
+            nvml: nvml_wrapper::Nvml::init().ok(),
+        }
+    }
+
+    pub(crate) fn info(&self) -> Vec<GpuInfo> {
+        use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+
+        let Some(nvml) = &self.nvml else {
+            return vec![];
+        };
+        let count = nvml.device_count().unwrap_or(0);
+
+        // Skip the devices that fail to report, e.g. lost or in a reset.
+        (0..count)
+            .filter_map(|ix| nvml.device_by_index(ix).ok())
+            .filter_map(|device| {
+                let memory = device.memory_info().ok()?;
+                Some(GpuInfo {
+                    name: device.name().unwrap_or_else(|_| "GPU".to_string()),
+                    utilization: device.utilization_rates().map_or(0., |rates| rates.gpu as f32),
+                    memory_used: memory.used,
+                    memory_total: memory.total,
+                    temperature: device
+                        .temperature(TemperatureSensor::Gpu)
+                        .ok()
+                        .map(|temperature| temperature as f32),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(not(feature = "gpu"))]
+pub(crate) struct GpuReader;
+
+#[cfg(not(feature = "gpu"))]
+impl GpuReader {
+    pub(crate) fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn info(&self) -> Vec<GpuInfo> {
+        vec![]
+    }
+}
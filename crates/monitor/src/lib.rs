@@ -0,0 +1,39 @@
+//! The system data collection of the task manager.
+//!
+//! This crate has no UI dependencies, so it can be embedded in a CLI or daemon:
+//!
+//! ```no_run
+//! use monitor::{LiveSource, SnapshotSource, format_bytes};
+//!
+//! let mut source = LiveSource::new();
+//! if let Some(snapshot) = source.next_snapshot() {
+//!     println!("CPU: {:.1}%", snapshot.global_cpu_usage);
+//!     println!("Memory: {}", format_bytes(snapshot.memory.used));
+//! }
+//! ```
+//!
+//! Or refresh on a background thread with [`SystemMonitor::watch`]:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use monitor::SystemMonitor;
+//!
+//! let handle = SystemMonitor::watch(Duration::from_secs(1), |snapshot| {
+//!     println!("CPU: {:.1}%", snapshot.global_cpu_usage);
+//! });
+//! std::thread::sleep(Duration::from_secs(10));
+//! handle.stop();
+//! ```
+mod battery;
+mod gpu;
+mod report;
+mod snapshot_source;
+mod system_monitor;
+mod watch;
+
+pub use battery::{BatteryInfo, BatteryState};
+pub use gpu::GpuInfo;
+pub use snapshot_source::*;
+pub use system_monitor::*;
+pub use watch::WatchHandle;
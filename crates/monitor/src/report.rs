@@ -0,0 +1,188 @@
+use std::fmt::Write as _;
+
+use crate::{ProcessInfo, SystemSnapshot, format_bytes};
+
+/// The number of processes listed in each top table of the report.
+const TOP_PROCESSES: usize = 10;
+
+impl SystemSnapshot {
+    /// Returns a Markdown summary of the snapshot, for pasting into bug reports.
+    ///
+    /// Includes the OS info of the host the snapshot was taken on, the CPU, memory and
+    /// disk totals, and the top processes by CPU and memory.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        _ = self.write_markdown(&mut out);
+        out
+    }
+
+    fn write_markdown(&self, out: &mut String) -> std::fmt::Result {
+        writeln!(out, "## System Report")?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "- **OS:** {}",
+            self.host.long_os_version().unwrap_or_else(|| "Unknown".into())
+        )?;
+        writeln!(
+            out,
+            "- **Kernel:** {}",
+            self.host.kernel_version.as_deref().unwrap_or("Unknown")
+        )?;
+        writeln!(out, "- **CPU:** {:.1}% ({} cores)", self.global_cpu_usage, self.cpus.len())?;
+        writeln!(
+            out,
+            "- **Memory:** {} / {} used",
+            format_bytes(self.memory.used),
+            format_bytes(self.memory.total)
+        )?;
+        for disk in &self.disks {
+            writeln!(
+                out,
+                "- **Disk {}:** {} free of {}",
+                disk.name,
+                format_bytes(disk.available),
+                format_bytes(disk.total)
+            )?;
+        }
+        writeln!(out, "- **Processes:** {}", self.processes.len())?;
+
+        let mut processes = self.processes.iter().collect::<Vec<_>>();
+
+        processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        writeln!(out)?;
+        writeln!(out, "### Top Processes by CPU")?;
+        writeln!(out)?;
+        write_process_table(out, &processes)?;
+
+        processes.sort_by(|a, b| b.memory.cmp(&a.memory));
+        writeln!(out)?;
+        writeln!(out, "### Top Processes by Memory")?;
+        writeln!(out)?;
+        write_process_table(out, &processes)?;
+
+        Ok(())
+    }
+}
+
+fn write_process_table(out: &mut String, processes: &[&ProcessInfo]) -> std::fmt::Result {
+    writeln!(out, "| Name | PID | CPU | Memory |")?;
+    writeln!(out, "| --- | ---: | ---: | ---: |")?;
+    for process in processes.iter().take(TOP_PROCESSES) {
+        writeln!(
+            out,
+            "| {} | {} | {:.1}% | {} |",
+            process.name.replace('|', "\\|"),
+            process.pid,
+            process.cpu_usage,
+            format_bytes(process.memory)
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::{CpuInfo, DiskInfo, HostInfo, MemoryInfo, ProcessInfo, SystemSnapshot};
+
+    const MB: u64 = 1024 * 1024;
+
+    fn snapshot() -> SystemSnapshot {
+        // The CPU usage rises and the memory falls with the PID, so the tables differ.
+        let processes = (0..12)
+            .map(|pid| ProcessInfo {
+                pid,
+                parent_pid: None,
+                name: if pid == 11 { "a|b".to_string() } else { format!("p{pid}") },
+                user: None,
+                cmd: vec![],
+                exe: None,
+                status: "Run".to_string(),
+                cpu_usage: pid as f32,
+                memory: (12 - pid as u64) * MB,
+                disk_usage: 0,
+                start_time: 0,
+                run_time: 0,
+                threads: None,
+                accessible: true,
+            })
+            .collect();
+
+        SystemSnapshot {
+            timestamp: Instant::now(),
+            timestamp_ms: 0,
+            processes,
+            cpus: vec![
+                CpuInfo { usage: 20., name: "cpu0".to_string() },
+                CpuInfo { usage: 30., name: "cpu1".to_string() },
+            ],
+            memory: MemoryInfo {
+                total: 8 * 1024 * MB,
+                used: 2 * 1024 * MB,
+                available: 6 * 1024 * MB,
+                swap_total: 0,
+                swap_used: 0,
+            },
+            disks: vec![DiskInfo {
+                name: "/dev/sda1".to_string(),
+                total: 100 * 1024 * MB,
+                available: 40 * 1024 * MB,
+                read_bytes: 0,
+                written_bytes: 0,
+            }],
+            networks: vec![],
+            global_cpu_usage: 25.,
+            uptime_secs: 0,
+            boot_time: 0,
+            components: vec![],
+            battery: None,
+            load_average: None,
+            gpus: vec![],
+            host: HostInfo {
+                os_name: Some("Ubuntu".to_string()),
+                os_version: Some("24.04".to_string()),
+                kernel_version: Some("6.8.0".to_string()),
+                host_name: Some("build-box".to_string()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown = snapshot().to_markdown();
+
+        assert!(markdown.starts_with("## System Report\n"));
+        // The OS of the snapshot, not of the machine running the test.
+        assert!(markdown.contains("- **OS:** Ubuntu 24.04\n"));
+        assert!(markdown.contains("- **Kernel:** 6.8.0\n"));
+        assert!(markdown.contains("- **CPU:** 25.0% (2 cores)\n"));
+        assert!(markdown.contains("- **Memory:** 2.00 GiB / 8.00 GiB used\n"));
+        assert!(markdown.contains("- **Disk /dev/sda1:** 40.00 GiB free of 100.00 GiB\n"));
+        assert!(markdown.contains("- **Processes:** 12\n"));
+
+        let (_, tables) = markdown.split_once("### Top Processes by CPU\n").unwrap();
+        let (by_cpu, by_memory) = tables.split_once("### Top Processes by Memory\n").unwrap();
+        let rows = |table: &str| {
+            // Skip the header and the separator rows.
+            table
+                .lines()
+                .filter(|line| line.starts_with("| "))
+                .skip(2)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        // The top 10, the busiest first, with the pipes in the names escaped.
+        let by_cpu = rows(by_cpu);
+        assert_eq!(by_cpu.len(), 10);
+        assert_eq!(by_cpu[0], "| a\\|b | 11 | 11.0% | 1.00 MiB |");
+        assert_eq!(by_cpu[9], "| p2 | 2 | 2.0% | 10.00 MiB |");
+
+        let by_memory = rows(by_memory);
+        assert_eq!(by_memory.len(), 10);
+        assert_eq!(by_memory[0], "| p0 | 0 | 0.0% | 12.00 MiB |");
+        assert_eq!(by_memory[9], "| p9 | 9 | 9.0% | 3.00 MiB |");
+    }
+}
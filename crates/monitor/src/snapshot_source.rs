@@ -0,0 +1,236 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufRead as _, BufReader, BufWriter, Write as _},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::system_monitor::{SystemMonitor, SystemSnapshot};
+
+/// A source of [`SystemSnapshot`]s consumed by the monitoring loop.
+pub trait SnapshotSource {
+    /// Returns the next snapshot, or `None` if the source has no more snapshots.
+    fn next_snapshot(&mut self) -> Option<SystemSnapshot>;
+
+    /// Returns the interval to poll the next snapshot.
+    fn update_interval(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    /// Returns the monitor of the live system, to act on it, e.g. to end processes.
+    ///
+    /// `None` if the snapshots are not of the live system, e.g. a replay.
+    fn live_monitor_mut(&mut self) -> Option<&mut SystemMonitor> {
+        None
+    }
+}
+
+/// Snapshots from the live system.
+pub struct LiveSource {
+    monitor: SystemMonitor,
+}
+
+impl LiveSource {
+    pub fn new() -> Self {
+        Self {
+            monitor: SystemMonitor::new(),
+        }
+    }
+
+    pub fn monitor(&self) -> &SystemMonitor {
+        &self.monitor
+    }
+
+    pub fn monitor_mut(&mut self) -> &mut SystemMonitor {
+        &mut self.monitor
+    }
+}
+
+impl Default for LiveSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotSource for LiveSource {
+    fn next_snapshot(&mut self) -> Option<SystemSnapshot> {
+        self.monitor.update();
+        Some(self.monitor.snapshot())
+    }
+
+    fn update_interval(&self) -> Duration {
+        self.monitor.update_interval()
+    }
+
+    fn live_monitor_mut(&mut self) -> Option<&mut SystemMonitor> {
+        Some(&mut self.monitor)
+    }
+}
+
+/// Snapshots replayed from a recording file, at the recorded intervals.
+pub struct ReplaySource {
+    snapshots: VecDeque<SystemSnapshot>,
+    /// The `timestamp_ms` of the last returned snapshot.
+    last_timestamp_ms: Option<u64>,
+}
+
+impl ReplaySource {
+    /// Load the recording at the `path` written by [`Recorder`].
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let start = Instant::now();
+        let mut first_timestamp_ms = None;
+        let mut snapshots = VecDeque::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut snapshot: SystemSnapshot = serde_json::from_str(&line)?;
+            // Keep the recorded intervals between the snapshots.
+            let first_timestamp_ms = *first_timestamp_ms.get_or_insert(snapshot.timestamp_ms);
+            snapshot.timestamp = start
+                + Duration::from_millis(snapshot.timestamp_ms.saturating_sub(first_timestamp_ms));
+            snapshots.push_back(snapshot);
+        }
+        anyhow::ensure!(!snapshots.is_empty(), "The recording is empty");
+
+        Ok(Self {
+            snapshots,
+            last_timestamp_ms: None,
+        })
+    }
+}
+
+impl SnapshotSource for ReplaySource {
+    fn next_snapshot(&mut self) -> Option<SystemSnapshot> {
+        let snapshot = self.snapshots.pop_front()?;
+        self.last_timestamp_ms = Some(snapshot.timestamp_ms);
+        Some(snapshot)
+    }
+
+    /// Returns the recorded gap between the last returned snapshot and the next one,
+    /// the default interval before the first snapshot and after the last one.
+    fn update_interval(&self) -> Duration {
+        match (self.last_timestamp_ms, self.snapshots.front()) {
+            (Some(last_timestamp_ms), Some(next)) => {
+                Duration::from_millis(next.timestamp_ms.saturating_sub(last_timestamp_ms))
+            }
+            _ => Duration::from_secs(1),
+        }
+    }
+}
+
+/// Record the snapshots to a file as JSON lines.
+pub struct Recorder {
+    writer: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Create the recording file, truncate it if it exists.
+    pub fn create(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    /// Append the snapshot as a JSON line.
+    pub fn record(&mut self, snapshot: &SystemSnapshot) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.writer, snapshot)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+
+    use super::{Recorder, ReplaySource, SnapshotSource};
+    use crate::{HostInfo, MemoryInfo, SystemSnapshot};
+
+    fn snapshot(timestamp_ms: u64, global_cpu_usage: f32) -> SystemSnapshot {
+        SystemSnapshot {
+            timestamp: Instant::now(),
+            timestamp_ms,
+            processes: vec![],
+            cpus: vec![],
+            memory: MemoryInfo {
+                total: 0,
+                used: 0,
+                available: 0,
+                swap_total: 0,
+                swap_used: 0,
+            },
+            disks: vec![],
+            networks: vec![],
+            global_cpu_usage,
+            uptime_secs: 0,
+            boot_time: 0,
+            components: vec![],
+            battery: None,
+            load_average: None,
+            gpus: vec![],
+            host: HostInfo::default(),
+        }
+    }
+
+    /// Returns a path in the temp dir unique to the test.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("monitor-{}-{}.jsonl", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let path = temp_path("record-and-replay");
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder.record(&snapshot(10_000, 20.)).unwrap();
+        recorder.record(&snapshot(10_250, 80.)).unwrap();
+        drop(recorder);
+
+        let mut source = ReplaySource::open(&path).unwrap();
+        _ = fs::remove_file(&path);
+
+        let first = source.next_snapshot().unwrap();
+        assert_eq!(first.timestamp_ms, 10_000);
+        assert_eq!(first.global_cpu_usage, 20.);
+        // The replay waits the recorded gap before the next snapshot.
+        assert_eq!(source.update_interval(), Duration::from_millis(250));
+
+        let second = source.next_snapshot().unwrap();
+        assert_eq!(second.timestamp_ms, 10_250);
+        assert_eq!(second.global_cpu_usage, 80.);
+        // The monotonic timestamps are rebased, keeping the recorded interval.
+        assert_eq!(second.timestamp - first.timestamp, Duration::from_millis(250));
+
+        assert_eq!(source.update_interval(), Duration::from_secs(1));
+        assert!(source.next_snapshot().is_none());
+        // A replay can't act on the live system.
+        assert!(source.live_monitor_mut().is_none());
+    }
+
+    #[test]
+    fn test_replay_errors() {
+        let path = temp_path("empty");
+        fs::write(&path, "\n").unwrap();
+        let err = ReplaySource::open(&path).err().unwrap();
+        _ = fs::remove_file(&path);
+        assert_eq!(err.to_string(), "The recording is empty");
+
+        let path = temp_path("malformed");
+        let line = serde_json::to_string(&snapshot(0, 0.)).unwrap();
+        fs::write(&path, format!("{}\n{{not json}}\n", line)).unwrap();
+        let result = ReplaySource::open(&path);
+        _ = fs::remove_file(&path);
+        assert!(result.is_err());
+
+        assert!(ReplaySource::open(temp_path("missing")).is_err());
+    }
+}
@@ -0,0 +1,848 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use sysinfo::{
+    Components, Disks, Networks, Pid, Process, ProcessStatus, ProcessesToUpdate, System, Uid, Users,
+};
+
+use crate::battery::{BatteryInfo, BatteryReader};
+use crate::gpu::{GpuInfo, GpuReader};
+
+pub use sysinfo::Signal;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    #[serde(default)]
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    /// The name of the user owning the process, `None` if it can't be resolved.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// The command line arguments, empty if unavailable (e.g. kernel threads).
+    #[serde(default)]
+    pub cmd: Vec<String>,
+    /// The path of the executable, `None` if unavailable.
+    #[serde(default)]
+    pub exe: Option<PathBuf>,
+    /// The process status, e.g. "Run", "Sleep" or "Zombie".
+    #[serde(default)]
+    pub status: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub disk_usage: u64,
+    /// The time the process started, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub start_time: u64,
+    /// The time the process has been running, in seconds.
+    #[serde(default)]
+    pub run_time: u64,
+    /// The number of threads, `None` if the platform doesn't report them (only Linux does).
+    #[serde(default)]
+    pub threads: Option<usize>,
+    /// Whether the usage of the process could be read, see [`ProcessInfo::looks_accessible`].
+    #[serde(default = "default_accessible")]
+    pub accessible: bool,
+}
+
+fn default_accessible() -> bool {
+    true
+}
+
+impl ProcessInfo {
+    /// Returns true if the data of the process looks readable, false if the OS likely
+    /// denied access to it, e.g. a process of another user without admin rights.
+    ///
+    /// The OS doesn't report a permission error, the data of the process is just empty,
+    /// so this is a guess:
+    /// - On Linux `/proc` exposes the name, memory and command of every process, only the
+    ///   path needs permission, so a process is inaccessible only if it has no name.
+    ///   Kernel threads and zombies have no memory or command, but are accessible.
+    /// - On macOS and Windows the protected processes (e.g. `csrss.exe` or the processes of
+    ///   other users) have no memory, command and path, and report 0% CPU.
+    pub fn looks_accessible(&self) -> bool {
+        if self.name.is_empty() {
+            return false;
+        }
+        cfg!(target_os = "linux")
+            || self.memory > 0
+            || !self.cmd.is_empty()
+            || self.exe.is_some()
+    }
+
+    /// Returns true if the process has exited but not been reaped by its parent.
+    pub fn is_zombie(&self) -> bool {
+        self.status == ProcessStatus::Zombie.to_string()
+    }
+
+    /// Returns true if ending the process may crash or log out the system,
+    /// e.g. `init`, `launchd` or `csrss.exe`.
+    pub fn is_system_critical(&self) -> bool {
+        const CRITICAL_NAMES: &[&str] = &[
+            "init",
+            "systemd",
+            "kthreadd",
+            "launchd",
+            "kernel_task",
+            "WindowServer",
+            "System",
+            "smss.exe",
+            "csrss.exe",
+            "wininit.exe",
+            "winlogon.exe",
+            "services.exe",
+            "lsass.exe",
+        ];
+
+        self.pid <= 1 || CRITICAL_NAMES.contains(&self.name.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuInfo {
+    pub usage: f32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    /// The total swap space, 0 if no swap is configured.
+    #[serde(default)]
+    pub swap_total: u64,
+    #[serde(default)]
+    pub swap_used: u64,
+}
+
+impl MemoryInfo {
+    /// Returns the used share of the memory in percent, 0 if the total is unknown.
+    pub fn used_percent(&self) -> f64 {
+        percent(self.used, self.total)
+    }
+
+    /// Returns the used share of the swap in percent, 0 if no swap is configured.
+    pub fn swap_percent(&self) -> f64 {
+        percent(self.swap_used, self.swap_total)
+    }
+}
+
+pub(crate) fn percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.
+    } else {
+        used as f64 / total as f64 * 100.
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub name: String,
+    pub total: u64,
+    pub available: u64,
+    /// The bytes read per second since the previous refresh.
+    #[serde(default)]
+    pub read_bytes: u64,
+    /// The bytes written per second since the previous refresh.
+    #[serde(default)]
+    pub written_bytes: u64,
+}
+
+/// The total I/O bytes of a disk at a refresh, to compute the rates.
+#[derive(Debug, Clone, Copy)]
+struct DiskSample {
+    total_read_bytes: u64,
+    total_written_bytes: u64,
+    at: Instant,
+}
+
+/// The I/O rates of a disk in bytes per second.
+#[derive(Debug, Default, Clone, Copy)]
+struct DiskRate {
+    read_bytes: u64,
+    written_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub interface: String,
+    pub received: u64,
+    pub transmitted: u64,
+}
+
+/// A hardware sensor, e.g. a CPU package or a drive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentInfo {
+    /// The sensor label, e.g. "coretemp Package id 0".
+    pub label: String,
+    /// The temperature in °C, `None` if the sensor can't be read.
+    pub temperature: Option<f32>,
+    /// The highest temperature in °C reported by the sensor.
+    pub max: Option<f32>,
+    /// The temperature in °C at which the hardware may throttle or shut down.
+    pub critical: Option<f32>,
+}
+
+impl ComponentInfo {
+    /// Returns true if the sensor likely measures a CPU, guessed from its label.
+    pub fn is_cpu(&self) -> bool {
+        const CPU_LABELS: &[&str] = &["cpu", "core", "package", "tctl", "tdie", "k10temp"];
+
+        let label = self.label.to_lowercase();
+        CPU_LABELS.iter().any(|name| label.contains(name))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    /// The monotonic time of the snapshot, not serialized.
+    ///
+    /// A deserialized snapshot gets the time it was deserialized.
+    #[serde(skip, default = "Instant::now")]
+    pub timestamp: Instant,
+    /// The milliseconds since the Unix epoch when the snapshot was taken.
+    pub timestamp_ms: u64,
+    pub processes: Vec<ProcessInfo>,
+    pub cpus: Vec<CpuInfo>,
+    pub memory: MemoryInfo,
+    pub disks: Vec<DiskInfo>,
+    pub networks: Vec<NetworkInfo>,
+    pub global_cpu_usage: f32,
+    /// The seconds since the system booted.
+    #[serde(default)]
+    pub uptime_secs: u64,
+    /// The seconds since the Unix epoch when the system booted.
+    #[serde(default)]
+    pub boot_time: u64,
+    /// The hardware sensors, empty if the platform reports none.
+    #[serde(default)]
+    pub components: Vec<ComponentInfo>,
+    /// The battery status, `None` on desktops.
+    #[serde(default)]
+    pub battery: Option<BatteryInfo>,
+    /// The 1, 5 and 15 minutes load averages, `None` on Windows.
+    #[serde(default)]
+    pub load_average: Option<(f64, f64, f64)>,
+    /// The GPUs, empty without the `gpu` feature or a supported GPU.
+    #[serde(default)]
+    pub gpus: Vec<GpuInfo>,
+    /// The OS, kernel and host name of the machine the snapshot was taken on.
+    #[serde(default)]
+    pub host: HostInfo,
+}
+
+impl SystemSnapshot {
+    /// Returns the temperature of the hottest CPU sensor in °C, `None` if there is none.
+    pub fn cpu_temperature(&self) -> Option<f32> {
+        self.components
+            .iter()
+            .filter(|component| component.is_cpu())
+            .filter_map(|component| component.temperature)
+            .reduce(f32::max)
+    }
+}
+
+/// The identity of the host, which rarely changes, see [`SystemMonitor::host_info`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostInfo {
+    /// The OS name, e.g. "Ubuntu" or "Windows".
+    pub os_name: Option<String>,
+    pub os_version: Option<String>,
+    pub kernel_version: Option<String>,
+    pub host_name: Option<String>,
+}
+
+impl HostInfo {
+    /// Returns the OS name and version, e.g. "Ubuntu 24.04", `None` if both are unknown.
+    pub fn long_os_version(&self) -> Option<String> {
+        match (&self.os_name, &self.os_version) {
+            (Some(name), Some(version)) => Some(format!("{} {}", name, version)),
+            (name, version) => name.clone().or_else(|| version.clone()),
+        }
+    }
+}
+
+pub struct SystemMonitor {
+    sys: System,
+    networks: Networks,
+    disks: Disks,
+    components: Components,
+    battery: BatteryReader,
+    gpu: GpuReader,
+    /// The users of the system, to resolve the owner of the processes.
+    users: Users,
+    /// The uids that could not be resolved after the last reload of the users,
+    /// e.g. in a container or of a deleted user, so they don't reload the users again.
+    unknown_uids: HashSet<Uid>,
+    /// The identity of this machine, fetched once, see [`SystemMonitor::host_info`].
+    host: HostInfo,
+    /// The previous I/O sample of each disk, keyed by disk name.
+    disk_samples: HashMap<String, DiskSample>,
+    disk_rates: HashMap<String, DiskRate>,
+    last_update: Instant,
+    update_interval: Duration,
+}
+
+impl SystemMonitor {
+    pub fn new() -> Self {
+        let mut sys = System::new_all();
+        sys.refresh_all();
+
+        let mut monitor = Self {
+            sys,
+            networks: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
+            battery: BatteryReader::new(),
+            gpu: GpuReader::new(),
+            users: Users::new_with_refreshed_list(),
+            unknown_uids: HashSet::new(),
+            host: Self::host_info(),
+            disk_samples: HashMap::new(),
+            disk_rates: HashMap::new(),
+            last_update: Instant::now(),
+            update_interval: Duration::from_millis(1000),
+        };
+        monitor.update_disk_rates();
+        monitor
+    }
+
+    /// Returns the OS, kernel and host name of this machine.
+    ///
+    /// These rarely change, so they are fetched once by [`SystemMonitor::new`],
+    /// and copied into each [`SystemSnapshot::host`].
+    pub fn host_info() -> HostInfo {
+        HostInfo {
+            os_name: System::name(),
+            os_version: System::os_version(),
+            kernel_version: System::kernel_version(),
+            host_name: System::host_name(),
+        }
+    }
+
+    /// Returns the minimum interval between two refreshes.
+    pub fn update_interval(&self) -> Duration {
+        self.update_interval
+    }
+
+    /// Set the minimum interval between two refreshes, default is 1s.
+    ///
+    /// [`Self::update`] is a no-op until the interval has elapsed since the last refresh.
+    pub fn set_update_interval(&mut self, interval: Duration) {
+        self.update_interval = interval;
+    }
+
+    pub fn update(&mut self) {
+        if self.last_update.elapsed() < self.update_interval {
+            return;
+        }
+
+        self.sys.refresh_all();
+        self.networks.refresh(true);
+        self.disks.refresh(true);
+        self.components.refresh(true);
+        self.battery.refresh();
+        self.update_disk_rates();
+        self.update_users();
+        self.last_update = Instant::now();
+    }
+
+    /// Reload the users if a process is owned by a new unknown user, e.g. a newly created user.
+    ///
+    /// The uids still unknown after the reload are remembered, so they only reload
+    /// the users once instead of on every refresh.
+    fn update_users(&mut self) {
+        let unknown_uids = |users: &Users, processes: &HashMap<Pid, Process>| {
+            processes
+                .values()
+                .filter_map(|process| process.user_id())
+                .filter(|uid| users.get_user_by_id(uid).is_none())
+                .cloned()
+                .collect::<HashSet<_>>()
+        };
+
+        let uids = unknown_uids(&self.users, self.sys.processes());
+        if uids.is_subset(&self.unknown_uids) {
+            // Forget the uids of the exited processes.
+            self.unknown_uids = uids;
+            return;
+        }
+
+        self.users = Users::new_with_refreshed_list();
+        self.unknown_uids = unknown_uids(&self.users, self.sys.processes());
+    }
+
+    /// Compute the per-second I/O rates of each disk from the previous sample.
+    fn update_disk_rates(&mut self) {
+        let now = Instant::now();
+        let mut samples = HashMap::with_capacity(self.disks.len());
+        self.disk_rates.clear();
+
+        for disk in self.disks.iter() {
+            let name = disk.name().to_string_lossy().to_string();
+            let usage = disk.usage();
+            let sample = DiskSample {
+                total_read_bytes: usage.total_read_bytes,
+                total_written_bytes: usage.total_written_bytes,
+                at: now,
+            };
+
+            if let Some(prev) = self.disk_samples.get(&name) {
+                let secs = now.duration_since(prev.at).as_secs_f64();
+                if secs > 0. {
+                    let rate = |total: u64, prev_total: u64| {
+                        (total.saturating_sub(prev_total) as f64 / secs) as u64
+                    };
+                    self.disk_rates.insert(
+                        name.clone(),
+                        DiskRate {
+                            read_bytes: rate(sample.total_read_bytes, prev.total_read_bytes),
+                            written_bytes: rate(
+                                sample.total_written_bytes,
+                                prev.total_written_bytes,
+                            ),
+                        },
+                    );
+                }
+            }
+            samples.insert(name, sample);
+        }
+
+        // Drop the samples of the removed disks.
+        self.disk_samples = samples;
+    }
+
+    pub fn snapshot(&self) -> SystemSnapshot {
+        let processes = self.sys.processes()
+            .iter()
+            .map(|(pid, process)| {
+                let mut info = ProcessInfo {
+                    pid: pid.as_u32(),
+                    parent_pid: process.parent().map(|pid| pid.as_u32()),
+                    name: process.name().to_string_lossy().to_string(),
+                    user: process
+                        .user_id()
+                        .and_then(|uid| self.users.get_user_by_id(uid))
+                        .map(|user| user.name().to_string()),
+                    cmd: process
+                        .cmd()
+                        .iter()
+                        .map(|arg| arg.to_string_lossy().to_string())
+                        .collect(),
+                    exe: process.exe().map(|path| path.to_path_buf()),
+                    status: process.status().to_string(),
+                    cpu_usage: process.cpu_usage(),
+                    memory: process.memory(),
+                    disk_usage: process.disk_usage().written_bytes,
+                    start_time: process.start_time(),
+                    run_time: process.run_time(),
+                    threads: process.tasks().map(|tasks| tasks.len()),
+                    accessible: true,
+                };
+                info.accessible = info.looks_accessible();
+                info
+            })
+            .collect();
+
+        let cpus = self.sys.cpus()
+            .iter()
+            .map(|cpu| CpuInfo {
+                usage: cpu.cpu_usage(),
+                name: cpu.name().to_string(),
+            })
+            .collect();
+
+        let memory = MemoryInfo {
+            total: self.sys.total_memory(),
+            used: self.sys.used_memory(),
+            available: self.sys.available_memory(),
+            swap_total: self.sys.total_swap(),
+            swap_used: self.sys.used_swap(),
+        };
+
+        let disks = self.disks.iter()
+            .map(|disk| {
+                let name = disk.name().to_string_lossy().to_string();
+                let rate = self.disk_rates.get(&name).copied().unwrap_or_default();
+                DiskInfo {
+                    name,
+                    total: disk.total_space(),
+                    available: disk.available_space(),
+                    read_bytes: rate.read_bytes,
+                    written_bytes: rate.written_bytes,
+                }
+            })
+            .collect();
+
+        let networks = self.networks.iter()
+            .map(|(interface, data)| NetworkInfo {
+                interface: interface.to_string(),
+                received: data.total_received(),
+                transmitted: data.total_transmitted(),
+            })
+            .collect();
+
+        let components = self.components.iter()
+            .map(|component| ComponentInfo {
+                label: component.label().to_string(),
+                temperature: component.temperature(),
+                max: component.max(),
+                critical: component.critical(),
+            })
+            .collect();
+
+        SystemSnapshot {
+            timestamp: Instant::now(),
+            timestamp_ms: epoch_millis(),
+            processes,
+            cpus,
+            memory,
+            disks,
+            networks,
+            global_cpu_usage: self.sys.global_cpu_usage(),
+            uptime_secs: System::uptime(),
+            boot_time: System::boot_time(),
+            components,
+            battery: self.battery.info(),
+            load_average: load_average(),
+            gpus: self.gpu.info(),
+            host: self.host.clone(),
+        }
+    }
+
+    /// Returns the current [`Self::snapshot`] as a single line of JSON, e.g. to export metrics.
+    ///
+    /// The monotonic `timestamp` is skipped, the wall-clock `timestamp_ms` identifies the
+    /// snapshot instead.
+    pub fn snapshot_json(&self) -> String {
+        serde_json::to_string(&self.snapshot()).expect("a snapshot is always serializable")
+    }
+
+    /// Kill the process with the `pid` by [`Signal::Kill`].
+    ///
+    /// Returns `Ok(false)` if the process no longer exists, or `Err` with a readable message
+    /// if the signal could not be sent, e.g. the OS denied permission.
+    pub fn kill_process(&mut self, pid: u32) -> Result<bool, String> {
+        kill(&mut self.sys, pid, Signal::Kill)
+    }
+
+    /// Send the `signal` to the process with the `pid`, see [`Self::kill_process`].
+    ///
+    /// Returns `Err` if the signal is not supported on this platform.
+    pub fn kill_process_with(&mut self, pid: u32, signal: Signal) -> Result<bool, String> {
+        kill(&mut self.sys, pid, signal)
+    }
+
+    pub fn get_process_count(&self) -> usize {
+        self.sys.processes().len()
+    }
+
+    pub fn get_cpu_count(&self) -> usize {
+        self.sys.cpus().len()
+    }
+}
+
+impl Default for SystemMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the load averages, Windows has no load average and reports zeros.
+fn load_average() -> Option<(f64, f64, f64)> {
+    if cfg!(windows) {
+        return None;
+    }
+
+    let load = System::load_average();
+    Some((load.one, load.five, load.fifteen))
+}
+
+fn kill(sys: &mut System, pid: u32, signal: Signal) -> Result<bool, String> {
+    let pid = Pid::from_u32(pid);
+    sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    let Some(process) = sys.process(pid) else {
+        return Ok(false);
+    };
+
+    match process.kill_with(signal) {
+        Some(true) => Ok(true),
+        // sysinfo doesn't tell why the signal failed, e.g. permission denied or a protected process.
+        Some(false) => Err(format!(
+            "Failed to end {} (PID {}), the signal could not be sent.",
+            process.name().to_string_lossy(),
+            pid
+        )),
+        None => Err(format!("The signal {:?} is not supported on this platform.", signal)),
+    }
+}
+
+/// Returns the pids of all the descendants of the process with the `pid`.
+pub fn descendants(processes: &[ProcessInfo], pid: u32) -> Vec<u32> {
+    let mut result = vec![];
+    let mut parents = vec![pid];
+    while let Some(parent) = parents.pop() {
+        for process in processes {
+            if process.parent_pid == Some(parent)
+                && process.pid != pid
+                && !result.contains(&process.pid)
+            {
+                result.push(process.pid);
+                parents.push(process.pid);
+            }
+        }
+    }
+    result
+}
+
+/// Returns the milliseconds since the Unix epoch.
+pub fn epoch_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Format a duration in seconds as e.g. "3d 4h 12m", or "42s" under a minute.
+pub fn format_duration(secs: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = MINUTE * 60;
+    const DAY: u64 = HOUR * 24;
+
+    let (days, hours, minutes) = (secs / DAY, secs % DAY / HOUR, secs % HOUR / MINUTE);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// The unit system of [`format_bytes_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Unit {
+    /// 1024-based, labelled KiB, MiB, GiB and TiB.
+    #[default]
+    Binary,
+    /// 1000-based, labelled KB, MB, GB and TB.
+    Si,
+}
+
+impl Unit {
+    fn base(self) -> f64 {
+        match self {
+            Self::Binary => 1024.,
+            Self::Si => 1000.,
+        }
+    }
+
+    fn labels(self) -> [&'static str; 4] {
+        match self {
+            Self::Binary => ["KiB", "MiB", "GiB", "TiB"],
+            Self::Si => ["KB", "MB", "GB", "TB"],
+        }
+    }
+}
+
+/// Format the bytes in the unit system with `precision` decimals, e.g. "1.5 GiB".
+///
+/// Less than one kilobyte is formatted without decimals, e.g. "512 B".
+pub fn format_bytes_with(bytes: u64, unit: Unit, precision: usize) -> String {
+    let base = unit.base();
+    let mut value = bytes as f64;
+    let mut label = None;
+    for next in unit.labels() {
+        if value < base {
+            break;
+        }
+        value /= base;
+        label = Some(next);
+    }
+
+    match label {
+        Some(label) => format!("{:.*} {}", precision, value, label),
+        None => format!("{} B", bytes),
+    }
+}
+
+/// Format the bytes in binary units with 2 decimals, e.g. "1.50 GiB".
+pub fn format_bytes(bytes: u64) -> String {
+    format_bytes_with(bytes, Unit::Binary, 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        ComponentInfo, MemoryInfo, ProcessInfo, SystemMonitor, Unit, descendants, format_bytes,
+        format_bytes_with, format_duration,
+    };
+
+    fn process(pid: u32, parent_pid: Option<u32>) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent_pid,
+            name: format!("process-{pid}"),
+            user: None,
+            cmd: vec![],
+            exe: None,
+            status: "Run".to_string(),
+            cpu_usage: 0.,
+            memory: 0,
+            disk_usage: 0,
+            start_time: 0,
+            run_time: 0,
+            threads: None,
+            accessible: true,
+        }
+    }
+
+    #[test]
+    fn test_update_interval() {
+        let mut monitor = SystemMonitor::new();
+        monitor.set_update_interval(Duration::from_secs(3600));
+        assert_eq!(monitor.update_interval(), Duration::from_secs(3600));
+
+        let last_update = monitor.last_update;
+        monitor.update();
+        assert_eq!(monitor.last_update, last_update);
+
+        monitor.set_update_interval(Duration::ZERO);
+        monitor.update();
+        assert!(monitor.last_update > last_update);
+    }
+
+    #[test]
+    fn test_swap_percent() {
+        let mut memory = MemoryInfo {
+            total: 8,
+            used: 2,
+            available: 6,
+            swap_total: 4,
+            swap_used: 1,
+        };
+        assert_eq!(memory.used_percent(), 25.);
+        assert_eq!(memory.swap_percent(), 25.);
+
+        // No swap configured.
+        memory.swap_total = 0;
+        memory.swap_used = 0;
+        assert_eq!(memory.swap_percent(), 0.);
+    }
+
+    #[test]
+    fn test_component_is_cpu() {
+        let component = |label: &str| ComponentInfo {
+            label: label.to_string(),
+            temperature: None,
+            max: None,
+            critical: None,
+        };
+
+        assert!(component("coretemp Package id 0").is_cpu());
+        assert!(component("k10temp Tctl").is_cpu());
+        assert!(component("CPU Proximity").is_cpu());
+        assert!(!component("nvme Composite").is_cpu());
+        assert!(!component("acpitz temp1").is_cpu());
+    }
+
+    #[test]
+    fn test_looks_accessible() {
+        let mut process = ProcessInfo {
+            name: "csrss.exe".to_string(),
+            ..process(42, None)
+        };
+        // Linux reports no memory or command for the kernel threads only.
+        assert_eq!(process.looks_accessible(), cfg!(target_os = "linux"));
+
+        process.memory = 1024;
+        assert!(process.looks_accessible());
+
+        process.name.clear();
+        assert!(!process.looks_accessible());
+    }
+
+    #[test]
+    fn test_descendants() {
+        // 1 ─┬─ 2 ─── 4 ─── 5
+        //    └─ 3
+        // 6 ─── 7
+        let processes = vec![
+            process(1, None),
+            process(2, Some(1)),
+            process(3, Some(1)),
+            process(4, Some(2)),
+            process(5, Some(4)),
+            process(6, None),
+            process(7, Some(6)),
+        ];
+
+        let mut pids = descendants(&processes, 1);
+        pids.sort();
+        assert_eq!(pids, vec![2, 3, 4, 5]);
+        assert_eq!(descendants(&processes, 4), vec![5]);
+
+        // No children, or an unknown PID.
+        assert!(descendants(&processes, 3).is_empty());
+        assert!(descendants(&processes, 100).is_empty());
+    }
+
+    #[test]
+    fn test_descendants_cycle() {
+        // A reused PID can make a process its own ancestor, e.g. 1 -> 2 -> 3 -> 1.
+        let processes = vec![process(1, Some(3)), process(2, Some(1)), process(3, Some(2))];
+
+        let mut pids = descendants(&processes, 1);
+        pids.sort();
+        assert_eq!(pids, vec![2, 3]);
+
+        // A process that is its own parent.
+        let processes = vec![process(1, Some(1)), process(2, Some(1))];
+        assert_eq!(descendants(&processes, 1), vec![2]);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(60), "1m");
+        assert_eq!(format_duration(3 * 3600 + 5 * 60 + 9), "3h 5m");
+        assert_eq!(format_duration(3 * 86400 + 4 * 3600 + 12 * 60), "3d 4h 12m");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.00 GiB");
+        assert_eq!(format_bytes(u64::MAX), "16777216.00 TiB");
+
+        assert_eq!(format_bytes_with(999, Unit::Si, 1), "999 B");
+        assert_eq!(format_bytes_with(1_500_000, Unit::Si, 1), "1.5 MB");
+        assert_eq!(format_bytes_with(2_000_000_000, Unit::Si, 0), "2 GB");
+        assert_eq!(format_bytes_with(1_500_000, Unit::Binary, 1), "1.4 MiB");
+    }
+
+    #[test]
+    fn test_snapshot_json() {
+        let monitor = SystemMonitor::new();
+        let json: serde_json::Value = serde_json::from_str(&monitor.snapshot_json()).unwrap();
+        assert!(json["timestamp_ms"].as_u64().is_some_and(|ms| ms > 0));
+        assert!(json.get("timestamp").is_none());
+        assert!(json["processes"].is_array());
+    }
+
+    #[test]
+    fn test_kill_missing_process() {
+        let mut monitor = SystemMonitor::new();
+        assert_eq!(monitor.kill_process(u32::MAX), Ok(false));
+    }
+}
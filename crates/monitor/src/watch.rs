@@ -0,0 +1,83 @@
+//! The monitoring loop on a background thread, for CLI tools or servers without a UI.
+use std::{
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::system_monitor::{SystemMonitor, SystemSnapshot};
+
+/// The handle of a loop started by [`SystemMonitor::watch`].
+///
+/// Dropping the handle stops the loop without waiting for it.
+pub struct WatchHandle {
+    stop: Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl WatchHandle {
+    /// Stop the loop and wait for the current callback to return.
+    pub fn stop(self) {
+        // The loop may have exited already, if the callback panicked.
+        _ = self.stop.send(());
+        _ = self.thread.join();
+    }
+}
+
+impl SystemMonitor {
+    /// Refresh the system every `interval` on a background thread and pass each snapshot
+    /// to the `callback`, until [`WatchHandle::stop`] is called.
+    ///
+    /// The first snapshot is taken after one interval, so the CPU usage is measured.
+    pub fn watch(
+        interval: Duration,
+        mut callback: impl FnMut(SystemSnapshot) + Send + 'static,
+    ) -> WatchHandle {
+        let (stop, stopped) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("system-monitor".to_string())
+            .spawn(move || {
+                let mut monitor = SystemMonitor::new();
+                // The loop paces the refreshes itself.
+                monitor.set_update_interval(Duration::ZERO);
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    monitor.update();
+                    callback(monitor.snapshot());
+                }
+            })
+            .expect("failed to spawn the monitoring thread");
+
+        WatchHandle { stop, thread }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_watch_stop() {
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let handle = SystemMonitor::watch(Duration::from_millis(10), {
+            let ticks = ticks.clone();
+            move |snapshot| {
+                assert!(snapshot.timestamp_ms > 0);
+                ticks.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        while ticks.load(Ordering::SeqCst) == 0 {
+            thread::sleep(Duration::from_millis(10));
+        }
+        handle.stop();
+
+        let stopped_at = ticks.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(ticks.load(Ordering::SeqCst), stopped_at);
+    }
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use monitor::{SystemSnapshot, format_bytes};
+
+/// The condition of an [`AlertRule`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// The global CPU usage is above the percent.
+    CpuAbove { percent: f32 },
+    /// The used memory is above the percent of the total memory.
+    MemoryAbove { percent: f32 },
+    /// The memory of any process with the name is above the bytes.
+    ProcessMemoryAbove { name: String, bytes: u64 },
+}
+
+/// A threshold alert, fired when the condition holds for `duration_secs`.
+///
+/// After fired, the rule will not fire again until the condition clears
+/// and `cooldown_secs` has elapsed, so a flapping metric does not spam.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub condition: AlertCondition,
+    #[serde(default)]
+    pub duration_secs: u64,
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_cooldown_secs() -> u64 {
+    60
+}
+
+/// A fired alert.
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub title: String,
+    pub message: String,
+}
+
+impl Alert {
+    /// Show the alert as a desktop notification, by the notification center of the OS.
+    ///
+    /// This blocks until the OS accepted the notification, so call it in the background.
+    pub fn show_desktop_notification(&self) -> anyhow::Result<()> {
+        notify_rust::Notification::new()
+            .appname("Task Manager")
+            .summary(&self.title)
+            .body(&self.message)
+            .show()?;
+        Ok(())
+    }
+}
+
+impl AlertRule {
+    pub fn new(condition: AlertCondition) -> Self {
+        Self {
+            condition,
+            duration_secs: 0,
+            cooldown_secs: default_cooldown_secs(),
+        }
+    }
+
+    /// Set how long the condition must hold before the alert fires.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration_secs = duration.as_secs();
+        self
+    }
+
+    /// The default rules: CPU > 90% for 10s and memory > 95% for 10s.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new(AlertCondition::CpuAbove { percent: 90. }).duration(Duration::from_secs(10)),
+            Self::new(AlertCondition::MemoryAbove { percent: 95. })
+                .duration(Duration::from_secs(10)),
+        ]
+    }
+
+    fn is_match(&self, snapshot: &SystemSnapshot) -> bool {
+        match &self.condition {
+            AlertCondition::CpuAbove { percent } => snapshot.global_cpu_usage > *percent,
+            AlertCondition::MemoryAbove { percent } => snapshot.memory.used_percent() > *percent as f64,
+            AlertCondition::ProcessMemoryAbove { name, bytes } => snapshot
+                .processes
+                .iter()
+                .any(|p| p.name == *name && p.memory > *bytes),
+        }
+    }
+
+    fn alert(&self, snapshot: &SystemSnapshot) -> Alert {
+        match &self.condition {
+            AlertCondition::CpuAbove { percent } => Alert {
+                title: "High CPU usage".into(),
+                message: format!(
+                    "CPU usage is {:.0}%, above {:.0}% for {}s.",
+                    snapshot.global_cpu_usage, percent, self.duration_secs
+                ),
+            },
+            AlertCondition::MemoryAbove { percent } => Alert {
+                title: "High memory usage".into(),
+                message: format!(
+                    "Memory usage is {:.0}%, above {:.0}% for {}s.",
+                    snapshot.memory.used_percent(),
+                    percent,
+                    self.duration_secs
+                ),
+            },
+            AlertCondition::ProcessMemoryAbove { name, bytes } => {
+                let memory = snapshot
+                    .processes
+                    .iter()
+                    .filter(|p| p.name == *name)
+                    .map(|p| p.memory)
+                    .max()
+                    .unwrap_or_default();
+                Alert {
+                    title: format!("{} is using too much memory", name),
+                    message: format!(
+                        "{} is using {}, above {}.",
+                        name,
+                        format_bytes(memory),
+                        format_bytes(*bytes)
+                    ),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct RuleState {
+    /// The time the condition started to hold.
+    matched_since: Option<Instant>,
+    /// Whether the alert has fired since the condition started to hold.
+    fired: bool,
+    last_fired_at: Option<Instant>,
+}
+
+/// Evaluate the [`AlertRule`]s against each [`SystemSnapshot`].
+pub struct AlertMonitor {
+    rules: Vec<AlertRule>,
+    states: Vec<RuleState>,
+}
+
+impl AlertMonitor {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        let states = vec![RuleState::default(); rules.len()];
+        Self { rules, states }
+    }
+
+    /// Evaluate the rules, returns the alerts fired by this snapshot.
+    pub fn evaluate(&mut self, snapshot: &SystemSnapshot) -> Vec<Alert> {
+        let now = snapshot.timestamp;
+        let mut alerts = vec![];
+
+        for (rule, state) in self.rules.iter().zip(self.states.iter_mut()) {
+            if !rule.is_match(snapshot) {
+                state.matched_since = None;
+                state.fired = false;
+                continue;
+            }
+
+            let since = *state.matched_since.get_or_insert(now);
+            if state.fired || now.duration_since(since).as_secs() < rule.duration_secs {
+                continue;
+            }
+
+            let cooling_down = state.last_fired_at.is_some_and(|last_fired_at| {
+                now.duration_since(last_fired_at).as_secs() < rule.cooldown_secs
+            });
+            if cooling_down {
+                continue;
+            }
+
+            state.fired = true;
+            state.last_fired_at = Some(now);
+            alerts.push(rule.alert(snapshot));
+        }
+
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use monitor::{HostInfo, MemoryInfo, SystemSnapshot};
+
+    use super::{AlertCondition, AlertMonitor, AlertRule};
+
+    fn snapshot(timestamp: Instant, global_cpu_usage: f32) -> SystemSnapshot {
+        SystemSnapshot {
+            timestamp,
+            timestamp_ms: 0,
+            processes: vec![],
+            cpus: vec![],
+            memory: MemoryInfo {
+                total: 0,
+                used: 0,
+                available: 0,
+                swap_total: 0,
+                swap_used: 0,
+            },
+            disks: vec![],
+            networks: vec![],
+            global_cpu_usage,
+            uptime_secs: 0,
+            boot_time: 0,
+            components: vec![],
+            battery: None,
+            load_average: None,
+            gpus: vec![],
+            host: HostInfo::default(),
+        }
+    }
+
+    /// Evaluate the CPU usages at the seconds after the start, returns the seconds that fired.
+    fn fired_at(monitor: &mut AlertMonitor, samples: &[(u64, f32)]) -> Vec<u64> {
+        let start = Instant::now();
+        samples
+            .iter()
+            .filter(|(secs, cpu_usage)| {
+                let now = start + Duration::from_secs(*secs);
+                !monitor.evaluate(&snapshot(now, *cpu_usage)).is_empty()
+            })
+            .map(|(secs, _)| *secs)
+            .collect()
+    }
+
+    fn cpu_rule(duration_secs: u64, cooldown_secs: u64) -> AlertRule {
+        AlertRule {
+            condition: AlertCondition::CpuAbove { percent: 90. },
+            duration_secs,
+            cooldown_secs,
+        }
+    }
+
+    #[test]
+    fn test_shorter_than_duration() {
+        let mut monitor = AlertMonitor::new(vec![cpu_rule(10, 60)]);
+        // The breach clears at 8s, so the duration restarts at 15s.
+        let fired = fired_at(
+            &mut monitor,
+            &[(0, 95.), (5, 95.), (8, 50.), (15, 95.), (20, 95.), (25, 95.)],
+        );
+        assert_eq!(fired, vec![25]);
+    }
+
+    #[test]
+    fn test_sustained() {
+        let mut monitor = AlertMonitor::new(vec![cpu_rule(10, 60)]);
+        // Fires once, and not again while the breach holds, even after the cooldown.
+        let fired = fired_at(
+            &mut monitor,
+            &[(0, 95.), (5, 95.), (10, 95.), (11, 95.), (100, 95.)],
+        );
+        assert_eq!(fired, vec![10]);
+    }
+
+    #[test]
+    fn test_flapping() {
+        let mut monitor = AlertMonitor::new(vec![cpu_rule(0, 60)]);
+        let fired = fired_at(
+            &mut monitor,
+            &[(0, 95.), (1, 50.), (2, 95.), (3, 50.), (4, 95.), (30, 50.), (31, 95.)],
+        );
+        assert_eq!(fired, vec![0]);
+    }
+
+    #[test]
+    fn test_fire_after_cooldown() {
+        let mut monitor = AlertMonitor::new(vec![cpu_rule(0, 60)]);
+        // Fires again when breached after the cooldown, once the previous breach has cleared.
+        let fired = fired_at(
+            &mut monitor,
+            &[(0, 95.), (1, 50.), (59, 95.), (61, 95.), (62, 95.)],
+        );
+        assert_eq!(fired, vec![0, 61]);
+    }
+}
@@ -1,24 +1,34 @@
-use gpui::{Context, div, IntoElement, ParentElement, Render, Styled, Window, prelude::FluentBuilder};
+use gpui::{Context, Div, div, IntoElement, ParentElement, Render, SharedString, Styled, Window, prelude::FluentBuilder, px};
 use gpui_component::{
+    chart::{BarChart, PieChart},
     h_flex, v_flex, ActiveTheme, StyledExt,
+    plot::PlotSized,
     progress::Progress,
 };
 
-use crate::system_monitor::{SystemSnapshot, format_bytes};
+use chrono::{DateTime, Local};
+use monitor::{BatteryInfo, GpuInfo, HostInfo, SystemMonitor, SystemSnapshot, format_bytes, format_duration};
+
+/// The maximum number of core labels drawn under the per-core chart.
+const MAX_CORE_LABELS: usize = 16;
 
 pub struct AppDetailsTab {
     snapshot: Option<SystemSnapshot>,
+    /// The host identity, fetched once since it rarely changes.
+    host_info: HostInfo,
 }
 
 impl AppDetailsTab {
     pub fn new(_cx: &mut Context<Self>) -> Self {
         Self {
             snapshot: None,
+            host_info: SystemMonitor::host_info(),
         }
     }
 
-    pub fn update_snapshot(&mut self, snapshot: SystemSnapshot, _cx: &mut Context<Self>) {
+    pub fn update_snapshot(&mut self, snapshot: SystemSnapshot, cx: &mut Context<Self>) {
         self.snapshot = Some(snapshot);
+        cx.notify();
     }
 
     fn render_info_card(
@@ -26,7 +36,7 @@ impl AppDetailsTab {
         title: String,
         items: Vec<(String, String)>,
         cx: &Context<Self>,
-    ) -> impl IntoElement {
+    ) -> Div {
         v_flex()
             .flex_1()
             .gap_3()
@@ -61,6 +71,110 @@ impl AppDetailsTab {
             }))
     }
 
+    fn render_sensors(&self, snapshot: &SystemSnapshot, cx: &Context<Self>) -> impl IntoElement {
+        let format_temperature =
+            |temperature: Option<f32>| temperature.map(|t| format!("{:.1} °C", t));
+
+        let items = snapshot
+            .components
+            .iter()
+            .map(|component| {
+                let mut value =
+                    format_temperature(component.temperature).unwrap_or_else(|| "—".to_string());
+                if let Some(critical) = format_temperature(component.critical) {
+                    value = format!("{} (critical {})", value, critical);
+                }
+                (component.label.clone(), value)
+            })
+            .collect();
+
+        self.render_info_card("Sensors".to_string(), items, cx)
+    }
+
+    /// The disk card, with a donut chart of the used and free space of each drive.
+    fn render_disks(
+        &self,
+        snapshot: &SystemSnapshot,
+        items: Vec<(String, String)>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let used_color = cx.theme().warning;
+        let free_color = cx.theme().muted;
+
+        self.render_info_card("Disk".to_string(), items, cx).child(
+            h_flex()
+                .flex_wrap()
+                .gap_4()
+                .children(snapshot.disks.iter().map(|disk| {
+                    let used = disk.total.saturating_sub(disk.available);
+                    let percent = if disk.total > 0 {
+                        used as f64 / disk.total as f64 * 100.
+                    } else {
+                        0.
+                    };
+                    let slices = vec![
+                        ("Used", used as f32, used_color),
+                        ("Free", disk.available as f32, free_color),
+                    ];
+
+                    v_flex()
+                        .items_center()
+                        .gap_1()
+                        .child(
+                            PieChart::new(slices)
+                                .value(|(_, value, _)| *value)
+                                .color(|(_, _, color)| *color)
+                                .inner_radius(26.)
+                                .outer_radius(36.)
+                                .center_label(format!("{:.0}%", percent))
+                                .width(px(80.))
+                                .height(px(80.))
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(disk.name.clone())
+                        )
+                })),
+        )
+    }
+
+    fn render_gpus(&self, gpus: &[GpuInfo], cx: &Context<Self>) -> impl IntoElement {
+        let items = gpus
+            .iter()
+            .map(|gpu| {
+                let mut value = format!(
+                    "{:.0}% · {} / {}",
+                    gpu.utilization,
+                    format_bytes(gpu.memory_used),
+                    format_bytes(gpu.memory_total)
+                );
+                if let Some(temperature) = gpu.temperature {
+                    value = format!("{} · {:.0} °C", value, temperature);
+                }
+                (gpu.name.clone(), value)
+            })
+            .collect();
+
+        self.render_info_card("GPU".to_string(), items, cx)
+    }
+
+    fn render_battery(&self, battery: &BatteryInfo, cx: &Context<Self>) -> impl IntoElement {
+        let mut items = vec![
+            ("Charge".to_string(), format!("{:.0}%", battery.charge)),
+            ("State".to_string(), battery.state.to_string()),
+        ];
+        if let Some(secs) = battery.time_to_full {
+            items.push(("Time to full".to_string(), format_duration(secs)));
+        }
+        if let Some(secs) = battery.time_to_empty {
+            items.push(("Time to empty".to_string(), format_duration(secs)));
+        }
+
+        self.render_info_card("Battery".to_string(), items, cx)
+    }
+
     fn render_resource_usage(
         &self,
         label: String,
@@ -105,6 +219,43 @@ impl AppDetailsTab {
                     .child(format!("{} / {}", format_bytes(used), format_bytes(total)))
             )
     }
+
+    fn render_core_usage(&self, snapshot: &SystemSnapshot, cx: &Context<Self>) -> impl IntoElement {
+        let cores: Vec<(SharedString, f64)> = snapshot
+            .cpus
+            .iter()
+            .enumerate()
+            .map(|(ix, cpu)| (SharedString::from(ix.to_string()), cpu.usage as f64))
+            .collect();
+        let tick_margin = cores.len().div_ceil(MAX_CORE_LABELS).max(1);
+        let color = cx.theme().primary;
+
+        v_flex()
+            .gap_4()
+            .p_4()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().background)
+            .child(
+                div()
+                    .text_lg()
+                    .font_semibold()
+                    .child("Per-core Usage")
+            )
+            .child(
+                div()
+                    .h(px(160.0))
+                    .child(
+                        BarChart::new(cores)
+                            .x(|(core, _)| core.clone())
+                            .y(|(_, usage)| *usage)
+                            .fill(move |_| color)
+                            .spacing(0.2)
+                            .tick_margin(tick_margin)
+                    )
+            )
+    }
 }
 
 impl Render for AppDetailsTab {
@@ -119,16 +270,34 @@ impl Render for AppDetailsTab {
                 0.0
             };
 
-            let cpu_items = vec![
+            let mut cpu_items = vec![
                 ("Logical processors".to_string(), cpu_count.to_string()),
                 ("Average usage".to_string(), format!("{:.1}%", avg_cpu)),
                 ("Global usage".to_string(), format!("{:.1}%", snapshot.global_cpu_usage)),
             ];
+            if let Some((one, five, fifteen)) = snapshot.load_average {
+                cpu_items.push((
+                    "Load average".to_string(),
+                    format!("{:.2}, {:.2}, {:.2}", one, five, fifteen),
+                ));
+            }
 
             let memory_items = vec![
                 ("Total".to_string(), format_bytes(snapshot.memory.total)),
                 ("Used".to_string(), format_bytes(snapshot.memory.used)),
                 ("Available".to_string(), format_bytes(snapshot.memory.available)),
+                (
+                    "Swap".to_string(),
+                    if snapshot.memory.swap_total > 0 {
+                        format!(
+                            "{} / {}",
+                            format_bytes(snapshot.memory.swap_used),
+                            format_bytes(snapshot.memory.swap_total)
+                        )
+                    } else {
+                        "Not configured".to_string()
+                    },
+                ),
             ];
 
             let total_disk_space: u64 = snapshot.disks.iter().map(|d| d.total).sum();
@@ -152,6 +321,14 @@ impl Render for AppDetailsTab {
             (vec![], vec![], vec![], vec![])
         };
 
+        let unknown = || "Unknown".to_string();
+        let system_info = vec![
+            ("Host name".to_string(), self.host_info.host_name.clone().unwrap_or_else(unknown)),
+            ("OS".to_string(), self.host_info.os_name.clone().unwrap_or_else(unknown)),
+            ("OS version".to_string(), self.host_info.os_version.clone().unwrap_or_else(unknown)),
+            ("Kernel".to_string(), self.host_info.kernel_version.clone().unwrap_or_else(unknown)),
+        ];
+
         v_flex()
             .size_full()
             .p_4()
@@ -168,20 +345,53 @@ impl Render for AppDetailsTab {
                     .text_color(cx.theme().muted_foreground)
                     .child("System resource summary and information")
             )
+            .when_some(snapshot, |el, snapshot| {
+                let boot_time = DateTime::from_timestamp(snapshot.boot_time as i64, 0)
+                    .map(|time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string());
+
+                el.child(
+                    h_flex()
+                        .gap_2()
+                        .text_sm()
+                        .child(
+                            div()
+                                .font_semibold()
+                                .child(format!("Up {}", format_duration(snapshot.uptime_secs)))
+                        )
+                        .when_some(boot_time, |this, boot_time| {
+                            this.child(
+                                div()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("since {}", boot_time))
+                            )
+                        })
+                )
+            })
             .when(snapshot.is_some(), |el| {
                 let snapshot = snapshot.unwrap();
                 el.child(
                     h_flex()
                         .gap_4()
+                        .child(self.render_info_card("System".to_string(), system_info, cx))
                         .child(self.render_info_card("CPU".to_string(), cpu_info, cx))
                         .child(self.render_info_card("Memory".to_string(), memory_info, cx))
                 )
                 .child(
                     h_flex()
                         .gap_4()
-                        .child(self.render_info_card("Disk".to_string(), disk_info, cx))
+                        .child(self.render_disks(snapshot, disk_info, cx))
                         .child(self.render_info_card("Network".to_string(), network_info, cx))
+                        .when_some(snapshot.battery.as_ref(), |this, battery| {
+                            this.child(self.render_battery(battery, cx))
+                        })
                 )
+                .child(self.render_core_usage(snapshot, cx))
+                .when(!snapshot.components.is_empty(), |el| {
+                    el.child(self.render_sensors(snapshot, cx))
+                })
+                .when(!snapshot.gpus.is_empty(), |el| {
+                    el.child(self.render_gpus(&snapshot.gpus, cx))
+                })
                 .child(
                     v_flex()
                         .gap_4()
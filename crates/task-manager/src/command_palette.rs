@@ -0,0 +1,223 @@
+use gpui::{
+    App, AppContext, Context, Entity, EventEmitter, InteractiveElement, IntoElement, KeyDownEvent,
+    ParentElement, Render, SharedString, StatefulInteractiveElement, Styled, Subscription, Window,
+    div, prelude::FluentBuilder, px,
+};
+use gpui_component::{
+    ActiveTheme, StyledExt, h_flex,
+    input::{Input, InputEvent, InputState},
+    list::ListItem,
+    v_flex,
+};
+
+use monitor::ProcessInfo;
+
+use crate::config::ThemePreference;
+
+const MAX_RESULTS: usize = 8;
+
+/// The target to jump to from the [`CommandPalette`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteTarget {
+    /// Show the processes tab filtered by the process name.
+    Process { pid: u32, name: SharedString },
+    Performance,
+    Network,
+    AppDetails,
+    /// Switch the theme mode.
+    Theme(ThemePreference),
+}
+
+impl PaletteTarget {
+    fn label(&self) -> SharedString {
+        match self {
+            Self::Process { name, .. } => name.clone(),
+            Self::Performance => "Performance: CPU, Memory, Disk and Network".into(),
+            Self::Network => "Network interfaces".into(),
+            Self::AppDetails => "App Details".into(),
+            Self::Theme(ThemePreference::System) => "Theme: System".into(),
+            Self::Theme(ThemePreference::Light) => "Theme: Light".into(),
+            Self::Theme(ThemePreference::Dark) => "Theme: Dark".into(),
+        }
+    }
+
+    fn detail(&self) -> SharedString {
+        match self {
+            Self::Process { pid, .. } => format!("PID {}", pid).into(),
+            Self::Performance | Self::Network | Self::AppDetails => "Tab".into(),
+            Self::Theme(_) => "Appearance".into(),
+        }
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Self::Process { .. } => &[],
+            Self::Performance => &["performance", "cpu", "memory", "disk", "network"],
+            Self::Network => &["network", "interfaces", "throughput"],
+            Self::AppDetails => &["app details", "about"],
+            Self::Theme(ThemePreference::System) => &["theme", "system", "appearance"],
+            Self::Theme(ThemePreference::Light) => &["theme", "light", "appearance"],
+            Self::Theme(ThemePreference::Dark) => &["theme", "dark", "appearance"],
+        }
+    }
+}
+
+pub enum CommandPaletteEvent {
+    Confirm(PaletteTarget),
+}
+
+/// A command palette to search processes, tabs and themes, opened by `cmd-k` / `ctrl-k`.
+pub struct CommandPalette {
+    input: Entity<InputState>,
+    processes: Vec<ProcessInfo>,
+    results: Vec<PaletteTarget>,
+    selected_ix: usize,
+    _subscription: Subscription,
+}
+
+impl EventEmitter<CommandPaletteEvent> for CommandPalette {}
+
+impl CommandPalette {
+    pub fn new(processes: Vec<ProcessInfo>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Search processes, tabs and themes...")
+        });
+        let _subscription = cx.subscribe_in(&input, window, Self::on_input_event);
+
+        let mut this = Self {
+            input,
+            processes,
+            results: vec![],
+            selected_ix: 0,
+            _subscription,
+        };
+        this.update_results(cx);
+        this
+    }
+
+    /// Focus the search input.
+    pub fn focus(&self, window: &mut Window, cx: &mut App) {
+        self.input.update(cx, |input, cx| input.focus(window, cx));
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<InputState>,
+        event: &InputEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change => self.update_results(cx),
+            InputEvent::PressEnter { .. } => self.confirm(self.selected_ix, cx),
+            _ => {}
+        }
+    }
+
+    fn update_results(&mut self, cx: &mut Context<Self>) {
+        let query = self.input.read(cx).value().trim().to_lowercase();
+
+        let tabs = [
+            PaletteTarget::Performance,
+            PaletteTarget::Network,
+            PaletteTarget::AppDetails,
+            PaletteTarget::Theme(ThemePreference::System),
+            PaletteTarget::Theme(ThemePreference::Light),
+            PaletteTarget::Theme(ThemePreference::Dark),
+        ]
+        .into_iter()
+        .filter(|target| {
+            query.is_empty()
+                || target
+                    .keywords()
+                    .iter()
+                    .any(|keyword| keyword.contains(&query))
+        });
+
+        let mut processes = self
+            .processes
+            .iter()
+            .filter(|p| !query.is_empty() && p.name.to_lowercase().contains(&query))
+            .collect::<Vec<_>>();
+        processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+
+        self.results = processes
+            .into_iter()
+            .map(|p| PaletteTarget::Process {
+                pid: p.pid,
+                name: p.name.clone().into(),
+            })
+            .chain(tabs)
+            .take(MAX_RESULTS)
+            .collect();
+        self.selected_ix = 0;
+        cx.notify();
+    }
+
+    fn confirm(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if let Some(target) = self.results.get(ix) {
+            cx.emit(CommandPaletteEvent::Confirm(target.clone()));
+        }
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.results.is_empty() {
+            return;
+        }
+
+        match event.keystroke.key.as_str() {
+            "up" => {
+                self.selected_ix = self
+                    .selected_ix
+                    .checked_sub(1)
+                    .unwrap_or(self.results.len() - 1);
+            }
+            "down" => self.selected_ix = (self.selected_ix + 1) % self.results.len(),
+            _ => return,
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+}
+
+impl Render for CommandPalette {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("command-palette")
+            .gap_2()
+            .capture_key_down(cx.listener(Self::on_key_down))
+            .child(Input::new(&self.input).cleanable(true))
+            .child(
+                v_flex()
+                    .id("results")
+                    .max_h(px(360.))
+                    .overflow_y_scroll()
+                    .when(self.results.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .p_2()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child("No results"),
+                        )
+                    })
+                    .children(self.results.iter().enumerate().map(|(ix, target)| {
+                        ListItem::new(ix)
+                            .selected(ix == self.selected_ix)
+                            .on_click(cx.listener(move |this, _, _, cx| this.confirm(ix, cx)))
+                            .child(
+                                h_flex()
+                                    .w_full()
+                                    .justify_between()
+                                    .child(div().font_medium().child(target.label()))
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(target.detail()),
+                                    ),
+                            )
+                    })),
+            )
+    }
+}
@@ -0,0 +1,166 @@
+use gpui::{App, Bounds, Pixels, WindowBounds, point, px, size};
+use gpui_component::PixelsExt;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::{alerts::AlertRule, processes_tab::DEFAULT_DETAILS_SPLIT_RATIO};
+
+/// The persisted settings of the task manager, stored as JSON in the user config directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub window: Option<WindowConfig>,
+    /// Keep the app running in the system tray when the window is closed.
+    pub minimize_to_tray: bool,
+    /// The threshold alerts, evaluated against each snapshot.
+    pub alerts: Vec<AlertRule>,
+    pub theme: ThemePreference,
+    /// The refresh interval of the live system in milliseconds.
+    pub update_interval_ms: u64,
+    /// The resized widths of the process table columns, keyed by column key.
+    pub column_widths: HashMap<String, f32>,
+    /// The names of the processes pinned to the top of the process table.
+    pub pinned_processes: Vec<String>,
+    /// The width of the process table as a ratio of the tab width when the details panel is open.
+    pub details_split_ratio: f32,
+}
+
+/// The theme mode chosen by the user.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemePreference {
+    /// Follow the OS appearance.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            window: None,
+            minimize_to_tray: false,
+            alerts: AlertRule::defaults(),
+            theme: ThemePreference::default(),
+            update_interval_ms: 1000,
+            column_widths: HashMap::new(),
+            pinned_processes: vec![],
+            details_split_ratio: DEFAULT_DETAILS_SPLIT_RATIO,
+        }
+    }
+}
+
+/// The last window bounds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowConfig {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+impl Config {
+    /// Returns the path of the config file, `None` if the config directory is unknown.
+    pub fn path() -> Option<PathBuf> {
+        let dir = if cfg!(target_os = "windows") {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            std::env::var_os("HOME")
+                .map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+
+        dir.map(|dir| dir.join("task-manager").join("config.json"))
+    }
+
+    /// Load the config, returns the default config if the file is missing or invalid.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                log::warn!("Failed to parse config {}: {}", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the config to the config file.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!("Unknown config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Load, update and save the config, errors are logged.
+    pub fn update(f: impl FnOnce(&mut Self)) {
+        let mut config = Self::load();
+        f(&mut config);
+        if let Err(err) = config.save() {
+            log::warn!("Failed to save config: {}", err);
+        }
+    }
+}
+
+impl WindowConfig {
+    pub fn from_window_bounds(window_bounds: WindowBounds) -> Self {
+        let (bounds, maximized) = match window_bounds {
+            WindowBounds::Windowed(bounds) => (bounds, false),
+            WindowBounds::Maximized(bounds) | WindowBounds::Fullscreen(bounds) => (bounds, true),
+        };
+
+        Self {
+            x: bounds.origin.x.as_f32(),
+            y: bounds.origin.y.as_f32(),
+            width: bounds.size.width.as_f32(),
+            height: bounds.size.height.as_f32(),
+            maximized,
+        }
+    }
+
+    /// Returns the window bounds to restore, clamped to the display that contains the saved origin.
+    ///
+    /// Returns `None` if the saved window is not on any current display (e.g. a disconnected monitor).
+    pub fn window_bounds(&self, min_size: gpui::Size<Pixels>, cx: &App) -> Option<WindowBounds> {
+        let origin = point(px(self.x), px(self.y));
+        let display_bounds = cx
+            .displays()
+            .into_iter()
+            .map(|display| display.bounds())
+            .find(|bounds| bounds.contains(&origin))?;
+
+        let width = px(self.width)
+            .max(min_size.width)
+            .min(display_bounds.size.width);
+        let height = px(self.height)
+            .max(min_size.height)
+            .min(display_bounds.size.height);
+        let x = origin
+            .x
+            .min(display_bounds.right() - width)
+            .max(display_bounds.left());
+        let y = origin
+            .y
+            .min(display_bounds.bottom() - height)
+            .max(display_bounds.top());
+
+        let bounds = Bounds::new(point(x, y), size(width, height));
+        Some(if self.maximized {
+            WindowBounds::Maximized(bounds)
+        } else {
+            WindowBounds::Windowed(bounds)
+        })
+    }
+}
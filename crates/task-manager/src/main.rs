@@ -1,23 +1,35 @@
-mod system_monitor;
+mod alerts;
+mod config;
+mod process_query;
 mod processes_tab;
 mod performance_tab;
 mod app_details_tab;
+mod network_tab;
+mod command_palette;
+mod tray;
 
 use gpui::{
-    actions, Application, App, AppContext, Bounds, Context, div, Entity, IntoElement, KeyBinding,
-    ParentElement, Render, Styled, Task, Window, WindowBounds, WindowOptions, px, size,
-    prelude::FluentBuilder, InteractiveElement,
+    actions, Application, App, AppContext, Bounds, ClipboardItem, Context, div, Entity, IntoElement, KeyBinding,
+    ParentElement, Render, Styled, Subscription, Task, Window, WindowBounds, WindowOptions, px,
+    size, prelude::FluentBuilder, InteractiveElement,
 };
 use gpui_component::{
-    v_flex, tab::{Tab, TabBar}, ActiveTheme, Root, StyledExt,
+    button::{Button, ButtonGroup}, h_flex, v_flex, notification::Notification, switch::Switch,
+    tab::{Tab, TabBar}, ActiveTheme, IconName, Root, Selectable, Sizable, StyledExt, Theme,
+    ThemeMode, WindowExt,
 };
 
-use system_monitor::SystemMonitor;
-use processes_tab::ProcessesTab;
+use alerts::{Alert, AlertMonitor};
+use config::{Config, ThemePreference, WindowConfig};
+use monitor::{LiveSource, Recorder, ReplaySource, SnapshotSource, SystemSnapshot};
+use processes_tab::{ProcessesTab, ProcessesTabEvent};
 use performance_tab::PerformanceTab;
 use app_details_tab::AppDetailsTab;
+use network_tab::NetworkTab;
+use command_palette::{CommandPalette, CommandPaletteEvent, PaletteTarget};
+use tray::{Tray, TrayAction};
 
-actions!(task_manager, [Quit]);
+actions!(task_manager, [Quit, ToggleCommandPalette, CopyReport]);
 
 const CONTEXT: &str = "TaskManager";
 
@@ -25,25 +37,53 @@ const CONTEXT: &str = "TaskManager";
 enum ActiveTab {
     Processes,
     Performance,
+    Network,
     AppDetails,
 }
 
 struct TaskManagerApp {
     active_tab: ActiveTab,
-    monitor: SystemMonitor,
+    source: Box<dyn SnapshotSource>,
+    recorder: Option<Recorder>,
+    alerts: AlertMonitor,
+    /// The latest snapshot.
+    snapshot: SystemSnapshot,
     processes_tab: Entity<ProcessesTab>,
     performance_tab: Entity<PerformanceTab>,
+    network_tab: Entity<NetworkTab>,
     app_details_tab: Entity<AppDetailsTab>,
     update_task: Option<Task<()>>,
+    tray: Option<Tray>,
+    tray_task: Option<Task<()>>,
+    minimize_to_tray: bool,
+    theme: ThemePreference,
+    save_bounds_task: Option<Task<()>>,
+    command_palette: Option<Entity<CommandPalette>>,
+    _command_palette_subscription: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl TaskManagerApp {
-    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let monitor = SystemMonitor::new();
-        let snapshot = monitor.snapshot();
-
+    fn new(
+        mut source: Box<dyn SnapshotSource>,
+        recorder: Option<Recorder>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let config = Config::load();
+        let snapshot = source
+            .next_snapshot()
+            .expect("The snapshot source must have at least one snapshot");
+
+        // A replay shows the processes of another run, never end the live processes from it.
+        let can_end_tasks = source.live_monitor_mut().is_some();
         let processes_tab = cx.new(|cx| {
-            ProcessesTab::new(snapshot.processes.clone(), window, cx)
+            let mut tab = ProcessesTab::new(snapshot.processes.clone(), snapshot.memory.total, window, cx);
+            tab.set_column_widths(&config.column_widths, cx);
+            tab.set_pinned(config.pinned_processes.clone(), cx);
+            tab.set_details_split_ratio(config.details_split_ratio, cx);
+            tab.set_can_end_tasks(can_end_tasks, cx);
+            tab
         });
 
         let performance_tab = cx.new(|cx| {
@@ -52,50 +92,117 @@ impl TaskManagerApp {
             tab
         });
 
+        let network_tab = cx.new(|cx| {
+            let mut tab = NetworkTab::new(window, cx);
+            tab.update_networks(&snapshot.networks, snapshot.timestamp_ms, cx);
+            tab
+        });
+
         let app_details_tab = cx.new(|cx| {
             let mut tab = AppDetailsTab::new(cx);
             tab.update_snapshot(snapshot.clone(), cx);
             tab
         });
 
+        let view = cx.weak_entity();
+        window.on_window_should_close(cx, move |window, cx| {
+            let keep_running = view
+                .upgrade()
+                .is_some_and(|view| view.read(cx).keep_running_in_tray());
+            if keep_running {
+                window.minimize_window();
+            }
+            !keep_running
+        });
+
+        let _subscriptions = vec![
+            cx.subscribe_in(&processes_tab, window, Self::on_processes_tab_event),
+            cx.observe_window_bounds(window, |this, window, cx| {
+                this.save_window_bounds(window, cx);
+            }),
+            cx.observe_window_appearance(window, |this, window, cx| {
+                if this.theme == ThemePreference::System {
+                    Theme::sync_system_appearance(Some(window), cx);
+                }
+            }),
+        ];
+
         let mut app = Self {
             active_tab: ActiveTab::Processes,
-            monitor,
+            source,
+            recorder,
+            alerts: AlertMonitor::new(config.alerts),
+            snapshot: snapshot.clone(),
             processes_tab,
             performance_tab,
+            network_tab,
             app_details_tab,
             update_task: None,
+            tray: Tray::new(),
+            tray_task: None,
+            minimize_to_tray: config.minimize_to_tray,
+            theme: config.theme,
+            save_bounds_task: None,
+            command_palette: None,
+            _command_palette_subscription: None,
+            _subscriptions,
         };
 
-        app.start_monitoring(cx);
+        app.apply_theme(window, cx);
+        app.record_snapshot(&snapshot);
+        if let Some(tray) = &app.tray {
+            tray.update(&snapshot);
+        }
+        app.start_monitoring(window, cx);
+        app.start_tray_events(window, cx);
         app
     }
 
-    fn start_monitoring(&mut self, cx: &mut Context<Self>) {
+    fn start_monitoring(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let processes_tab = self.processes_tab.clone();
         let performance_tab = self.performance_tab.clone();
+        let network_tab = self.network_tab.clone();
         let app_details_tab = self.app_details_tab.clone();
 
-        let task = cx.spawn(async move |this, cx| {
+        let task = cx.spawn_in(window, async move |this, cx| {
             loop {
-                cx.background_executor().timer(std::time::Duration::from_secs(1)).await;
-
-                let _ = this.update(cx, |this, cx| {
-                    this.monitor.update();
-                    let snapshot = this.monitor.snapshot();
+                let Ok(interval) = this.read_with(cx, |this, _| this.source.update_interval()) else {
+                    break;
+                };
+                cx.background_executor().timer(interval).await;
+
+                let _ = this.update_in(cx, |this, window, cx| {
+                    // Keep the last snapshot when the replay ends.
+                    let Some(snapshot) = this.source.next_snapshot() else {
+                        return;
+                    };
+                    this.record_snapshot(&snapshot);
+                    if let Some(tray) = &this.tray {
+                        tray.update(&snapshot);
+                    }
+
+                    for alert in this.alerts.evaluate(&snapshot) {
+                        log::warn!("{}: {}", alert.title, alert.message);
+                        this.notify_alert(alert, window, cx);
+                    }
 
                     processes_tab.update(cx, |tab, cx| {
-                        tab.update_processes(snapshot.processes.clone(), cx);
+                        tab.update_processes(snapshot.processes.clone(), snapshot.memory.total, cx);
                     });
 
                     performance_tab.update(cx, |tab, cx| {
                         tab.update_snapshot(snapshot.clone(), cx);
                     });
 
+                    network_tab.update(cx, |tab, cx| {
+                        tab.update_networks(&snapshot.networks, snapshot.timestamp_ms, cx);
+                    });
+
                     app_details_tab.update(cx, |tab, cx| {
                         tab.update_snapshot(snapshot.clone(), cx);
                     });
 
+                    this.snapshot = snapshot;
                     cx.notify();
                 });
             }
@@ -104,26 +211,226 @@ impl TaskManagerApp {
         self.update_task = Some(task);
     }
 
+    /// Show the alert as a desktop notification, or as a toast in the window if the OS
+    /// has no notification center, e.g. no notification daemon is running on Linux.
+    fn notify_alert(&mut self, alert: Alert, window: &mut Window, cx: &mut Context<Self>) {
+        let shown = cx.background_spawn({
+            let alert = alert.clone();
+            async move { alert.show_desktop_notification() }
+        });
+
+        cx.spawn_in(window, async move |_, cx| {
+            if let Err(err) = shown.await {
+                log::warn!("Failed to show the desktop notification: {}", err);
+                _ = cx.update(|window, cx| {
+                    window.push_notification(
+                        Notification::warning(alert.message).title(alert.title),
+                        cx,
+                    );
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Record the snapshot if `--record` is given, stop recording on error.
+    fn record_snapshot(&mut self, snapshot: &SystemSnapshot) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+
+        if let Err(err) = recorder.record(snapshot) {
+            log::error!("Failed to record snapshot: {}", err);
+            self.recorder = None;
+        }
+    }
+
+    /// Returns true if closing the window should keep the app running in the tray.
+    ///
+    /// The monitoring loop keeps running and feeding the tray while the window is minimized,
+    /// use the `Quit` action or the tray menu to exit.
+    fn keep_running_in_tray(&self) -> bool {
+        self.minimize_to_tray && self.tray.is_some()
+    }
+
+    fn set_minimize_to_tray(&mut self, minimize_to_tray: bool, cx: &mut Context<Self>) {
+        self.minimize_to_tray = minimize_to_tray;
+        Config::update(|config| config.minimize_to_tray = minimize_to_tray);
+        cx.notify();
+    }
+
+    fn apply_theme(&self, window: &mut Window, cx: &mut App) {
+        match self.theme {
+            ThemePreference::System => Theme::sync_system_appearance(Some(window), cx),
+            ThemePreference::Light => Theme::change(ThemeMode::Light, Some(window), cx),
+            ThemePreference::Dark => Theme::change(ThemeMode::Dark, Some(window), cx),
+        }
+    }
+
+    fn set_theme(&mut self, theme: ThemePreference, window: &mut Window, cx: &mut Context<Self>) {
+        self.theme = theme;
+        self.apply_theme(window, cx);
+        Config::update(|config| config.theme = theme);
+        cx.notify();
+    }
+
+    /// Poll the tray icon and menu events.
+    fn start_tray_events(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.tray.is_none() {
+            return;
+        }
+
+        self.tray_task = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor().timer(std::time::Duration::from_millis(100)).await;
+
+                let result = this.update_in(cx, |this, window, cx| {
+                    let actions = this.tray.as_ref().map(Tray::poll).unwrap_or_default();
+                    for action in actions {
+                        this.on_tray_action(action, window, cx);
+                    }
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    fn on_tray_action(&mut self, action: TrayAction, window: &mut Window, cx: &mut Context<Self>) {
+        match action {
+            TrayAction::Activate => {
+                window.activate_window();
+                cx.activate(true);
+            }
+            TrayAction::ToggleWindow => {
+                if window.is_window_active() {
+                    window.minimize_window();
+                } else {
+                    window.activate_window();
+                    cx.activate(true);
+                }
+            }
+            TrayAction::Quit => cx.quit(),
+        }
+    }
+
+    /// Save the window bounds after the window stops moving or resizing.
+    fn save_window_bounds(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let window_config = WindowConfig::from_window_bounds(window.window_bounds());
+
+        self.save_bounds_task = Some(cx.spawn(async move |_, cx| {
+            cx.background_executor().timer(std::time::Duration::from_millis(500)).await;
+            Config::update(|config| config.window = Some(window_config));
+        }));
+    }
+
     fn set_active_tab(&mut self, tab: ActiveTab, cx: &mut Context<Self>) {
         self.active_tab = tab;
         cx.notify();
     }
 
+    fn toggle_command_palette(
+        &mut self,
+        _: &ToggleCommandPalette,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.command_palette.take().is_some() {
+            window.close_dialog(cx);
+            return;
+        }
+
+        let processes = self.processes_tab.read(cx).processes(cx);
+        let palette = cx.new(|cx| CommandPalette::new(processes, window, cx));
+        self._command_palette_subscription =
+            Some(cx.subscribe_in(&palette, window, Self::on_command_palette_event));
+        self.command_palette = Some(palette.clone());
+
+        let view = cx.weak_entity();
+        window.open_dialog(cx, {
+            let palette = palette.clone();
+            move |dialog, _, _| {
+                let view = view.clone();
+                dialog
+                    .w(px(560.))
+                    .margin_top(px(80.))
+                    .close_button(false)
+                    .on_close(move |_, _, cx| {
+                        _ = view.update(cx, |this, _| this.command_palette = None);
+                    })
+                    .child(palette.clone())
+            }
+        });
+        // Focus after the dialog opened, the dialog takes the focus when opening.
+        palette.update(cx, |palette, cx| palette.focus(window, cx));
+    }
+
+    fn on_command_palette_event(
+        &mut self,
+        _: &Entity<CommandPalette>,
+        event: &CommandPaletteEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let CommandPaletteEvent::Confirm(target) = event;
+        self.command_palette = None;
+        window.close_dialog(cx);
+
+        match target {
+            PaletteTarget::Process { name, .. } => {
+                self.processes_tab.update(cx, |tab, cx| {
+                    tab.set_search(name.clone(), window, cx);
+                });
+                self.set_active_tab(ActiveTab::Processes, cx);
+            }
+            PaletteTarget::Performance => self.set_active_tab(ActiveTab::Performance, cx),
+            PaletteTarget::Network => self.set_active_tab(ActiveTab::Network, cx),
+            PaletteTarget::AppDetails => self.set_active_tab(ActiveTab::AppDetails, cx),
+            PaletteTarget::Theme(theme) => self.set_theme(*theme, window, cx),
+        }
+    }
+
+    fn on_processes_tab_event(
+        &mut self,
+        _: &Entity<ProcessesTab>,
+        event: &ProcessesTabEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ProcessesTabEvent::EndProcesses { description, pids } = event;
+        let Some(monitor) = self.source.live_monitor_mut() else {
+            return;
+        };
+        processes_tab::end_processes(monitor, description, pids, window, cx);
+    }
+
+    fn copy_report(&mut self, _: &CopyReport, window: &mut Window, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(self.snapshot.to_markdown()));
+        window.push_notification(Notification::success("System report copied to clipboard."), cx);
+    }
+
     fn quit(&mut self, _action: &Quit, _window: &mut Window, cx: &mut Context<Self>) {
         cx.quit();
     }
 
-    fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
-        cx.new(|cx| Self::new(window, cx))
+    fn view(
+        source: Box<dyn SnapshotSource>,
+        recorder: Option<Recorder>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        cx.new(|cx| Self::new(source, recorder, window, cx))
     }
 }
 
 impl Render for TaskManagerApp {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let active_index = match self.active_tab {
             ActiveTab::Processes => 0,
             ActiveTab::Performance => 1,
-            ActiveTab::AppDetails => 2,
+            ActiveTab::Network => 2,
+            ActiveTab::AppDetails => 3,
         };
 
         v_flex()
@@ -132,9 +439,12 @@ impl Render for TaskManagerApp {
             .text_color(cx.theme().foreground)
             .key_context(CONTEXT)
             .on_action(cx.listener(Self::quit))
+            .on_action(cx.listener(Self::toggle_command_palette))
+            .on_action(cx.listener(Self::copy_report))
             .child(
-                div()
+                h_flex()
                     .p_4()
+                    .justify_between()
                     .border_b_1()
                     .border_color(cx.theme().border)
                     .child(
@@ -143,6 +453,63 @@ impl Render for TaskManagerApp {
                             .font_bold()
                             .child("Task Manager")
                     )
+                    .child(
+                        h_flex()
+                            .gap_4()
+                            .when(self.tray.is_some(), |this| {
+                                this.child(
+                                    Switch::new("minimize-to-tray")
+                                        .small()
+                                        .label("Keep running in tray")
+                                        .checked(self.minimize_to_tray)
+                                        .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                            this.set_minimize_to_tray(*checked, cx);
+                                        })),
+                                )
+                            })
+                            .child(
+                                Button::new("copy-report")
+                                    .small()
+                                    .outline()
+                                    .icon(IconName::Copy)
+                                    .label("Copy report")
+                                    .tooltip("Copy a Markdown summary of the system")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.copy_report(&CopyReport, window, cx);
+                                    })),
+                            )
+                            .child(
+                                ButtonGroup::new("theme")
+                                    .small()
+                                    .outline()
+                                    .child(
+                                        Button::new("theme-system")
+                                            .label("System")
+                                            .selected(self.theme == ThemePreference::System),
+                                    )
+                                    .child(
+                                        Button::new("theme-light")
+                                            .icon(IconName::Sun)
+                                            .tooltip("Light")
+                                            .selected(self.theme == ThemePreference::Light),
+                                    )
+                                    .child(
+                                        Button::new("theme-dark")
+                                            .icon(IconName::Moon)
+                                            .tooltip("Dark")
+                                            .selected(self.theme == ThemePreference::Dark),
+                                    )
+                                    .on_click(cx.listener(|this, clicks: &Vec<usize>, window, cx| {
+                                        let theme = match clicks.first() {
+                                            Some(0) => ThemePreference::System,
+                                            Some(1) => ThemePreference::Light,
+                                            Some(2) => ThemePreference::Dark,
+                                            _ => return,
+                                        };
+                                        this.set_theme(theme, window, cx);
+                                    })),
+                            ),
+                    )
             )
             .child(
                 TabBar::new("main-tabs")
@@ -151,13 +518,15 @@ impl Render for TaskManagerApp {
                         let tab = match ix {
                             0 => ActiveTab::Processes,
                             1 => ActiveTab::Performance,
-                            2 => ActiveTab::AppDetails,
+                            2 => ActiveTab::Network,
+                            3 => ActiveTab::AppDetails,
                             _ => return,
                         };
                         this.set_active_tab(tab, cx);
                     }))
                     .child(Tab::new().child("Processes"))
                     .child(Tab::new().child("Performance"))
+                    .child(Tab::new().child("Network"))
                     .child(Tab::new().child("App Details"))
             )
             .child(
@@ -170,15 +539,65 @@ impl Render for TaskManagerApp {
                     .when(self.active_tab == ActiveTab::Performance, |el| {
                         el.child(self.performance_tab.clone())
                     })
+                    .when(self.active_tab == ActiveTab::Network, |el| {
+                        el.child(self.network_tab.clone())
+                    })
                     .when(self.active_tab == ActiveTab::AppDetails, |el| {
                         el.child(self.app_details_tab.clone())
                     })
             )
+            .children(Root::render_dialog_layer(window, cx))
+            .children(Root::render_notification_layer(window, cx))
+    }
+}
+
+/// Parse the command line arguments:
+///
+/// - `--record <path>` record the snapshots to the file as JSON lines.
+/// - `--replay <path>` replay the recorded snapshots instead of the live system.
+fn parse_args() -> (Box<dyn SnapshotSource>, Option<Recorder>) {
+    let mut record_path = None;
+    let mut replay_path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => record_path = args.next(),
+            "--replay" => replay_path = args.next(),
+            _ => log::warn!("Unknown argument: {}", arg),
+        }
     }
+
+    let source: Box<dyn SnapshotSource> = match replay_path {
+        Some(path) => match ReplaySource::open(&path) {
+            Ok(source) => Box::new(source),
+            Err(err) => {
+                log::error!("Failed to open recording {}: {}", path, err);
+                Box::new(LiveSource::new())
+            }
+        },
+        None => {
+            let mut source = LiveSource::new();
+            let interval =
+                std::time::Duration::from_millis(Config::load().update_interval_ms.max(100));
+            source.monitor_mut().set_update_interval(interval);
+            Box::new(source)
+        }
+    };
+
+    let recorder = record_path.and_then(|path| match Recorder::create(&path) {
+        Ok(recorder) => Some(recorder),
+        Err(err) => {
+            log::error!("Failed to create recording {}: {}", path, err);
+            None
+        }
+    });
+
+    (source, recorder)
 }
 
 fn main() {
     env_logger::init();
+    let (source, recorder) = parse_args();
 
     let app = Application::new();
 
@@ -188,14 +607,23 @@ fn main() {
         cx.bind_keys([
             KeyBinding::new("cmd-q", Quit, Some(CONTEXT)),
             KeyBinding::new("ctrl-q", Quit, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeyBinding::new("cmd-k", ToggleCommandPalette, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeyBinding::new("ctrl-k", ToggleCommandPalette, Some(CONTEXT)),
         ]);
+        processes_tab::init(cx);
 
         let window_size = size(px(1200.0), px(800.0));
-        let window_bounds = Bounds::centered(None, window_size, cx);
+        let window_min_size = size(px(800.0), px(600.0));
+        let window_bounds = Config::load()
+            .window
+            .and_then(|window| window.window_bounds(window_min_size, cx))
+            .unwrap_or_else(|| WindowBounds::Windowed(Bounds::centered(None, window_size, cx)));
 
         let _window = cx.open_window(
             WindowOptions {
-                window_bounds: Some(WindowBounds::Windowed(window_bounds)),
+                window_bounds: Some(window_bounds),
                 titlebar: Some(gpui::TitlebarOptions {
                     title: Some("Task Manager".into()),
                     appears_transparent: false,
@@ -209,13 +637,13 @@ fn main() {
                 is_minimizable: true,
                 is_resizable: true,
                 display_id: None,
-                window_min_size: Some(size(px(800.0), px(600.0))),
+                window_min_size: Some(window_min_size),
                 app_id: Some("com.taskmanager.app".to_string()),
                 tabbing_identifier: None,
                 window_decorations: Some(gpui::WindowDecorations::Client),
             },
             |window, cx| {
-                let view = TaskManagerApp::view(window, cx);
+                let view = TaskManagerApp::view(source, recorder, window, cx);
                 cx.new(|cx| Root::new(view, window, cx))
             },
         ).ok();
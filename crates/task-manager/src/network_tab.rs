@@ -0,0 +1,284 @@
+use gpui::{App, AppContext, Context, div, Entity, IntoElement, ParentElement, Render, SharedString, Styled, Window, px};
+use gpui_component::{
+    table::{TableDelegate, TableState, Table, Column, ColumnSort, SortableFilterable},
+    v_flex, ActiveTheme, StyledExt,
+};
+use std::collections::HashMap;
+
+use monitor::{NetworkInfo, format_bytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NetworkColumn {
+    Interface,
+    Throughput,
+    ReceiveRate,
+    SendRate,
+    Received,
+    Sent,
+}
+
+impl NetworkColumn {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Interface => "Interface",
+            Self::Throughput => "Throughput",
+            Self::ReceiveRate => "Receive rate",
+            Self::SendRate => "Send rate",
+            Self::Received => "Received",
+            Self::Sent => "Sent",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Interface => "interface",
+            Self::Throughput => "throughput",
+            Self::ReceiveRate => "receive_rate",
+            Self::SendRate => "send_rate",
+            Self::Received => "received",
+            Self::Sent => "sent",
+        }
+    }
+
+    fn all() -> Vec<Self> {
+        vec![
+            Self::Interface,
+            Self::Throughput,
+            Self::ReceiveRate,
+            Self::SendRate,
+            Self::Received,
+            Self::Sent,
+        ]
+    }
+
+    fn text(&self, row: &InterfaceRow) -> String {
+        match self {
+            Self::Interface => row.interface.clone(),
+            Self::Throughput => format!("{}/s", format_bytes(row.throughput())),
+            Self::ReceiveRate => format!("{}/s", format_bytes(row.receive_rate)),
+            Self::SendRate => format!("{}/s", format_bytes(row.send_rate)),
+            Self::Received => format_bytes(row.received),
+            Self::Sent => format_bytes(row.sent),
+        }
+    }
+
+    fn column(&self) -> Column {
+        let width = match self {
+            Self::Interface => px(200.0),
+            _ => px(140.0),
+        };
+        Column::new(self.key(), self.label())
+            .width(width)
+            .min_width(px(80.0))
+            .sortable()
+    }
+}
+
+/// A network interface with the bytes per second since the previous snapshot.
+#[derive(Debug, Clone)]
+struct InterfaceRow {
+    interface: String,
+    /// The total bytes received.
+    received: u64,
+    /// The total bytes sent.
+    sent: u64,
+    receive_rate: u64,
+    send_rate: u64,
+}
+
+impl InterfaceRow {
+    fn throughput(&self) -> u64 {
+        self.receive_rate + self.send_rate
+    }
+}
+
+struct NetworkTableDelegate {
+    interfaces: SortableFilterable<InterfaceRow>,
+    columns: Vec<NetworkColumn>,
+    /// The timestamp of the previous snapshot, to compute the rates.
+    last_timestamp_ms: Option<u64>,
+}
+
+impl NetworkTableDelegate {
+    fn new() -> Self {
+        let mut interfaces = SortableFilterable::new(vec![])
+            .sort_by(NetworkColumn::Interface.key(), |a: &InterfaceRow, b| a.interface.cmp(&b.interface))
+            .sort_by_key(NetworkColumn::Throughput.key(), |row| row.throughput())
+            .sort_by_key(NetworkColumn::ReceiveRate.key(), |row| row.receive_rate)
+            .sort_by_key(NetworkColumn::SendRate.key(), |row| row.send_rate)
+            .sort_by_key(NetworkColumn::Received.key(), |row| row.received)
+            .sort_by_key(NetworkColumn::Sent.key(), |row| row.sent);
+        interfaces.set_sort(NetworkColumn::Throughput.key(), ColumnSort::Descending);
+
+        Self {
+            interfaces,
+            columns: NetworkColumn::all(),
+            last_timestamp_ms: None,
+        }
+    }
+
+    /// Update the interfaces, the rates are computed against the previous update.
+    fn update_networks(&mut self, networks: &[NetworkInfo], timestamp_ms: u64) {
+        let previous: HashMap<&str, &InterfaceRow> = self
+            .interfaces
+            .rows()
+            .iter()
+            .map(|row| (row.interface.as_str(), row))
+            .collect();
+        let elapsed_secs = self
+            .last_timestamp_ms
+            .map_or(0., |last| timestamp_ms.saturating_sub(last) as f64 / 1000.);
+        let rows = networks
+            .iter()
+            .map(|network| {
+                let previous = previous.get(network.interface.as_str());
+                InterfaceRow {
+                    interface: network.interface.clone(),
+                    received: network.received,
+                    sent: network.transmitted,
+                    receive_rate: rate(network.received, previous.map(|row| row.received), elapsed_secs),
+                    send_rate: rate(network.transmitted, previous.map(|row| row.sent), elapsed_secs),
+                }
+            })
+            .collect();
+
+        // Sort again on every update, so the busiest interfaces stay on top.
+        self.interfaces.set_rows(rows);
+        self.last_timestamp_ms = Some(timestamp_ms);
+    }
+}
+
+/// Returns the bytes per second from the `previous` total to the `total`, 0 for a new
+/// interface or the first update. A counter reset (e.g. the interface restarted) counts as 0.
+fn rate(total: u64, previous: Option<u64>, elapsed_secs: f64) -> u64 {
+    match previous {
+        Some(previous) if elapsed_secs > 0. => {
+            (total.saturating_sub(previous) as f64 / elapsed_secs) as u64
+        }
+        _ => 0,
+    }
+}
+
+impl TableDelegate for NetworkTableDelegate {
+    fn columns_count(&self, _cx: &App) -> usize {
+        self.columns.len()
+    }
+
+    fn rows_count(&self, _cx: &App) -> usize {
+        self.interfaces.len()
+    }
+
+    fn column(&self, col_ix: usize, _cx: &App) -> Column {
+        self.columns[col_ix].column()
+    }
+
+    fn render_td(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        let (Some(row), Some(column)) = (self.interfaces.get(row_ix), self.columns.get(col_ix))
+        else {
+            return div();
+        };
+
+        div().child(column.text(row))
+    }
+
+    fn cell_text(&self, row_ix: usize, col_ix: usize, _cx: &App) -> SharedString {
+        match (self.interfaces.get(row_ix), self.columns.get(col_ix)) {
+            (Some(row), Some(column)) => column.text(row).into(),
+            _ => SharedString::default(),
+        }
+    }
+
+    fn perform_sort(
+        &mut self,
+        col_ix: usize,
+        sort: ColumnSort,
+        _window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+        if let Some(column) = self.columns.get(col_ix) {
+            // Unsorted means the default sort, the busiest interfaces first.
+            let (key, sort) = match sort {
+                ColumnSort::Default => (NetworkColumn::Throughput.key(), ColumnSort::Descending),
+                sort => (column.key(), sort),
+            };
+            self.interfaces.set_sort(key, sort);
+            cx.notify();
+        }
+    }
+}
+
+/// The network interfaces, sortable by their throughput.
+pub struct NetworkTab {
+    table_state: Entity<TableState<NetworkTableDelegate>>,
+}
+
+impl NetworkTab {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let table_state = cx.new(|cx| {
+            TableState::new(NetworkTableDelegate::new(), window, cx).sortable(true)
+        });
+
+        Self { table_state }
+    }
+
+    pub fn update_networks(&mut self, networks: &[NetworkInfo], timestamp_ms: u64, cx: &mut Context<Self>) {
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().update_networks(networks, timestamp_ms);
+            cx.notify();
+        });
+    }
+}
+
+impl Render for NetworkTab {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let count = self.table_state.read(cx).delegate().rows_count(cx);
+
+        v_flex()
+            .size_full()
+            .p_4()
+            .gap_4()
+            .child(
+                div()
+                    .text_xl()
+                    .font_semibold()
+                    .child("Network")
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("{} interfaces", count))
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .child(
+                        Table::new(&self.table_state)
+                            .stripe(true)
+                            .bordered(true)
+                    )
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rate;
+
+    #[test]
+    fn test_rate() {
+        assert_eq!(rate(3000, Some(1000), 2.), 1000);
+        assert_eq!(rate(1500, Some(1000), 0.5), 1000);
+        // A new interface, or the first update.
+        assert_eq!(rate(3000, None, 2.), 0);
+        assert_eq!(rate(3000, Some(1000), 0.), 0);
+        // The counter was reset.
+        assert_eq!(rate(100, Some(1000), 1.), 0);
+    }
+}
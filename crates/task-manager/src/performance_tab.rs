@@ -1,26 +1,83 @@
-use gpui::{Context, div, IntoElement, ParentElement, Render, SharedString, Styled, Window, px};
+use chrono::{DateTime, Local};
+use gpui::{Context, div, IntoElement, ParentElement, Render, SharedString, Styled, Window, prelude::FluentBuilder, px};
 use gpui_component::{
+    button::{Button, ButtonGroup, ButtonVariants},
     chart::{LineChart, AreaChart},
-    h_flex, v_flex, ActiveTheme, StyledExt,
+    menu::{DropdownMenu, PopupMenuItem},
+    notification::Notification,
+    scroll::ScrollView,
+    h_flex, v_flex, ActiveTheme, IconName, Selectable, Sizable, StyledExt, WindowExt,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write as _,
+    path::PathBuf,
 };
-use std::collections::VecDeque;
 
-use crate::system_monitor::{SystemSnapshot, format_bytes};
+use monitor::{SystemSnapshot, format_bytes};
 
 const MAX_HISTORY: usize = 60;
+/// The history lengths offered in the tab header, in samples.
+pub const HISTORY_OPTIONS: [usize; 3] = [60, 300, 900];
+/// The maximum number of X axis labels drawn on a chart.
+const MAX_X_LABELS: usize = 6;
+
+/// The wall-clock time of a sample, labelled as `HH:MM:SS` on the X axis.
+///
+/// Equality uses the full timestamp, so samples taken within the same second
+/// still get their own point on the chart.
+#[derive(Clone, Copy, PartialEq)]
+struct Timestamp(DateTime<Local>);
+
+impl From<Timestamp> for SharedString {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0.format("%H:%M:%S").to_string().into()
+    }
+}
 
 #[derive(Clone)]
 struct DataPoint {
-    time: SharedString,
+    time: Timestamp,
     value: f64,
 }
 
+#[derive(Clone)]
+struct MemoryDataPoint {
+    time: Timestamp,
+    ram: f64,
+    swap: f64,
+}
+
+#[derive(Clone)]
+struct NetworkDataPoint {
+    time: Timestamp,
+    received: f64,
+    transmitted: f64,
+}
+
+/// Returns the tick margin that keeps at most [`MAX_X_LABELS`] labels on the X axis
+/// of `len` samples.
+fn tick_margin(len: usize) -> usize {
+    len.div_ceil(MAX_X_LABELS).max(1)
+}
+
 pub struct PerformanceTab {
     cpu_history: VecDeque<DataPoint>,
-    memory_history: VecDeque<DataPoint>,
+    memory_history: VecDeque<MemoryDataPoint>,
     disk_history: VecDeque<DataPoint>,
-    network_history: VecDeque<DataPoint>,
-    time_counter: u32,
+    /// The I/O of each disk, keyed by disk name. Dropped when the disk is unmounted.
+    disk_histories: HashMap<String, VecDeque<DataPoint>>,
+    /// The disk to chart, `None` for all the disks.
+    selected_disk: Option<String>,
+    network_history: VecDeque<NetworkDataPoint>,
+    /// The hottest CPU sensor, empty if the platform reports no sensors.
+    temperature_history: VecDeque<DataPoint>,
+    /// The average utilization of the GPUs, empty if there is no supported GPU.
+    gpu_history: VecDeque<DataPoint>,
+    /// The number of samples retained in each history.
+    max_history: usize,
+    /// Whether new samples are dropped, freezing the charts.
+    paused: bool,
     current_snapshot: Option<SystemSnapshot>,
 }
 
@@ -30,85 +87,273 @@ impl PerformanceTab {
             cpu_history: VecDeque::with_capacity(MAX_HISTORY),
             memory_history: VecDeque::with_capacity(MAX_HISTORY),
             disk_history: VecDeque::with_capacity(MAX_HISTORY),
+            disk_histories: HashMap::new(),
+            selected_disk: None,
             network_history: VecDeque::with_capacity(MAX_HISTORY),
-            time_counter: 0,
+            temperature_history: VecDeque::with_capacity(MAX_HISTORY),
+            gpu_history: VecDeque::with_capacity(MAX_HISTORY),
+            max_history: MAX_HISTORY,
+            paused: false,
             current_snapshot: None,
         }
     }
 
+    /// Set the number of samples retained in each chart, dropping the oldest samples
+    /// when shrinking.
+    pub fn set_max_history(&mut self, max_history: usize, cx: &mut Context<Self>) {
+        self.max_history = max_history.max(1);
+        self.trim_histories();
+        cx.notify();
+    }
+
+    /// Pause or resume recording samples.
+    ///
+    /// The monitor keeps running while paused, the snapshots are just not recorded.
+    pub fn set_paused(&mut self, paused: bool, cx: &mut Context<Self>) {
+        self.paused = paused;
+        cx.notify();
+    }
+
+    /// Chart the disk with the name, or all the disks if `None`.
+    pub fn set_selected_disk(&mut self, disk: Option<String>, cx: &mut Context<Self>) {
+        self.selected_disk = disk;
+        cx.notify();
+    }
+
+    fn trim_histories(&mut self) {
+        trim_history(&mut self.cpu_history, self.max_history);
+        trim_history(&mut self.memory_history, self.max_history);
+        trim_history(&mut self.disk_history, self.max_history);
+        for history in self.disk_histories.values_mut() {
+            trim_history(history, self.max_history);
+        }
+        trim_history(&mut self.network_history, self.max_history);
+        trim_history(&mut self.temperature_history, self.max_history);
+        trim_history(&mut self.gpu_history, self.max_history);
+    }
+
     pub fn update_snapshot(&mut self, snapshot: SystemSnapshot, _cx: &mut Context<Self>) {
-        self.time_counter += 1;
+        if self.paused {
+            return;
+        }
 
         let cpu_usage = snapshot.global_cpu_usage as f64;
-        let memory_percent = if snapshot.memory.total > 0 {
-            (snapshot.memory.used as f64 / snapshot.memory.total as f64) * 100.0
-        } else {
-            0.0
-        };
 
-        let total_disk: u64 = snapshot.disks.iter().map(|d| d.total - d.available).sum();
-        let total_disk_capacity: u64 = snapshot.disks.iter().map(|d| d.total).sum();
-        let disk_percent = if total_disk_capacity > 0 {
-            (total_disk as f64 / total_disk_capacity as f64) * 100.0
-        } else {
-            0.0
-        };
+        let disk_read: u64 = snapshot.disks.iter().map(|d| d.read_bytes).sum();
+        let disk_written: u64 = snapshot.disks.iter().map(|d| d.written_bytes).sum();
+        let disk_mbps = |bytes: u64| bytes as f64 / 1024.0 / 1024.0;
 
-        let total_network: u64 = snapshot.networks.iter()
-            .map(|n| n.received + n.transmitted)
-            .sum();
-        let network_mbps = (total_network as f64 / 1024.0 / 1024.0) / 1000.0;
+        let network_received: u64 = snapshot.networks.iter().map(|n| n.received).sum();
+        let network_transmitted: u64 = snapshot.networks.iter().map(|n| n.transmitted).sum();
+        let network_mbps = |bytes: u64| (bytes as f64 / 1024.0 / 1024.0) / 1000.0;
 
-        let time_label: SharedString = format!("{}", self.time_counter).into();
+        let time = Timestamp(
+            DateTime::from_timestamp_millis(snapshot.timestamp_ms as i64)
+                .map(|time| time.with_timezone(&Local))
+                .unwrap_or_else(Local::now),
+        );
 
         self.cpu_history.push_back(DataPoint {
-            time: time_label.clone(),
+            time,
             value: cpu_usage,
         });
-        self.memory_history.push_back(DataPoint {
-            time: time_label.clone(),
-            value: memory_percent,
+        self.memory_history.push_back(MemoryDataPoint {
+            time,
+            ram: snapshot.memory.used_percent(),
+            swap: snapshot.memory.swap_percent(),
         });
         self.disk_history.push_back(DataPoint {
-            time: time_label.clone(),
-            value: disk_percent,
-        });
-        self.network_history.push_back(DataPoint {
-            time: time_label,
-            value: network_mbps,
+            time,
+            value: disk_mbps(disk_read + disk_written),
         });
-
-        if self.cpu_history.len() > MAX_HISTORY {
-            self.cpu_history.pop_front();
+        self.disk_histories
+            .retain(|name, _| snapshot.disks.iter().any(|disk| disk.name == *name));
+        let max_history = self.max_history;
+        for disk in &snapshot.disks {
+            self.disk_histories
+                .entry(disk.name.clone())
+                .or_insert_with(|| VecDeque::with_capacity(max_history))
+                .push_back(DataPoint {
+                    time,
+                    value: disk_mbps(disk.read_bytes + disk.written_bytes),
+                });
         }
-        if self.memory_history.len() > MAX_HISTORY {
-            self.memory_history.pop_front();
+        if self
+            .selected_disk
+            .as_ref()
+            .is_some_and(|name| !self.disk_histories.contains_key(name))
+        {
+            self.selected_disk = None;
         }
-        if self.disk_history.len() > MAX_HISTORY {
-            self.disk_history.pop_front();
+        self.network_history.push_back(NetworkDataPoint {
+            time,
+            received: network_mbps(network_received),
+            transmitted: network_mbps(network_transmitted),
+        });
+        if let Some(temperature) = snapshot.cpu_temperature() {
+            self.temperature_history.push_back(DataPoint {
+                time,
+                value: temperature as f64,
+            });
         }
-        if self.network_history.len() > MAX_HISTORY {
-            self.network_history.pop_front();
+        if !snapshot.gpus.is_empty() {
+            let utilization: f32 = snapshot.gpus.iter().map(|gpu| gpu.utilization).sum();
+            self.gpu_history.push_back(DataPoint {
+                time,
+                value: (utilization / snapshot.gpus.len() as f32) as f64,
+            });
         }
 
+        self.trim_histories();
+
         self.current_snapshot = Some(snapshot);
     }
+
+    /// Returns the CPU, memory, disk and network histories as CSV, one row per sample.
+    ///
+    /// The histories are pushed together, so they have the same length, which is less than
+    /// the history length until the histories are filled.
+    pub fn export_csv(&self) -> String {
+        let mut csv = String::from(
+            "time,cpu_percent,memory_percent,swap_percent,disk_mb_per_sec,network_received_mb_per_sec,network_sent_mb_per_sec\n",
+        );
+        let samples = self
+            .cpu_history
+            .iter()
+            .zip(&self.memory_history)
+            .zip(&self.disk_history)
+            .zip(&self.network_history);
+        for (((cpu, memory), disk), network) in samples {
+            _ = writeln!(
+                csv,
+                "{},{:.2},{:.2},{:.2},{:.4},{:.4},{:.4}",
+                cpu.time.0.to_rfc3339(),
+                cpu.value,
+                memory.ram,
+                memory.swap,
+                disk.value,
+                network.received,
+                network.transmitted,
+            );
+        }
+        csv
+    }
+
+    /// Prompt for a file and save the [`Self::export_csv`] to it.
+    fn save_csv(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let csv = self.export_csv();
+        let directory = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let file_name = format!("performance-{}.csv", Local::now().format("%Y%m%d-%H%M%S"));
+        let path = cx.prompt_for_new_path(&directory, Some(&file_name));
+
+        cx.spawn_in(window, async move |_, cx| {
+            // Cancelled, or the platform has no file dialog.
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            let notification = match std::fs::write(&path, csv) {
+                Ok(()) => Notification::success(format!("Saved the history to {}.", path.display())),
+                Err(err) => Notification::error(format!("Failed to save the history: {}", err)),
+            };
+            _ = cx.update(|window, cx| window.push_notification(notification, cx));
+        })
+        .detach();
+    }
+
+    fn render_disk_selector(&self, disk_names: Vec<String>, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let selected_disk = self.selected_disk.clone();
+
+        Button::new("disk-selector")
+            .small()
+            .ghost()
+            .label(selected_disk.clone().unwrap_or_else(|| "All".to_string()))
+            .dropdown_caret(true)
+            .dropdown_menu(move |menu, _, _| {
+                let item = |label: &str, disk: Option<String>| {
+                    let view = view.clone();
+                    PopupMenuItem::new(label.to_string())
+                        .checked(selected_disk == disk)
+                        .on_click(move |_, _, cx| {
+                            view.update(cx, |this, cx| this.set_selected_disk(disk.clone(), cx));
+                        })
+                };
+
+                let menu = menu.item(item("All", None)).separator();
+                disk_names.iter().fold(menu, |menu, name| {
+                    menu.item(item(name, Some(name.clone())))
+                })
+            })
+    }
+}
+
+fn trim_history<T>(history: &mut VecDeque<T>, max_history: usize) {
+    while history.len() > max_history {
+        history.pop_front();
+    }
 }
 
 impl Render for PerformanceTab {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // The charts keep a fixed window of samples, so the lines scroll as the samples arrive.
+        let max_history = self.max_history;
         let cpu_data: Vec<DataPoint> = self.cpu_history.iter().cloned().collect();
-        let memory_data: Vec<DataPoint> = self.memory_history.iter().cloned().collect();
-        let disk_data: Vec<DataPoint> = self.disk_history.iter().cloned().collect();
-        let network_data: Vec<DataPoint> = self.network_history.iter().cloned().collect();
+        let memory_data: Vec<MemoryDataPoint> = self.memory_history.iter().cloned().collect();
+        let disk_history = self
+            .selected_disk
+            .as_ref()
+            .and_then(|name| self.disk_histories.get(name))
+            .unwrap_or(&self.disk_history);
+        let disk_data: Vec<DataPoint> = disk_history.iter().cloned().collect();
+        let mut disk_names: Vec<String> = self.disk_histories.keys().cloned().collect();
+        disk_names.sort();
+        let network_data: Vec<NetworkDataPoint> = self.network_history.iter().cloned().collect();
+        let temperature_data: Vec<DataPoint> = self.temperature_history.iter().cloned().collect();
+        let gpu_data: Vec<DataPoint> = self.gpu_history.iter().cloned().collect();
+        let (gpu_memory_used, gpu_memory_total) = self
+            .current_snapshot
+            .as_ref()
+            .map(|snapshot| {
+                snapshot.gpus.iter().fold((0, 0), |(used, total), gpu| {
+                    (used + gpu.memory_used, total + gpu.memory_total)
+                })
+            })
+            .unwrap_or((0, 0));
 
         let current_cpu = cpu_data.last().map(|d| d.value).unwrap_or(0.0);
-        let current_memory = memory_data.last().map(|d| d.value).unwrap_or(0.0);
+        let current_memory = memory_data.last().map(|d| d.ram).unwrap_or(0.0);
         let current_disk = disk_data.last().map(|d| d.value).unwrap_or(0.0);
-        let current_network = network_data.last().map(|d| d.value).unwrap_or(0.0);
+        let (current_received, current_transmitted) = network_data
+            .last()
+            .map(|d| (d.received, d.transmitted))
+            .unwrap_or((0.0, 0.0));
 
-        let (memory_used, memory_total) = if let Some(ref snapshot) = self.current_snapshot {
-            (snapshot.memory.used, snapshot.memory.total)
+        let (memory_used, memory_total, swap_used, swap_total) =
+            if let Some(ref snapshot) = self.current_snapshot {
+                (
+                    snapshot.memory.used,
+                    snapshot.memory.total,
+                    snapshot.memory.swap_used,
+                    snapshot.memory.swap_total,
+                )
+            } else {
+                (0, 0, 0, 0)
+            };
+        let has_swap = swap_total > 0;
+        let load_average = self
+            .current_snapshot
+            .as_ref()
+            .and_then(|snapshot| snapshot.load_average);
+        let (disk_read, disk_written) = if let Some(ref snapshot) = self.current_snapshot {
+            let disks = snapshot.disks.iter().filter(|d| {
+                self.selected_disk.as_ref().is_none_or(|name| d.name == *name)
+            });
+            disks.fold((0, 0), |(read, written), d| {
+                (read + d.read_bytes, written + d.written_bytes)
+            })
         } else {
             (0, 0)
         };
@@ -117,162 +362,367 @@ impl Render for PerformanceTab {
             .size_full()
             .p_4()
             .gap_4()
-            .child(
-                div()
-                    .text_xl()
-                    .font_semibold()
-                    .child("Performance")
-            )
             .child(
                 h_flex()
-                    .flex_1()
-                    .gap_4()
+                    .justify_between()
+                    .items_center()
                     .child(
-                        v_flex()
-                            .flex_1()
-                            .gap_2()
-                            .child(
-                                div()
-                                    .text_lg()
-                                    .font_semibold()
-                                    .child("CPU")
-                            )
-                            .child(
-                                div()
-                                    .text_2xl()
-                                    .font_bold()
-                                    .text_color(cx.theme().primary)
-                                    .child(format!("{:.1}%", current_cpu))
-                            )
-                            .child(
-                                div()
-                                    .flex_1()
-                                    .min_h(px(200.0))
-                                    .border_1()
-                                    .border_color(cx.theme().border)
-                                    .rounded(cx.theme().radius)
-                                    .p_2()
-                                    .child(
-                                        AreaChart::new(cpu_data.clone())
-                                            .x(|d| d.time.clone())
-                                            .y(|d| d.value)
-                                            .stroke(cx.theme().primary)
-                                    )
-                            )
+                        div()
+                            .text_xl()
+                            .font_semibold()
+                            .child("Performance")
                     )
                     .child(
-                        v_flex()
-                            .flex_1()
+                        h_flex()
                             .gap_2()
                             .child(
-                                div()
-                                    .text_lg()
-                                    .font_semibold()
-                                    .child("Memory")
+                                ButtonGroup::new("history")
+                                    .small()
+                                    .outline()
+                                    .children(HISTORY_OPTIONS.iter().map(|&len| {
+                                        Button::new(("history", len))
+                                            .label(format!("{}", len))
+                                            .tooltip(format!("Keep the last {} samples", len))
+                                            .selected(self.max_history == len)
+                                    }))
+                                    .on_click(cx.listener(|this, clicks: &Vec<usize>, _, cx| {
+                                        let len = clicks.first().and_then(|ix| HISTORY_OPTIONS.get(*ix));
+                                        if let Some(&len) = len {
+                                            this.set_max_history(len, cx);
+                                        }
+                                    }))
                             )
                             .child(
-                                div()
-                                    .text_2xl()
-                                    .font_bold()
-                                    .text_color(cx.theme().success)
-                                    .child(format!("{:.1}%", current_memory))
+                                Button::new("export")
+                                    .small()
+                                    .outline()
+                                    .icon(IconName::ArrowDown)
+                                    .label("Export")
+                                    .tooltip("Save the charted history as CSV")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.save_csv(window, cx);
+                                    }))
                             )
                             .child(
-                                div()
-                                    .text_sm()
-                                    .text_color(cx.theme().muted_foreground)
-                                    .child(format!("{} / {}",
-                                        format_bytes(memory_used),
-                                        format_bytes(memory_total)
-                                    ))
-                            )
-                            .child(
-                                div()
-                                    .flex_1()
-                                    .min_h(px(200.0))
-                                    .border_1()
-                                    .border_color(cx.theme().border)
-                                    .rounded(cx.theme().radius)
-                                    .p_2()
-                                    .child(
-                                        AreaChart::new(memory_data.clone())
-                                            .x(|d| d.time.clone())
-                                            .y(|d| d.value)
-                                            .stroke(cx.theme().success)
-                                    )
+                                Button::new("pause")
+                                    .small()
+                                    .outline()
+                                    .label(if self.paused { "Resume" } else { "Pause" })
+                                    .selected(self.paused)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.set_paused(!this.paused, cx);
+                                    }))
                             )
                     )
             )
             .child(
-                h_flex()
+                // Scroll when the extra charts (temperature, GPU) don't fit in the window.
+                ScrollView::vertical("performance-charts")
                     .flex_1()
-                    .gap_4()
                     .child(
                         v_flex()
-                            .flex_1()
-                            .gap_2()
-                            .child(
-                                div()
-                                    .text_lg()
-                                    .font_semibold()
-                                    .child("Disk")
-                            )
-                            .child(
-                                div()
-                                    .text_2xl()
-                                    .font_bold()
-                                    .text_color(cx.theme().warning)
-                                    .child(format!("{:.1}%", current_disk))
-                            )
+                            .min_h_full()
+                            .gap_4()
                             .child(
-                                div()
+                                h_flex()
                                     .flex_1()
-                                    .min_h(px(200.0))
-                                    .border_1()
-                                    .border_color(cx.theme().border)
-                                    .rounded(cx.theme().radius)
-                                    .p_2()
+                                    .gap_4()
                                     .child(
-                                        AreaChart::new(disk_data.clone())
-                                            .x(|d| d.time.clone())
-                                            .y(|d| d.value)
-                                            .stroke(cx.theme().warning)
+                                        v_flex()
+                                            .flex_1()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_lg()
+                                                    .font_semibold()
+                                                    .child("CPU")
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_2xl()
+                                                    .font_bold()
+                                                    .text_color(cx.theme().primary)
+                                                    .child(format!("{:.1}%", current_cpu))
+                                            )
+                                            .when_some(load_average, |this, (one, five, fifteen)| {
+                                                this.child(
+                                                    div()
+                                                        .text_sm()
+                                                        .text_color(cx.theme().muted_foreground)
+                                                        .child(format!("Load {:.2} / {:.2} / {:.2}", one, five, fifteen))
+                                                )
+                                            })
+                                            .child(
+                                                div()
+                                                    .flex_1()
+                                                    .min_h(px(200.0))
+                                                    .border_1()
+                                                    .border_color(cx.theme().border)
+                                                    .rounded(cx.theme().radius)
+                                                    .p_2()
+                                                    .child(
+                                                        AreaChart::new(cpu_data.clone())
+                                                            .x(|d| d.time)
+                                                            .tick_margin(tick_margin(max_history))
+                                                            .x_window(max_history)
+                                                            .y(|d| d.value)
+                                                            .stroke(cx.theme().primary)
+                                                            .y_range(0., 100.)
+                                                            .y_axis(true)
+                                                    )
+                                            )
+                                    )
+                                    .child(
+                                        v_flex()
+                                            .flex_1()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_lg()
+                                                    .font_semibold()
+                                                    .child("Memory")
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_2xl()
+                                                    .font_bold()
+                                                    .text_color(cx.theme().success)
+                                                    .child(format!("{:.1}%", current_memory))
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(cx.theme().muted_foreground)
+                                                    .child(format!("{} / {}",
+                                                        format_bytes(memory_used),
+                                                        format_bytes(memory_total)
+                                                    ))
+                                            )
+                                            .when(has_swap, |this| {
+                                                this.child(
+                                                    div()
+                                                        .text_sm()
+                                                        .text_color(cx.theme().muted_foreground)
+                                                        .child(format!("Swap {} / {}",
+                                                            format_bytes(swap_used),
+                                                            format_bytes(swap_total)
+                                                        ))
+                                                )
+                                            })
+                                            .child(
+                                                div()
+                                                    .flex_1()
+                                                    .min_h(px(200.0))
+                                                    .border_1()
+                                                    .border_color(cx.theme().border)
+                                                    .rounded(cx.theme().radius)
+                                                    .p_2()
+                                                    .child({
+                                                        let chart = AreaChart::new(memory_data.clone())
+                                                            .x(|d| d.time)
+                                                            .tick_margin(tick_margin(max_history))
+                                                            .x_window(max_history)
+                                                            .y_range(0., 100.)
+                                                            .y_axis(true);
+                                                        if has_swap {
+                                                            chart
+                                                                .series("RAM", cx.theme().success, |d| d.ram)
+                                                                .series("Swap", cx.theme().warning, |d| d.swap)
+                                                        } else {
+                                                            chart.y(|d| d.ram).stroke(cx.theme().success)
+                                                        }
+                                                    })
+                                            )
                                     )
-                            )
-                    )
-                    .child(
-                        v_flex()
-                            .flex_1()
-                            .gap_2()
-                            .child(
-                                div()
-                                    .text_lg()
-                                    .font_semibold()
-                                    .child("Network")
-                            )
-                            .child(
-                                div()
-                                    .text_2xl()
-                                    .font_bold()
-                                    .text_color(cx.theme().info)
-                                    .child(format!("{:.2} MB/s", current_network))
                             )
                             .child(
-                                div()
+                                h_flex()
                                     .flex_1()
-                                    .min_h(px(200.0))
-                                    .border_1()
-                                    .border_color(cx.theme().border)
-                                    .rounded(cx.theme().radius)
-                                    .p_2()
+                                    .gap_4()
+                                    .child(
+                                        v_flex()
+                                            .flex_1()
+                                            .gap_2()
+                                            .child(
+                                                h_flex()
+                                                    .justify_between()
+                                                    .items_center()
+                                                    .child(
+                                                        div()
+                                                            .text_lg()
+                                                            .font_semibold()
+                                                            .child("Disk")
+                                                    )
+                                                    .child(self.render_disk_selector(disk_names, cx))
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_2xl()
+                                                    .font_bold()
+                                                    .text_color(cx.theme().warning)
+                                                    .child(format!("{:.2} MB/s", current_disk))
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(cx.theme().muted_foreground)
+                                                    .child(format!("Read {}/s, Write {}/s",
+                                                        format_bytes(disk_read),
+                                                        format_bytes(disk_written)
+                                                    ))
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex_1()
+                                                    .min_h(px(200.0))
+                                                    .border_1()
+                                                    .border_color(cx.theme().border)
+                                                    .rounded(cx.theme().radius)
+                                                    .p_2()
+                                                    .child(
+                                                        AreaChart::new(disk_data.clone())
+                                                            .x(|d| d.time)
+                                                            .tick_margin(tick_margin(max_history))
+                                                            .x_window(max_history)
+                                                            .y(|d| d.value)
+                                                            .stroke(cx.theme().warning)
+                                                            .y_axis(true)
+                                                    )
+                                            )
+                                    )
                                     .child(
-                                        LineChart::new(network_data.clone())
-                                            .x(|d| d.time.clone())
-                                            .y(|d| d.value)
-                                            .stroke(cx.theme().info)
-                                            .dot()
+                                        v_flex()
+                                            .flex_1()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_lg()
+                                                    .font_semibold()
+                                                    .child("Network")
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_2xl()
+                                                    .font_bold()
+                                                    .text_color(cx.theme().info)
+                                                    .child(format!("{:.2} MB/s", current_received + current_transmitted))
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(cx.theme().muted_foreground)
+                                                    .child(format!("Received {:.2} MB/s, Sent {:.2} MB/s",
+                                                        current_received,
+                                                        current_transmitted
+                                                    ))
+                                            )
+                                            .child(
+                                                div()
+                                                    .flex_1()
+                                                    .min_h(px(200.0))
+                                                    .border_1()
+                                                    .border_color(cx.theme().border)
+                                                    .rounded(cx.theme().radius)
+                                                    .p_2()
+                                                    .child(
+                                                        LineChart::new(network_data.clone())
+                                                            .x(|d| d.time)
+                                                            .tick_margin(tick_margin(max_history))
+                                                            .x_window(max_history)
+                                                            .series("Received", cx.theme().info, |d| d.received)
+                                                            .series("Sent", cx.theme().chart_3, |d| d.transmitted)
+                                                            .y_axis(true)
+                                                            .dot()
+                                                    )
+                                            )
                                     )
                             )
+                            .when_some(temperature_data.last().map(|d| d.value), |this, current_temperature| {
+                                this.child(
+                                    v_flex()
+                                        .flex_1()
+                                        .gap_2()
+                                        .child(
+                                            div()
+                                                .text_lg()
+                                                .font_semibold()
+                                                .child("CPU Temperature")
+                                        )
+                                        .child(
+                                            div()
+                                                .text_2xl()
+                                                .font_bold()
+                                                .text_color(cx.theme().danger)
+                                                .child(format!("{:.1} °C", current_temperature))
+                                        )
+                                        .child(
+                                            div()
+                                                .flex_1()
+                                                .min_h(px(200.0))
+                                                .border_1()
+                                                .border_color(cx.theme().border)
+                                                .rounded(cx.theme().radius)
+                                                .p_2()
+                                                .child(
+                                                    LineChart::new(temperature_data.clone())
+                                                        .x(|d| d.time)
+                                                        .tick_margin(tick_margin(max_history))
+                                                        .x_window(max_history)
+                                                        .y(|d| d.value)
+                                                        .stroke(cx.theme().danger)
+                                                        .y_axis(true)
+                                                )
+                                        )
+                                )
+                            })
+                            .when_some(gpu_data.last().map(|d| d.value), |this, current_gpu| {
+                                this.child(
+                                    v_flex()
+                                        .flex_1()
+                                        .gap_2()
+                                        .child(
+                                            div()
+                                                .text_lg()
+                                                .font_semibold()
+                                                .child("GPU")
+                                        )
+                                        .child(
+                                            div()
+                                                .text_2xl()
+                                                .font_bold()
+                                                .text_color(cx.theme().chart_4)
+                                                .child(format!("{:.1}%", current_gpu))
+                                        )
+                                        .child(
+                                            div()
+                                                .text_sm()
+                                                .text_color(cx.theme().muted_foreground)
+                                                .child(format!("Video memory {} / {}",
+                                                    format_bytes(gpu_memory_used),
+                                                    format_bytes(gpu_memory_total)
+                                                ))
+                                        )
+                                        .child(
+                                            div()
+                                                .flex_1()
+                                                .min_h(px(200.0))
+                                                .border_1()
+                                                .border_color(cx.theme().border)
+                                                .rounded(cx.theme().radius)
+                                                .p_2()
+                                                .child(
+                                                    AreaChart::new(gpu_data.clone())
+                                                        .x(|d| d.time)
+                                                        .tick_margin(tick_margin(max_history))
+                                                        .x_window(max_history)
+                                                        .y(|d| d.value)
+                                                        .stroke(cx.theme().chart_4)
+                                                        .y_range(0., 100.)
+                                                        .y_axis(true)
+                                                )
+                                        )
+                                )
+                            })
                     )
             )
     }
@@ -0,0 +1,246 @@
+use std::cmp::Ordering;
+
+use regex::{Regex, RegexBuilder};
+
+use monitor::ProcessInfo;
+
+/// Matches a text by a case-insensitive substring or regex.
+enum Matcher {
+    /// The lowercased substring.
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// An invalid regex falls back to a literal match of the pattern.
+    fn new(pattern: &str, regex: bool) -> Self {
+        let regex = regex
+            .then(|| RegexBuilder::new(pattern).case_insensitive(true).build().ok())
+            .flatten();
+        match regex {
+            Some(regex) => Self::Regex(regex),
+            None => Self::Literal(pattern.to_lowercase()),
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Literal(literal) => text.to_lowercase().contains(literal.as_str()),
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+enum Term {
+    Name(Matcher),
+    Pid(Matcher),
+    User(Matcher),
+    /// The CPU usage compared with a percentage.
+    Cpu(Ordering, f32),
+    /// The memory compared with a number of MB.
+    Memory(Ordering, f64),
+    /// Matches the name, PID, user, command or path.
+    Any(Matcher),
+}
+
+impl Term {
+    fn parse(term: &str, regex: bool) -> Self {
+        // The prefixes are case-insensitive, e.g. `CPU>5` or `Name:chrome`.
+        let lowercase = term.to_ascii_lowercase();
+        for (prefix, ordering) in [(">", Ordering::Greater), ("<", Ordering::Less)] {
+            if let Some(value) = lowercase
+                .strip_prefix("cpu")
+                .and_then(|rest| rest.strip_prefix(prefix))
+                .and_then(|value| value.parse().ok())
+            {
+                return Self::Cpu(ordering, value);
+            }
+            if let Some(value) = lowercase
+                .strip_prefix("mem")
+                .and_then(|rest| rest.strip_prefix(prefix))
+                .and_then(|value| value.parse().ok())
+            {
+                return Self::Memory(ordering, value);
+            }
+        }
+
+        if let Some((column, pattern)) = term.split_once(':') {
+            let matcher = Matcher::new(pattern, regex);
+            match column.to_ascii_lowercase().as_str() {
+                "name" => return Self::Name(matcher),
+                "pid" => return Self::Pid(matcher),
+                "user" => return Self::User(matcher),
+                _ => {}
+            }
+        }
+
+        Self::Any(Matcher::new(term, regex))
+    }
+
+    fn is_match(&self, process: &ProcessInfo) -> bool {
+        const MB: f64 = 1024. * 1024.;
+
+        match self {
+            Self::Name(matcher) => matcher.is_match(&process.name),
+            Self::Pid(matcher) => matcher.is_match(&process.pid.to_string()),
+            Self::User(matcher) => process.user.as_ref().is_some_and(|user| matcher.is_match(user)),
+            Self::Cpu(ordering, value) => process.cpu_usage.total_cmp(value) == *ordering,
+            Self::Memory(ordering, value) => {
+                (process.memory as f64 / MB).total_cmp(value) == *ordering
+            }
+            Self::Any(matcher) => {
+                matcher.is_match(&process.name)
+                    || matcher.is_match(&process.pid.to_string())
+                    || process.user.as_ref().is_some_and(|user| matcher.is_match(user))
+                    || matcher.is_match(&process.cmd.join(" "))
+                    || process
+                        .exe
+                        .as_ref()
+                        .is_some_and(|exe| matcher.is_match(&exe.to_string_lossy()))
+            }
+        }
+    }
+}
+
+/// The search query of the processes table, the whitespace separated terms must all match.
+///
+/// - `name:chrome`, `pid:42` and `user:root` match a single column,
+/// - `cpu>5` and `cpu<1` compare the CPU usage in percent,
+/// - `mem>500` and `mem<10` compare the memory in MB,
+/// - any other term matches the name, PID, user, command or path.
+///
+/// The prefixes are case-insensitive, a prefix with an empty pattern (e.g. `name:` while
+/// typing) matches every process with that column.
+///
+/// In the regex mode the patterns are case-insensitive regexes,
+/// an invalid regex falls back to a substring match.
+pub struct ProcessQuery {
+    terms: Vec<Term>,
+}
+
+impl ProcessQuery {
+    pub fn parse(query: &str, regex: bool) -> Self {
+        Self {
+            terms: query
+                .split_whitespace()
+                .map(|term| Term::parse(term, regex))
+                .collect(),
+        }
+    }
+
+    pub fn is_match(&self, process: &ProcessInfo) -> bool {
+        self.terms.iter().all(|term| term.is_match(process))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use monitor::ProcessInfo;
+
+    use super::ProcessQuery;
+
+    fn process(
+        pid: u32,
+        name: &str,
+        user: Option<&str>,
+        cpu_usage: f32,
+        memory_mb: u64,
+    ) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent_pid: None,
+            name: name.to_string(),
+            user: user.map(str::to_string),
+            cmd: vec![format!("/usr/bin/{name}"), "--flag".to_string()],
+            exe: None,
+            status: "Run".to_string(),
+            cpu_usage,
+            memory: memory_mb * 1024 * 1024,
+            disk_usage: 0,
+            start_time: 0,
+            run_time: 0,
+            threads: None,
+            accessible: true,
+        }
+    }
+
+    fn is_match(query: &str, regex: bool, process: &ProcessInfo) -> bool {
+        ProcessQuery::parse(query, regex).is_match(process)
+    }
+
+    #[test]
+    fn test_compare() {
+        let busy = process(1, "chrome", None, 12.5, 500);
+        let idle = process(2, "sshd", None, 0.1, 4);
+
+        assert!(is_match("cpu>5", false, &busy));
+        assert!(!is_match("cpu>5", false, &idle));
+        assert!(is_match("cpu<1", false, &idle));
+        assert!(is_match("mem<10", false, &idle));
+        assert!(!is_match("mem<10", false, &busy));
+        assert!(is_match("mem>100", false, &busy));
+
+        // Case-insensitive prefixes.
+        assert!(is_match("CPU>5", false, &busy));
+        assert!(is_match("Mem<10", false, &idle));
+
+        // Not a number, so a plain term.
+        assert!(!is_match("cpu>high", false, &busy));
+    }
+
+    #[test]
+    fn test_prefix() {
+        let process = process(42, "chrome", Some("alice"), 0., 0);
+
+        assert!(is_match("name:chr", false, &process));
+        assert!(!is_match("name:42", false, &process));
+        assert!(is_match("pid:42", false, &process));
+        assert!(!is_match("pid:chrome", false, &process));
+        assert!(is_match("user:ALI", false, &process));
+        assert!(!is_match("user:bob", false, &process));
+
+        // Case-insensitive prefixes.
+        assert!(is_match("NAME:chr", false, &process));
+        assert!(is_match("Pid:42", false, &process));
+
+        // Unknown prefixes are plain terms.
+        assert!(!is_match("foo:chrome", false, &process));
+        // Any column without a prefix, including the command.
+        assert!(is_match("42", false, &process));
+        assert!(is_match("--flag", false, &process));
+    }
+
+    #[test]
+    fn test_empty_pattern() {
+        let process = process(42, "chrome", None, 0., 0);
+
+        assert!(is_match("name:", false, &process));
+        assert!(is_match("pid:", true, &process));
+        // The process has no user.
+        assert!(!is_match("user:", false, &process));
+        assert!(is_match("", false, &process));
+    }
+
+    #[test]
+    fn test_and() {
+        let process = process(42, "chrome", Some("alice"), 12.5, 500);
+
+        assert!(is_match("chrome cpu>5 user:alice", false, &process));
+        assert!(!is_match("chrome cpu>50", false, &process));
+        assert!(!is_match("name:chrome user:bob", false, &process));
+    }
+
+    #[test]
+    fn test_regex() {
+        let chrome = process(42, "chrome", None, 0., 0);
+        assert!(is_match("name:^chr.*e$", true, &chrome));
+        assert!(!is_match("name:^chr.*e$", false, &chrome));
+        assert!(is_match("CHROME", true, &chrome));
+
+        // An invalid regex falls back to a substring match.
+        let parens = process(1, "a(b", None, 0., 0);
+        assert!(is_match("a(b", true, &parens));
+        assert!(is_match("name:(", true, &parens));
+        assert!(!is_match("name:(c", true, &parens));
+    }
+}
@@ -1,19 +1,66 @@
-use gpui::{App, AppContext, Context, div, Entity, IntoElement, ParentElement, Render, Styled, Window, Subscription};
+use gpui::{actions, App, AppContext, ClipboardItem, Context, div, Entity, EventEmitter, Focusable, InteractiveElement, IntoElement, KeyBinding, ParentElement, Render, SharedString, StatefulInteractiveElement, Styled, Task, Window, Subscription, prelude::FluentBuilder, px};
 use gpui_component::{
-    table::{TableDelegate, TableState, Table, Column, ColumnSort},
+    button::{Button, ButtonVariant, ButtonVariants},
+    chart::Sparkline,
+    checkbox::Checkbox,
+    dialog::DialogButtonProps,
+    notification::Notification,
+    table::{TableDelegate, TableEvent, TableState, Table, Column, ColumnSort, SortableFilterable},
     input::{InputState, Input, InputEvent},
-    v_flex, h_flex, StyledExt,
+    menu::{PopupMenu, PopupMenuItem},
+    plot::PlotSized,
+    scroll::ScrollView,
+    split_pane::SplitPane,
+    v_flex, h_flex, ActiveTheme, Disableable, IconName, PixelsExt, Sizable, StyledExt, WindowExt,
 };
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    rc::Rc,
+    time::Duration,
+};
+
+use monitor::{ProcessInfo, SystemMonitor, descendants, format_bytes, format_duration};
+
+use crate::{config::Config, process_query::ProcessQuery};
+
+actions!(processes_tab, [FocusSearch, ClearSearch, CloseDetails]);
+
+const CONTEXT: &str = "ProcessesTab";
+
+#[cfg(target_os = "macos")]
+const SEARCH_PLACEHOLDER: &str = "Search (⌘F), e.g. chrome, user:root or cpu>5";
+#[cfg(not(target_os = "macos"))]
+const SEARCH_PLACEHOLDER: &str = "Search (Ctrl+F), e.g. chrome, user:root or cpu>5";
 
-use crate::system_monitor::{ProcessInfo, format_bytes};
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-f", FocusSearch, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-f", FocusSearch, Some(CONTEXT)),
+        // Override the escape of the Input, the search input has nothing else to cancel.
+        KeyBinding::new("escape", ClearSearch, Some("ProcessesTab > Input")),
+        // The table handles the escape first, clearing the selection closes the details.
+        KeyBinding::new("escape", CloseDetails, Some(CONTEXT)),
+    ]);
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ProcessColumn {
     Name,
     Pid,
+    User,
+    Status,
     Cpu,
+    CpuHistory,
     Memory,
+    MemoryPercent,
     Disk,
+    Uptime,
+    Command,
+    Path,
+    DeltaCpu,
+    DeltaMemory,
 }
 
 impl ProcessColumn {
@@ -21,9 +68,18 @@ impl ProcessColumn {
         match self {
             Self::Name => "Name",
             Self::Pid => "PID",
+            Self::User => "User",
+            Self::Status => "Status",
             Self::Cpu => "CPU %",
+            Self::CpuHistory => "CPU History",
             Self::Memory => "Memory",
+            Self::MemoryPercent => "Memory %",
             Self::Disk => "Disk",
+            Self::Uptime => "Uptime",
+            Self::Command => "Command",
+            Self::Path => "Path",
+            Self::DeltaCpu => "Δ CPU",
+            Self::DeltaMemory => "Δ Memory",
         }
     }
 
@@ -31,9 +87,18 @@ impl ProcessColumn {
         match self {
             Self::Name => "name",
             Self::Pid => "pid",
+            Self::User => "user",
+            Self::Status => "status",
             Self::Cpu => "cpu",
+            Self::CpuHistory => "cpu_history",
             Self::Memory => "memory",
+            Self::MemoryPercent => "memory_percent",
             Self::Disk => "disk",
+            Self::Uptime => "uptime",
+            Self::Command => "command",
+            Self::Path => "path",
+            Self::DeltaCpu => "delta_cpu",
+            Self::DeltaMemory => "delta_memory",
         }
     }
 
@@ -41,130 +106,665 @@ impl ProcessColumn {
         vec![
             Self::Name,
             Self::Pid,
+            Self::User,
+            Self::Status,
             Self::Cpu,
+            Self::CpuHistory,
             Self::Memory,
+            Self::MemoryPercent,
             Self::Disk,
+            Self::Uptime,
+            Self::Command,
+            Self::Path,
+            Self::DeltaCpu,
+            Self::DeltaMemory,
         ]
     }
+
+    /// Returns true if the column is hidden by default.
+    fn is_optional(&self) -> bool {
+        matches!(self, Self::Command | Self::Path)
+    }
+
+    /// Returns true if the column is unknown for the processes we have no access to,
+    /// these cells are muted, see [`ProcessInfo::accessible`].
+    fn is_restricted(&self) -> bool {
+        matches!(
+            self,
+            Self::Status
+                | Self::Cpu
+                | Self::CpuHistory
+                | Self::Memory
+                | Self::MemoryPercent
+                | Self::Disk
+                | Self::DeltaCpu
+                | Self::DeltaMemory
+        )
+    }
+
+    /// Returns true if the column compares with the baseline, only shown while there is one.
+    fn is_delta(&self) -> bool {
+        matches!(self, Self::DeltaCpu | Self::DeltaMemory)
+    }
+
+    /// The plain text of the cell of the process, the CPU history and the deltas are empty,
+    /// see [`ProcessesTableDelegate::text`].
+    ///
+    /// The `total_memory` is the system memory, for the memory percentage.
+    fn text(&self, process: &ProcessInfo, total_memory: u64) -> String {
+        match self {
+            Self::Name => process.name.clone(),
+            Self::Pid => process.pid.to_string(),
+            Self::User => process.user.clone().unwrap_or_else(|| "—".to_string()),
+            Self::Status if !process.accessible => "Access denied".to_string(),
+            Self::Status if process.is_zombie() => "Zombie (defunct)".to_string(),
+            // Don't show a misleading 0% for the processes we can't read.
+            Self::Cpu | Self::Memory | Self::MemoryPercent | Self::Disk | Self::DeltaCpu
+            | Self::DeltaMemory
+                if !process.accessible =>
+            {
+                "—".to_string()
+            }
+            Self::Status => process.status.clone(),
+            Self::Cpu => format!("{:.1}%", process.cpu_usage),
+            Self::CpuHistory => String::new(),
+            Self::Memory => format_bytes(process.memory),
+            Self::MemoryPercent => format!("{:.1}%", memory_percent(process.memory, total_memory)),
+            Self::Disk => format_bytes(process.disk_usage),
+            Self::Uptime => format_duration(process.run_time),
+            Self::Command if process.cmd.is_empty() => "-".to_string(),
+            Self::Command => process.cmd.join(" "),
+            Self::Path => process
+                .exe
+                .as_ref()
+                .map(|exe| exe.display().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            Self::DeltaCpu | Self::DeltaMemory => String::new(),
+        }
+    }
+
+    fn column(&self) -> Column {
+        let width = match self {
+            Self::Name => px(250.0),
+            Self::Pid | Self::Status | Self::MemoryPercent => px(100.0),
+            Self::User | Self::Uptime => px(120.0),
+            Self::Cpu | Self::CpuHistory => px(120.0),
+            Self::Memory | Self::Disk => px(150.0),
+            Self::Command | Self::Path => px(300.0),
+            Self::DeltaCpu | Self::DeltaMemory => px(120.0),
+        };
+        let min_width = match self {
+            Self::Name | Self::Command | Self::Path => px(120.0),
+            _ => px(60.0),
+        };
+        let column = Column::new(self.key(), self.label())
+            .width(width)
+            .min_width(min_width)
+            // Keep the name visible when scrolling through the other columns.
+            .when(*self == Self::Name, |column| column.fixed_left());
+        if *self == Self::CpuHistory || self.is_delta() {
+            column
+        } else {
+            column.sortable()
+        }
+    }
+}
+
+/// The share of the system memory, in percent, from which the memory % is colored as a warning.
+const MEMORY_WARNING_PERCENT: f64 = 10.;
+/// The share of the system memory, in percent, from which the memory % is colored as a danger.
+const MEMORY_DANGER_PERCENT: f64 = 25.;
+
+/// Returns the share of the `total` memory used by `memory`, in percent.
+fn memory_percent(memory: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.
+    } else {
+        memory as f64 / total as f64 * 100.
+    }
+}
+
+/// Format a signed byte count, e.g. "+1.50 MiB" or "-512 B".
+fn format_bytes_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_bytes(delta.unsigned_abs()))
+}
+
+/// The CPU usage in percent below which a process is hidden by "Hide idle".
+const IDLE_CPU_PERCENT: f32 = 0.1;
+
+/// The delay after the last keystroke before the search query is applied.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+/// How long the errors of ending processes stay, the success notifications use the default.
+const ERROR_NOTIFICATION_DURATION: Duration = Duration::from_secs(10);
+/// The number of samples kept for each process, about a minute at the default interval.
+const PROCESS_HISTORY_LEN: usize = 60;
+/// The indent of each level of the process tree.
+const TREE_INDENT: f32 = 16.;
+/// The default width of the process table as a ratio of the tab width when the details panel is open.
+pub(crate) const DEFAULT_DETAILS_SPLIT_RATIO: f32 = 0.7;
+
+/// A row of the process tree or of the name groups.
+struct TreeRow {
+    /// The process, with the usage of its descendants added when it is collapsed.
+    process: ProcessInfo,
+    depth: usize,
+    has_children: bool,
+    /// The pids of the processes in the name group, empty if the row is not a group.
+    group_pids: Vec<u32>,
+}
+
+/// Build the process tree of the visible processes, the siblings keep their sorted order.
+///
+/// A process whose parent is not visible (filtered out or exited) is a root, and the
+/// CPU, memory and disk usage of the descendants of a collapsed process are added to its row.
+fn build_tree<'a>(
+    processes: impl Iterator<Item = &'a ProcessInfo>,
+    collapsed: &HashSet<u32>,
+) -> Vec<TreeRow> {
+    let processes = processes.collect::<Vec<_>>();
+    let pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+
+    let mut children: HashMap<u32, Vec<&ProcessInfo>> = HashMap::new();
+    let mut roots = vec![];
+    for process in &processes {
+        match process.parent_pid {
+            Some(parent) if parent != process.pid && pids.contains(&parent) => {
+                children.entry(parent).or_default().push(*process);
+            }
+            _ => roots.push(*process),
+        }
+    }
+
+    let mut rows = vec![];
+    let mut visited = HashSet::new();
+    // Start from the roots, then any process left in a parent cycle (e.g. after pid reuse).
+    for start in roots.into_iter().chain(processes.iter().copied()) {
+        let mut stack = vec![(start, 0)];
+        while let Some((process, depth)) = stack.pop() {
+            if !visited.insert(process.pid) {
+                continue;
+            }
+
+            let process_children = children
+                .get(&process.pid)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut row = TreeRow {
+                process: process.clone(),
+                depth,
+                has_children: !process_children.is_empty(),
+                group_pids: vec![],
+            };
+
+            if collapsed.contains(&process.pid) {
+                let mut pending = process_children.to_vec();
+                while let Some(child) = pending.pop() {
+                    if !visited.insert(child.pid) {
+                        continue;
+                    }
+                    row.process.cpu_usage += child.cpu_usage;
+                    row.process.memory += child.memory;
+                    row.process.disk_usage += child.disk_usage;
+                    pending.extend(children.get(&child.pid).into_iter().flatten().copied());
+                }
+            } else {
+                // Reversed, so the children are popped in their sorted order.
+                stack.extend(process_children.iter().rev().map(|child| (*child, depth + 1)));
+            }
+
+            rows.push(row);
+        }
+    }
+
+    rows
+}
+
+/// Group the visible processes by name, the groups are sorted by their aggregated usage.
+///
+/// A group row is the first process of the group with the CPU, memory and disk usage of
+/// the others added and the pids of all of them, followed by the processes of the group
+/// when it is expanded.
+fn build_groups(
+    processes: &SortableFilterable<ProcessInfo>,
+    expanded: &HashSet<String>,
+) -> Vec<TreeRow> {
+    let mut groups: Vec<(TreeRow, Vec<&ProcessInfo>)> = vec![];
+    let mut group_ixs: HashMap<&str, usize> = HashMap::new();
+    for process in processes.iter() {
+        if let Some(&ix) = group_ixs.get(process.name.as_str()) {
+            let (row, members) = &mut groups[ix];
+            row.process.cpu_usage += process.cpu_usage;
+            row.process.memory += process.memory;
+            row.process.disk_usage += process.disk_usage;
+            members.push(process);
+        } else {
+            group_ixs.insert(process.name.as_str(), groups.len());
+            let row = TreeRow {
+                process: process.clone(),
+                depth: 0,
+                has_children: false,
+                group_pids: vec![],
+            };
+            groups.push((row, vec![process]));
+        }
+    }
+    // Stable sort, so the groups with equal usage keep the order of their first process.
+    groups.sort_by(|(a, _), (b, _)| processes.compare(&a.process, &b.process));
+
+    let mut rows = vec![];
+    for (mut row, members) in groups {
+        row.group_pids = members.iter().map(|p| p.pid).collect();
+        row.has_children = members.len() > 1;
+        let is_expanded = row.has_children && expanded.contains(&row.process.name);
+        rows.push(row);
+        if is_expanded {
+            rows.extend(members.into_iter().map(|process| TreeRow {
+                process: process.clone(),
+                depth: 1,
+                has_children: false,
+                group_pids: vec![],
+            }));
+        }
+    }
+
+    rows
+}
+
+/// A sample of the usage of a process, see [`ProcessesTableDelegate::history`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessSample {
+    pub cpu_usage: f32,
+    pub memory: u64,
 }
 
 pub struct ProcessesTableDelegate {
-    processes: Vec<ProcessInfo>,
-    filtered_processes: Vec<ProcessInfo>,
-    filter_query: String,
-    sort_column: ProcessColumn,
-    sort_ascending: bool,
+    processes: SortableFilterable<ProcessInfo>,
+    /// The total memory of the system, for the memory percentage.
+    total_memory: u64,
+    visible_columns: Vec<ProcessColumn>,
     columns: Vec<Column>,
+    /// Whether the optional command and path columns are visible.
+    show_details: bool,
+    /// The processes at the time of the "Snapshot" button, keyed by pid, to compare with.
+    baseline: Option<HashMap<u32, ProcessInfo>>,
+    /// The recent usage of each process, keyed by pid.
+    history: HashMap<u32, VecDeque<ProcessSample>>,
+    /// The names of the pinned processes, always sorted to the top.
+    pinned: HashSet<String>,
+    /// Whether to nest the child processes under their parent.
+    tree_view: bool,
+    /// The pids of the collapsed processes in the tree view.
+    collapsed: HashSet<u32>,
+    /// Whether to aggregate the processes with the same name into one row.
+    group_by_name: bool,
+    /// The names of the expanded groups.
+    expanded_groups: HashSet<String>,
+    /// The rows of the tree view or the name groups, empty in the flat view.
+    tree: Vec<TreeRow>,
+    /// The search query, see [`ProcessQuery`].
+    query: String,
+    /// Whether the search patterns are regexes.
+    regex: bool,
+    /// The minimum CPU usage in percent of the visible processes.
+    min_cpu: f32,
+    /// The minimum memory in bytes of the visible processes.
+    min_memory: u64,
+    /// Whether the processes can be ended, false in a replay.
+    can_end_tasks: bool,
+    /// Called by the "End task" item of the row context menu.
+    ///
+    /// The processes are all the processes of a name group row, or the process of the row.
+    on_end_task: Option<Rc<dyn Fn(&[ProcessInfo], &mut Window, &mut App)>>,
 }
 
 impl ProcessesTableDelegate {
-    pub fn new(processes: Vec<ProcessInfo>) -> Self {
-        let columns = vec![
-            Column::new("name", "Name").width(250.0).sortable(),
-            Column::new("pid", "PID").width(100.0).sortable(),
-            Column::new("cpu", "CPU %").width(120.0).sortable(),
-            Column::new("memory", "Memory").width(150.0).sortable(),
-            Column::new("disk", "Disk").width(150.0).sortable(),
-        ];
+    pub fn new(processes: Vec<ProcessInfo>, total_memory: u64) -> Self {
+        let mut history = HashMap::new();
+        record_history(&mut history, &processes);
+
+        let mut processes = SortableFilterable::new(processes)
+            .sort_by(ProcessColumn::Name.key(), |a: &ProcessInfo, b| a.name.cmp(&b.name))
+            .sort_by_key(ProcessColumn::Pid.key(), |p| p.pid)
+            .sort_by_key(ProcessColumn::User.key(), |p| p.user.clone())
+            .sort_by_key(ProcessColumn::Status.key(), |p| p.status.clone())
+            .sort_by_float(ProcessColumn::Cpu.key(), |p| p.cpu_usage as f64)
+            .sort_by_key(ProcessColumn::Memory.key(), |p| p.memory)
+            .sort_by_key(ProcessColumn::MemoryPercent.key(), |p| p.memory)
+            .sort_by_key(ProcessColumn::Disk.key(), |p| p.disk_usage)
+            .sort_by_key(ProcessColumn::Uptime.key(), |p| p.run_time)
+            .sort_by_key(ProcessColumn::Command.key(), |p| p.cmd.join(" "))
+            .sort_by_key(ProcessColumn::Path.key(), |p| p.exe.clone());
+        processes.set_sort(ProcessColumn::Cpu.key(), ColumnSort::Descending);
 
-        let mut delegate = Self {
+        let mut this = Self {
             processes,
-            filtered_processes: Vec::new(),
-            filter_query: String::new(),
-            sort_column: ProcessColumn::Cpu,
-            sort_ascending: false,
-            columns,
+            total_memory,
+            visible_columns: vec![],
+            columns: vec![],
+            show_details: false,
+            baseline: None,
+            history,
+            pinned: HashSet::new(),
+            tree_view: false,
+            collapsed: HashSet::new(),
+            group_by_name: false,
+            expanded_groups: HashSet::new(),
+            tree: vec![],
+            query: String::new(),
+            regex: false,
+            min_cpu: 0.,
+            min_memory: 0,
+            can_end_tasks: true,
+            on_end_task: None,
+        };
+        this.refresh_columns();
+        this
+    }
+
+    /// Set the handler of the "End task" item in the row context menu.
+    pub fn on_end_task(
+        mut self,
+        handler: impl Fn(&[ProcessInfo], &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_end_task = Some(Rc::new(handler));
+        self
+    }
+
+    /// Show or hide the "End task" item of the row context menu.
+    pub fn set_can_end_tasks(&mut self, can_end_tasks: bool) {
+        self.can_end_tasks = can_end_tasks;
+    }
+
+    /// Show or hide the optional command and path columns.
+    pub fn set_show_details(&mut self, show_details: bool) {
+        self.show_details = show_details;
+        self.refresh_columns();
+    }
+
+    fn refresh_columns(&mut self) {
+        let has_baseline = self.baseline.is_some();
+        self.visible_columns = ProcessColumn::all()
+            .into_iter()
+            .filter(|column| self.show_details || !column.is_optional())
+            .filter(|column| has_baseline || !column.is_delta())
+            .collect();
+        self.columns = self.visible_columns.iter().map(ProcessColumn::column).collect();
+    }
+
+    /// Store the current processes as the baseline, and show the delta columns against it.
+    pub fn take_baseline(&mut self) {
+        let baseline = self.processes.rows().iter().map(|p| (p.pid, p.clone())).collect();
+        self.baseline = Some(baseline);
+        self.refresh_columns();
+    }
+
+    /// Drop the baseline and hide the delta columns.
+    pub fn clear_baseline(&mut self) {
+        self.baseline = None;
+        self.refresh_columns();
+    }
+
+    pub fn has_baseline(&self) -> bool {
+        self.baseline.is_some()
+    }
+
+    /// Returns the process in the baseline with the same pid and name,
+    /// `None` if it started after the baseline was taken.
+    fn baseline_process(&self, process: &ProcessInfo) -> Option<&ProcessInfo> {
+        self.baseline
+            .as_ref()?
+            .get(&process.pid)
+            .filter(|baseline| baseline.name == process.name)
+    }
+
+    /// Returns the number of the started and exited processes since the baseline.
+    pub fn baseline_changes(&self) -> (usize, usize) {
+        let Some(baseline) = &self.baseline else {
+            return (0, 0);
         };
-        delegate.apply_filter();
-        delegate.sort();
-        delegate
+
+        let rows = self.processes.rows();
+        let started = rows.iter().filter(|p| self.baseline_process(p).is_none()).count();
+        let current: HashSet<(u32, &str)> = rows.iter().map(|p| (p.pid, p.name.as_str())).collect();
+        let exited = baseline
+            .values()
+            .filter(|old| !current.contains(&(old.pid, old.name.as_str())))
+            .count();
+        (started, exited)
     }
 
-    pub fn update_processes(&mut self, processes: Vec<ProcessInfo>) {
-        self.processes = processes;
-        self.apply_filter();
-        self.sort();
+    /// The plain text of the cell of the process, including the deltas against the baseline.
+    fn text(&self, column: ProcessColumn, process: &ProcessInfo) -> String {
+        let baseline = self.baseline_process(process);
+        match column {
+            column if !process.accessible => column.text(process, self.total_memory),
+            ProcessColumn::DeltaCpu | ProcessColumn::DeltaMemory if baseline.is_none() => {
+                "New".to_string()
+            }
+            ProcessColumn::DeltaCpu => {
+                let delta = process.cpu_usage - baseline.map_or(0., |p| p.cpu_usage);
+                format!("{:+.1}%", delta)
+            }
+            ProcessColumn::DeltaMemory => {
+                let delta = process.memory as i64 - baseline.map_or(0, |p| p.memory as i64);
+                format_bytes_delta(delta)
+            }
+            column => column.text(process, self.total_memory),
+        }
+    }
+
+    /// Pin or unpin the processes with the name.
+    pub fn toggle_pinned(&mut self, name: &str) {
+        if !self.pinned.remove(name) {
+            self.pinned.insert(name.to_string());
+        }
+        self.refresh_pinned();
+    }
+
+    /// Replace the pinned process names, e.g. with the names saved in the config.
+    pub fn set_pinned(&mut self, names: impl IntoIterator<Item = String>) {
+        self.pinned = names.into_iter().collect();
+        self.refresh_pinned();
+    }
+
+    /// Returns the names of the pinned processes, sorted.
+    pub fn pinned(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.pinned.iter().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn refresh_pinned(&mut self) {
+        let pinned = self.pinned.clone();
+        self.processes.set_pinned(move |p| pinned.contains(&p.name));
+        self.refresh_tree();
+    }
+
+    /// Switch between the process tree and the flat list, this ungroups the processes.
+    pub fn set_tree_view(&mut self, tree_view: bool) {
+        self.tree_view = tree_view;
+        if tree_view {
+            self.group_by_name = false;
+        }
+        self.refresh_tree();
+    }
+
+    /// Aggregate the processes with the same name into one row, this leaves the tree view.
+    pub fn set_group_by_name(&mut self, group_by_name: bool) {
+        self.group_by_name = group_by_name;
+        if group_by_name {
+            self.tree_view = false;
+        }
+        self.refresh_tree();
+    }
+
+    /// Expand or collapse the processes of the name group.
+    pub fn toggle_group(&mut self, name: &str) {
+        if !self.expanded_groups.remove(name) {
+            self.expanded_groups.insert(name.to_string());
+        }
+        self.refresh_tree();
+    }
+
+    /// Returns true if the rows are the process tree or the name groups.
+    fn is_nested(&self) -> bool {
+        self.tree_view || self.group_by_name
+    }
+
+    /// Expand or collapse the children of the process in the tree view.
+    pub fn toggle_collapsed(&mut self, pid: u32) {
+        if !self.collapsed.remove(&pid) {
+            self.collapsed.insert(pid);
+        }
+        self.refresh_tree();
+    }
+
+    fn refresh_tree(&mut self) {
+        self.tree = if self.tree_view {
+            build_tree(self.processes.iter(), &self.collapsed)
+        } else if self.group_by_name {
+            build_groups(&self.processes, &self.expanded_groups)
+        } else {
+            vec![]
+        };
+    }
+
+    pub fn update_processes(&mut self, processes: Vec<ProcessInfo>, total_memory: u64) {
+        self.total_memory = total_memory;
+        record_history(&mut self.history, &processes);
+        let pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+        self.collapsed.retain(|pid| pids.contains(pid));
+        let names: HashSet<&str> = processes.iter().map(|p| p.name.as_str()).collect();
+        self.expanded_groups.retain(|name| names.contains(name.as_str()));
+        // Diff by pid, so the rows don't move while the user is reading or scrolling them.
+        self.processes.merge_rows(processes, |p| p.pid);
+        self.refresh_tree();
+    }
+
+    /// Returns the row of the process with the pid, `None` if it is not visible.
+    pub fn row_of(&self, pid: u32) -> Option<usize> {
+        if self.is_nested() {
+            self.tree.iter().position(|row| row.process.pid == pid)
+        } else {
+            self.processes.iter().position(|p| p.pid == pid)
+        }
     }
 
     pub fn set_filter(&mut self, query: String) {
-        self.filter_query = query.to_lowercase();
-        self.apply_filter();
-        self.sort();
+        self.query = query;
+        self.refresh_filter();
+    }
+
+    /// Match the search patterns as regexes rather than substrings.
+    pub fn set_regex(&mut self, regex: bool) {
+        self.regex = regex;
+        self.refresh_filter();
+    }
+
+    /// Hide the processes using less than `min_cpu` percent of CPU, on top of the search query.
+    pub fn set_min_cpu(&mut self, min_cpu: f32) {
+        self.min_cpu = min_cpu;
+        self.refresh_filter();
+    }
+
+    /// Hide the processes using less than `min_memory` bytes, on top of the search query.
+    pub fn set_min_memory(&mut self, min_memory: u64) {
+        self.min_memory = min_memory;
+        self.refresh_filter();
+    }
+
+    fn refresh_filter(&mut self) {
+        let query = ProcessQuery::parse(&self.query, self.regex);
+        self.processes.set_filter(move |p, _| query.is_match(p));
+        let (min_cpu, min_memory) = (self.min_cpu, self.min_memory);
+        self.processes
+            .set_predicate(move |p| p.cpu_usage >= min_cpu && p.memory >= min_memory);
+        self.processes.set_query(&self.query);
+        self.refresh_tree();
+    }
+
+    /// Returns the recent usage of the process, oldest first, `None` if it has exited.
+    ///
+    /// Up to [`PROCESS_HISTORY_LEN`] samples are kept, one per update.
+    pub fn history(&self, pid: u32) -> Option<&VecDeque<ProcessSample>> {
+        self.history.get(&pid)
+    }
+
+    /// Returns all the processes, include the filtered out processes.
+    pub fn processes(&self) -> &[ProcessInfo] {
+        self.processes.rows()
     }
 
-    fn apply_filter(&mut self) {
-        if self.filter_query.is_empty() {
-            self.filtered_processes = self.processes.clone();
+    /// Returns the visible process at the row.
+    ///
+    /// In the tree view, a collapsed process includes the usage of its descendants,
+    /// and a name group includes the usage of all its processes.
+    pub fn process(&self, row_ix: usize) -> Option<&ProcessInfo> {
+        if self.is_nested() {
+            self.tree.get(row_ix).map(|row| &row.process)
         } else {
-            self.filtered_processes = self.processes
+            self.processes.get(row_ix)
+        }
+    }
+
+    /// Returns true if the row is a name group of several processes.
+    pub fn is_group(&self, row_ix: usize) -> bool {
+        self.group_by_name
+            && self
+                .tree
+                .get(row_ix)
+                .is_some_and(|row| row.group_pids.len() > 1)
+    }
+
+    /// Returns the processes to act on for the row, e.g. to end them.
+    ///
+    /// That is all the processes of a name group row, or the process of any other row.
+    pub fn row_processes(&self, row_ix: usize) -> Vec<ProcessInfo> {
+        if self.is_group(row_ix) {
+            let pids: HashSet<u32> = self.tree[row_ix].group_pids.iter().copied().collect();
+            self.processes
+                .rows()
                 .iter()
-                .filter(|p| {
-                    p.name.to_lowercase().contains(&self.filter_query) ||
-                    p.pid.to_string().contains(&self.filter_query)
-                })
+                .filter(|p| pids.contains(&p.pid))
                 .cloned()
-                .collect();
+                .collect()
+        } else {
+            self.process(row_ix).cloned().into_iter().collect()
         }
     }
+}
 
-    fn sort(&mut self) {
-        match self.sort_column {
-            ProcessColumn::Name => {
-                self.filtered_processes.sort_by(|a, b| {
-                    if self.sort_ascending {
-                        a.name.cmp(&b.name)
-                    } else {
-                        b.name.cmp(&a.name)
-                    }
-                });
-            }
-            ProcessColumn::Pid => {
-                self.filtered_processes.sort_by(|a, b| {
-                    if self.sort_ascending {
-                        a.pid.cmp(&b.pid)
-                    } else {
-                        b.pid.cmp(&a.pid)
-                    }
-                });
-            }
-            ProcessColumn::Cpu => {
-                self.filtered_processes.sort_by(|a, b| {
-                    if self.sort_ascending {
-                        a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap()
-                    } else {
-                        b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap()
-                    }
-                });
-            }
-            ProcessColumn::Memory => {
-                self.filtered_processes.sort_by(|a, b| {
-                    if self.sort_ascending {
-                        a.memory.cmp(&b.memory)
-                    } else {
-                        b.memory.cmp(&a.memory)
-                    }
-                });
-            }
-            ProcessColumn::Disk => {
-                self.filtered_processes.sort_by(|a, b| {
-                    if self.sort_ascending {
-                        a.disk_usage.cmp(&b.disk_usage)
-                    } else {
-                        b.disk_usage.cmp(&a.disk_usage)
-                    }
-                });
-            }
+/// Append a sample of each process, and drop the history of the exited processes.
+fn record_history(history: &mut HashMap<u32, VecDeque<ProcessSample>>, processes: &[ProcessInfo]) {
+    let pids: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+    history.retain(|pid, _| pids.contains(pid));
+
+    for process in processes {
+        let samples = history
+            .entry(process.pid)
+            .or_insert_with(|| VecDeque::with_capacity(PROCESS_HISTORY_LEN));
+        if samples.len() == PROCESS_HISTORY_LEN {
+            samples.pop_front();
         }
+        samples.push_back(ProcessSample {
+            cpu_usage: process.cpu_usage,
+            memory: process.memory,
+        });
     }
 }
 
 impl TableDelegate for ProcessesTableDelegate {
     fn columns_count(&self, _cx: &App) -> usize {
-        ProcessColumn::all().len()
+        self.visible_columns.len()
     }
 
     fn rows_count(&self, _cx: &App) -> usize {
-        self.filtered_processes.len()
+        if self.is_nested() {
+            self.tree.len()
+        } else {
+            self.processes.len()
+        }
     }
 
     fn column(&self, col_ix: usize, _cx: &App) -> Column {
@@ -176,23 +776,204 @@ impl TableDelegate for ProcessesTableDelegate {
         row_ix: usize,
         col_ix: usize,
         _window: &mut Window,
-        _cx: &mut Context<TableState<Self>>,
+        cx: &mut Context<TableState<Self>>,
     ) -> impl IntoElement {
-        let process = &self.filtered_processes[row_ix];
-        let all_columns = ProcessColumn::all();
-        let column = all_columns.get(col_ix).unwrap();
+        let (Some(process), Some(column)) =
+            (self.process(row_ix), self.visible_columns.get(col_ix).copied())
+        else {
+            return div();
+        };
 
         let text = match column {
-            ProcessColumn::Name => process.name.clone(),
-            ProcessColumn::Pid => process.pid.to_string(),
-            ProcessColumn::Cpu => format!("{:.1}%", process.cpu_usage),
-            ProcessColumn::Memory => format_bytes(process.memory),
-            ProcessColumn::Disk => format_bytes(process.disk_usage),
+            ProcessColumn::Name => {
+                let pid = process.pid;
+                let name = process.name.clone();
+                let is_pinned = self.pinned.contains(&name);
+                let tree_row = self
+                    .is_nested()
+                    .then(|| self.tree.get(row_ix))
+                    .flatten()
+                    .map(|row| (row.depth, row.has_children));
+                let group_len = self.tree.get(row_ix).map_or(0, |row| row.group_pids.len());
+                let group_by_name = self.group_by_name;
+                let is_collapsed = if group_by_name {
+                    !self.expanded_groups.contains(&name)
+                } else {
+                    self.collapsed.contains(&pid)
+                };
+                let label = if group_by_name && group_len > 1 {
+                    format!("{} ({})", name, group_len)
+                } else {
+                    name.clone()
+                };
+
+                return div().child(
+                    h_flex()
+                        .gap_1()
+                        .when_some(tree_row, |this, (depth, has_children)| {
+                            this.pl(px(depth as f32 * TREE_INDENT)).child(if has_children {
+                                Button::new(("expand", pid as usize))
+                                    .ghost()
+                                    .xsmall()
+                                    .icon(if is_collapsed {
+                                        IconName::ChevronRight
+                                    } else {
+                                        IconName::ChevronDown
+                                    })
+                                    .on_click(cx.listener({
+                                        let name = name.clone();
+                                        move |state, _, _, cx| {
+                                            if group_by_name {
+                                                state.delegate_mut().toggle_group(&name);
+                                            } else {
+                                                state.delegate_mut().toggle_collapsed(pid);
+                                            }
+                                            cx.notify();
+                                        }
+                                    }))
+                                    .into_any_element()
+                            } else {
+                                div().size_5().into_any_element()
+                            })
+                        })
+                        .child(
+                            Button::new(("pin", process.pid as usize))
+                                .ghost()
+                                .xsmall()
+                                .icon(if is_pinned {
+                                    IconName::StarFill
+                                } else {
+                                    IconName::Star
+                                })
+                                .tooltip(if is_pinned { "Unpin" } else { "Pin to top" })
+                                .on_click(cx.listener(move |state, _, _, cx| {
+                                    state.delegate_mut().toggle_pinned(&name);
+                                    let pinned = state.delegate().pinned();
+                                    Config::update(|config| config.pinned_processes = pinned);
+                                    cx.notify();
+                                })),
+                        )
+                        .child(label),
+                );
+            }
+            column if !process.accessible && column.is_restricted() => {
+                return div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(column.text(process, self.total_memory));
+            }
+            ProcessColumn::Status if process.is_zombie() => {
+                return div()
+                    .font_semibold()
+                    .text_color(cx.theme().danger)
+                    .child("Zombie (defunct)");
+            }
+            ProcessColumn::CpuHistory => {
+                let history = self
+                    .history(process.pid)
+                    .map(|history| history.iter().map(|s| s.cpu_usage as f64).collect::<Vec<_>>())
+                    .unwrap_or_default();
+
+                return div().child(
+                    Sparkline::new(&history, cx.theme().chart_2)
+                        .width(px(100.))
+                        .xsmall(),
+                );
+            }
+            ProcessColumn::MemoryPercent => {
+                let percent = memory_percent(process.memory, self.total_memory);
+                let color = if percent >= MEMORY_DANGER_PERCENT {
+                    cx.theme().danger
+                } else if percent >= MEMORY_WARNING_PERCENT {
+                    cx.theme().warning
+                } else {
+                    cx.theme().success
+                };
+
+                return div()
+                    .text_color(color)
+                    .child(column.text(process, self.total_memory));
+            }
+            ProcessColumn::DeltaCpu | ProcessColumn::DeltaMemory
+                if self.baseline_process(process).is_none() =>
+            {
+                return div()
+                    .font_semibold()
+                    .text_color(cx.theme().info)
+                    .child("New");
+            }
+            column => self.text(column, process),
         };
 
         div().child(text)
     }
 
+    fn cell_text(&self, row_ix: usize, col_ix: usize, _cx: &App) -> SharedString {
+        match (self.process(row_ix), self.visible_columns.get(col_ix)) {
+            (Some(process), Some(column)) => self.text(*column, process).into(),
+            _ => SharedString::default(),
+        }
+    }
+
+    fn context_menu(
+        &mut self,
+        row_ix: usize,
+        menu: PopupMenu,
+        _window: &mut Window,
+        _cx: &mut Context<TableState<Self>>,
+    ) -> PopupMenu {
+        let Some(process) = self.process(row_ix).cloned() else {
+            return menu;
+        };
+        let processes = self.row_processes(row_ix);
+        let is_group = self.is_group(row_ix);
+        let on_end_task = self.on_end_task.clone();
+        let pid = process.pid;
+        let name = process.name.clone();
+        let exe = process.exe.clone();
+
+        let menu = if self.can_end_tasks {
+            menu.item(
+                PopupMenuItem::new(if is_group { "End all tasks" } else { "End task" })
+                    .icon(IconName::CircleX)
+                    .disabled(on_end_task.is_none())
+                    .on_click(move |_, window, cx| {
+                        if let Some(on_end_task) = &on_end_task {
+                            on_end_task(&processes, window, cx);
+                        }
+                    }),
+            )
+            .separator()
+        } else {
+            menu
+        };
+
+        menu.item(
+            PopupMenuItem::new("Copy PID")
+                .icon(IconName::Copy)
+                .disabled(is_group)
+                .on_click(move |_, _, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(pid.to_string()));
+                }),
+        )
+        .item(
+            PopupMenuItem::new("Copy name")
+                .icon(IconName::Copy)
+                .on_click(move |_, _, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(name.clone()));
+                }),
+        )
+        .item(
+            PopupMenuItem::new("Open file location")
+                .icon(IconName::FolderOpen)
+                .disabled(exe.is_none())
+                .on_click(move |_, _, cx| {
+                    if let Some(exe) = &exe {
+                        cx.reveal_path(exe);
+                    }
+                }),
+        )
+    }
+
     fn perform_sort(
         &mut self,
         col_ix: usize,
@@ -200,48 +981,266 @@ impl TableDelegate for ProcessesTableDelegate {
         _window: &mut Window,
         cx: &mut Context<TableState<Self>>,
     ) {
-        if let Some(column) = ProcessColumn::all().get(col_ix) {
-            self.sort_column = *column;
-            self.sort_ascending = match sort {
-                ColumnSort::Ascending => true,
-                ColumnSort::Descending => false,
-                ColumnSort::Default => false,
+        if let Some(column) = self.visible_columns.get(col_ix) {
+            // Unsorted means the default sort, the busiest processes first.
+            let (key, sort) = match sort {
+                ColumnSort::Default => (ProcessColumn::Cpu.key(), ColumnSort::Descending),
+                sort => (column.key(), sort),
             };
-            self.sort();
+            self.processes.set_sort(key, sort);
+            self.refresh_tree();
             cx.notify();
         }
     }
 }
 
+pub enum ProcessesTabEvent {
+    /// End the processes with the pids, confirmed by the user, the `description` names them
+    /// in the notification of the result, see [`end_processes`].
+    EndProcesses { description: String, pids: Vec<u32> },
+}
+
 pub struct ProcessesTab {
     table_state: Entity<TableState<ProcessesTableDelegate>>,
     search_input: Entity<InputState>,
-    _subscription: Subscription,
+    /// Whether to also end the child processes in the end task dialog.
+    kill_children: bool,
+    /// Whether to show the command and path columns.
+    show_details: bool,
+    /// Whether to show the processes as a tree.
+    tree_view: bool,
+    /// Whether to aggregate the processes by name.
+    group_by_name: bool,
+    /// Whether the search patterns are regexes.
+    regex: bool,
+    /// Whether to hide the processes below [`IDLE_CPU_PERCENT`].
+    hide_idle: bool,
+    /// The pid of the process shown in the details panel, set by clicking its row.
+    details_pid: Option<u32>,
+    /// The width of the process table as a ratio of the tab width when the details panel is open.
+    details_split_ratio: f32,
+    /// Whether the processes can be ended, false in a replay.
+    can_end_tasks: bool,
+    /// The pending search, replaced on each keystroke to debounce the filtering.
+    search_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
 }
 
+impl EventEmitter<ProcessesTabEvent> for ProcessesTab {}
+
 impl ProcessesTab {
-    pub fn new(processes: Vec<ProcessInfo>, window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let delegate = ProcessesTableDelegate::new(processes);
+    pub fn new(
+        processes: Vec<ProcessInfo>,
+        total_memory: u64,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let view = cx.weak_entity();
+        let delegate = ProcessesTableDelegate::new(processes, total_memory).on_end_task(
+            move |processes, window, cx| {
+                _ = view.update(cx, |this, cx| {
+                    this.end_processes_from_menu(processes.to_vec(), window, cx)
+                });
+            },
+        );
+        let view = cx.weak_entity();
         let table_state = cx.new(|cx| {
             TableState::new(delegate, window, cx)
                 .sortable(true)
+                .multi_row_selectable(true)
+                .on_selection_change(move |selected_rows, cx| {
+                    // Update the End task button, after the table state has been updated.
+                    let view = view.clone();
+                    let is_empty = selected_rows.is_empty();
+                    cx.defer(move |cx| {
+                        _ = view.update(cx, |this, cx| {
+                            if is_empty {
+                                this.details_pid = None;
+                            }
+                            cx.notify();
+                        });
+                    });
+                })
+                .on_column_resize(|key, width, _, _| {
+                    Config::update(|config| {
+                        config.column_widths.insert(key.to_string(), width.as_f32());
+                    });
+                })
         });
 
         let search_input = cx.new(|cx| {
             InputState::new(window, cx)
-                .placeholder("Search processes by name or PID...")
+                .placeholder(SEARCH_PLACEHOLDER)
         });
 
-        let _subscription = cx.subscribe_in(&search_input, window, Self::on_search_input);
+        let _subscriptions = vec![
+            cx.subscribe_in(&search_input, window, Self::on_search_input),
+            // Update the End task button when the selection changed.
+            cx.subscribe(&table_state, Self::on_table_event),
+            // Keep the details panel live while it is open.
+            cx.observe(&table_state, |this, _, cx| {
+                if this.details_pid.is_some() {
+                    cx.notify();
+                }
+            }),
+        ];
 
         Self {
             table_state,
             search_input,
-            _subscription,
+            kill_children: false,
+            show_details: false,
+            tree_view: false,
+            group_by_name: false,
+            regex: false,
+            hide_idle: false,
+            details_pid: None,
+            details_split_ratio: DEFAULT_DETAILS_SPLIT_RATIO,
+            can_end_tasks: true,
+            search_task: None,
+            _subscriptions,
         }
     }
 
-    fn on_search_input(&mut self, _: &Entity<InputState>, _event: &InputEvent, _window: &mut Window, cx: &mut Context<Self>) {
+    fn on_search_input(&mut self, _: &Entity<InputState>, event: &InputEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        match event {
+            InputEvent::Change => {
+                self.search_task = Some(cx.spawn(async move |this, cx| {
+                    cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+                    _ = this.update(cx, |this, cx| this.apply_search(cx));
+                }));
+            }
+            InputEvent::PressEnter { .. } => {
+                self.search_task = None;
+                self.apply_search(cx);
+            }
+            _ => {}
+        }
+    }
+
+    fn on_table_event(
+        &mut self,
+        table_state: Entity<TableState<ProcessesTableDelegate>>,
+        event: &TableEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let TableEvent::SelectRow(row_ix) = event {
+            // A name group has no single process to show the details of.
+            let delegate = table_state.read(cx).delegate();
+            let pid = delegate
+                .process(*row_ix)
+                .filter(|_| !delegate.is_group(*row_ix))
+                .map(|p| p.pid);
+            self.details_pid = pid;
+        }
+        cx.notify();
+    }
+
+    fn on_close_details(&mut self, _: &CloseDetails, _: &mut Window, cx: &mut Context<Self>) {
+        if self.details_pid.take().is_none() {
+            cx.propagate();
+        }
+        cx.notify();
+    }
+
+    /// The panel with the details and recent usage of the clicked process,
+    /// `None` if it is closed or the process has exited.
+    fn render_details(&self, cx: &Context<Self>) -> Option<impl IntoElement> {
+        let pid = self.details_pid?;
+        let delegate = self.table_state.read(cx).delegate();
+        let process = delegate.processes().iter().find(|p| p.pid == pid)?;
+        let parent = process.parent_pid.map(|parent_pid| {
+            match delegate.processes().iter().find(|p| p.pid == parent_pid) {
+                Some(parent) => format!("{} ({})", parent.name, parent_pid),
+                None => parent_pid.to_string(),
+            }
+        });
+        let (cpu_history, memory_history): (Vec<f64>, Vec<f64>) = delegate
+            .history(pid)
+            .into_iter()
+            .flatten()
+            .map(|sample| (sample.cpu_usage as f64, sample.memory as f64))
+            .unzip();
+
+        let items = [
+            ("PID", pid.to_string()),
+            ("Status", ProcessColumn::Status.text(process, 0)),
+            ("User", ProcessColumn::User.text(process, 0)),
+            ("Parent", parent.unwrap_or_else(|| "—".to_string())),
+            ("Threads", process.threads.map_or_else(|| "—".to_string(), |t| t.to_string())),
+            ("Uptime", format_duration(process.run_time)),
+            ("CPU", format!("{:.1}%", process.cpu_usage)),
+            ("Memory", format_bytes(process.memory)),
+        ];
+        let label = |label: &'static str| {
+            div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(label)
+        };
+        let long_text = |text: String| {
+            div()
+                .text_xs()
+                .font_family(cx.theme().mono_font_family.clone())
+                .text_color(cx.theme().foreground)
+                .child(text)
+        };
+
+        Some(
+            ScrollView::vertical("process-details")
+                .size_full()
+                .border_1()
+                .border_color(cx.theme().border)
+                .rounded(cx.theme().radius)
+                .bg(cx.theme().background)
+                .scrollbar_thickness(px(6.))
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .p_4()
+                        .child(
+                            h_flex()
+                                .justify_between()
+                                .gap_2()
+                                .child(
+                                    div()
+                                        .text_lg()
+                                        .font_semibold()
+                                        .truncate()
+                                        .child(process.name.clone())
+                                )
+                                .child(
+                                    Button::new("close-details")
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(IconName::Close)
+                                        .tooltip("Close (Esc)")
+                                        .on_click(cx.listener(|this, _, _, cx| {
+                                            this.details_pid = None;
+                                            cx.notify();
+                                        }))
+                                )
+                        )
+                        .children(items.into_iter().map(|(name, value)| {
+                            h_flex()
+                                .justify_between()
+                                .child(label(name))
+                                .child(div().text_sm().font_medium().child(value))
+                        }))
+                        .child(label("Command line"))
+                        .child(long_text(ProcessColumn::Command.text(process, 0)))
+                        .child(label("Path"))
+                        .child(long_text(ProcessColumn::Path.text(process, 0)))
+                        .child(label("CPU history"))
+                        .child(Sparkline::new(&cpu_history, cx.theme().chart_2).small())
+                        .child(label("Memory history"))
+                        .child(Sparkline::new(&memory_history, cx.theme().chart_3).small()),
+                ),
+        )
+    }
+
+    /// Filter the table by the current search query.
+    fn apply_search(&mut self, cx: &mut Context<Self>) {
         let query = self.search_input.read(cx).value();
         self.table_state.update(cx, |state, _cx| {
             state.delegate_mut().set_filter(query.to_string());
@@ -249,16 +1248,427 @@ impl ProcessesTab {
         cx.notify();
     }
 
-    pub fn update_processes(&mut self, processes: Vec<ProcessInfo>, cx: &mut App) {
-        self.table_state.update(cx, |state, _cx| {
-            state.delegate_mut().update_processes(processes);
+    /// Move the focus to the search input.
+    pub fn focus_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_input.update(cx, |input, cx| input.focus(window, cx));
+    }
+
+    fn on_focus_search(&mut self, _: &FocusSearch, window: &mut Window, cx: &mut Context<Self>) {
+        self.focus_search(window, cx);
+    }
+
+    /// Clear the search and move the focus from the search input to the table.
+    fn on_clear_search(&mut self, _: &ClearSearch, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_search("", window, cx);
+        self.search_task = None;
+        self.apply_search(cx);
+        self.table_state.read(cx).focus_handle(cx).focus(window, cx);
+    }
+
+    /// Set the search query, the table will be filtered after the [`SEARCH_DEBOUNCE`] delay.
+    pub fn set_search(&mut self, query: impl Into<SharedString>, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_input.update(cx, |input, cx| {
+            input.set_value(query, window, cx);
+        });
+    }
+
+    pub fn processes(&self, cx: &App) -> Vec<ProcessInfo> {
+        self.table_state.read(cx).delegate().processes().to_vec()
+    }
+
+    /// Restore the saved column widths, keyed by column key.
+    pub fn set_column_widths(&mut self, widths: &HashMap<String, f32>, cx: &mut Context<Self>) {
+        self.table_state.update(cx, |state, cx| {
+            for (key, width) in widths {
+                state.set_column_width(key.clone(), px(*width), cx);
+            }
+        });
+    }
+
+    /// Show or hide the End task actions, hide them when the processes are not of the live
+    /// system, e.g. in a replay.
+    pub fn set_can_end_tasks(&mut self, can_end_tasks: bool, cx: &mut Context<Self>) {
+        self.can_end_tasks = can_end_tasks;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().set_can_end_tasks(can_end_tasks);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// Ask the app to end the processes with the pids, after the user has confirmed.
+    fn request_end_processes(&mut self, description: String, pids: Vec<u32>, cx: &mut Context<Self>) {
+        cx.emit(ProcessesTabEvent::EndProcesses { description, pids });
+    }
+
+    /// Restore the saved split between the table and the details panel.
+    pub fn set_details_split_ratio(&mut self, ratio: f32, cx: &mut Context<Self>) {
+        self.details_split_ratio = ratio;
+        cx.notify();
+    }
+
+    /// Pin the processes with the names to the top of the table.
+    pub fn set_pinned(&mut self, names: Vec<String>, cx: &mut Context<Self>) {
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().set_pinned(names);
+            cx.notify();
+        });
+    }
+
+    /// Copy the visible processes as CSV to the clipboard.
+    fn export_csv(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.table_state.read(cx);
+        let csv = state.export_csv(cx);
+        let rows_count = state.delegate().rows_count(cx);
+
+        cx.write_to_clipboard(ClipboardItem::new_string(csv));
+        window.push_notification(
+            Notification::success(format!("Copied {} processes as CSV.", rows_count)),
+            cx,
+        );
+    }
+
+    fn set_show_details(&mut self, show_details: bool, cx: &mut Context<Self>) {
+        self.show_details = show_details;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().set_show_details(show_details);
+            state.refresh(cx);
+        });
+        cx.notify();
+    }
+
+    /// Take or clear the baseline to compare the processes with.
+    fn toggle_baseline(&mut self, cx: &mut Context<Self>) {
+        self.table_state.update(cx, |state, cx| {
+            let delegate = state.delegate_mut();
+            if delegate.has_baseline() {
+                delegate.clear_baseline();
+            } else {
+                delegate.take_baseline();
+            }
+            state.refresh(cx);
+        });
+        cx.notify();
+    }
+
+    fn set_tree_view(&mut self, tree_view: bool, cx: &mut Context<Self>) {
+        self.tree_view = tree_view;
+        self.group_by_name &= !tree_view;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().set_tree_view(tree_view);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    fn set_regex(&mut self, regex: bool, cx: &mut Context<Self>) {
+        self.regex = regex;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().set_regex(regex);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    fn set_hide_idle(&mut self, hide_idle: bool, cx: &mut Context<Self>) {
+        self.hide_idle = hide_idle;
+        let min_cpu = if hide_idle { IDLE_CPU_PERCENT } else { 0. };
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().set_min_cpu(min_cpu);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    fn set_group_by_name(&mut self, group_by_name: bool, cx: &mut Context<Self>) {
+        self.group_by_name = group_by_name;
+        self.tree_view &= !group_by_name;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().set_group_by_name(group_by_name);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// Returns the processes of the selected rows, a name group row is replaced by all
+    /// the processes of the group, and the labels of the selected groups, e.g. `chrome (24)`.
+    fn selected_processes(&self, cx: &App) -> (Vec<ProcessInfo>, Vec<String>) {
+        let state = self.table_state.read(cx);
+        let delegate = state.delegate();
+        let mut pids = HashSet::new();
+        let mut processes = vec![];
+        let mut groups = vec![];
+        for &row_ix in state.selected_rows() {
+            let row_processes = delegate.row_processes(row_ix);
+            if delegate.is_group(row_ix) {
+                if let Some(first) = row_processes.first() {
+                    groups.push(format!("{} ({})", first.name, row_processes.len()));
+                }
+            }
+            // A group row and its expanded processes may be selected together.
+            processes.extend(row_processes.into_iter().filter(|p| pids.insert(p.pid)));
+        }
+
+        (processes, groups)
+    }
+
+    /// Confirm and end the selected process, and optionally its child processes.
+    ///
+    /// When multiple processes are selected, or a name group of several processes,
+    /// they are ended together after one confirmation.
+    fn end_task(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.can_end_tasks {
+            return;
+        }
+
+        let (mut processes, groups) = self.selected_processes(cx);
+        if processes.len() > 1 {
+            self.confirm_end_processes(processes, groups, window, cx);
+        } else if let Some(process) = processes.pop() {
+            self.confirm_end_process(process, window, cx);
+        }
+    }
+
+    /// Confirm and end the processes, the `groups` are the labels of the name groups
+    /// they include, to tell that all the processes of the groups will be ended.
+    fn confirm_end_processes(
+        &mut self,
+        processes: Vec<ProcessInfo>,
+        groups: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        /// The max number of process names listed in the dialog.
+        const MAX_LISTED: usize = 8;
+
+        let view = cx.weak_entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let view = view.clone();
+            let has_system_critical = processes.iter().any(|p| p.is_system_critical());
+            let pids = processes.iter().map(|p| p.pid).collect::<Vec<_>>();
+
+            dialog
+                .confirm()
+                .title(format!("End {} tasks?", processes.len()))
+                .w(px(420.))
+                .button_props(
+                    DialogButtonProps::default()
+                        .ok_text("End tasks")
+                        .ok_variant(ButtonVariant::Danger),
+                )
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child("Are you sure you want to end these processes? Unsaved data will be lost.")
+                        .when(!groups.is_empty(), |this| {
+                            this.child(format!(
+                                "This ends every process of {}.",
+                                groups.join(", ")
+                            ))
+                        })
+                        .child(
+                            v_flex()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .children(processes.iter().take(MAX_LISTED).map(|p| {
+                                    format!("{} (PID {})", p.name, p.pid)
+                                }))
+                                .when(processes.len() > MAX_LISTED, |this| {
+                                    this.child(format!("and {} more", processes.len() - MAX_LISTED))
+                                }),
+                        )
+                        .when(has_system_critical, |this| {
+                            this.child(
+                                div()
+                                    .font_semibold()
+                                    .text_color(cx.theme().danger)
+                                    .child("Some of them are system processes, ending them may make the system unstable or log you out."),
+                            )
+                        }),
+                )
+                .on_result(move |confirmed, _, cx| {
+                    if confirmed {
+                        let description = format!("{} processes", pids.len());
+                        _ = view.update(cx, |this, cx| {
+                            this.request_end_processes(description, pids.clone(), cx)
+                        });
+                    }
+                })
+        });
+    }
+
+    /// End the processes of a row from the row context menu.
+    ///
+    /// A single process asks for confirmation only if it is system-critical, since picking
+    /// the menu item is already an explicit choice, the processes of a name group always ask.
+    fn end_processes_from_menu(
+        &mut self,
+        mut processes: Vec<ProcessInfo>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if processes.len() > 1 {
+            let groups = vec![format!("{} ({})", processes[0].name, processes.len())];
+            self.confirm_end_processes(processes, groups, window, cx);
+            return;
+        }
+
+        let Some(process) = processes.pop() else {
+            return;
+        };
+        if process.is_system_critical() {
+            self.confirm_end_process(process, window, cx);
+        } else {
+            let description = format!("\"{}\" (PID {})", process.name, process.pid);
+            self.request_end_processes(description, vec![process.pid], cx);
+        }
+    }
+
+    fn confirm_end_process(
+        &mut self,
+        process: ProcessInfo,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let children = descendants(self.table_state.read(cx).delegate().processes(), process.pid);
+        self.kill_children = false;
+
+        let view = cx.weak_entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let kill_children = view
+                .upgrade()
+                .is_some_and(|view| view.read(cx).kill_children);
+            let pid = process.pid;
+            let name = process.name.clone();
+            let is_system_critical = process.is_system_critical();
+            let children = children.clone();
+
+            dialog
+                .confirm()
+                .title("End task?")
+                .w(px(420.))
+                .button_props(
+                    DialogButtonProps::default()
+                        .ok_text("End task")
+                        .ok_variant(ButtonVariant::Danger),
+                )
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(format!(
+                            "Are you sure you want to end \"{}\" (PID {})? Unsaved data will be lost.",
+                            name, pid
+                        ))
+                        .when(is_system_critical, |this| {
+                            this.child(
+                                div()
+                                    .font_semibold()
+                                    .text_color(cx.theme().danger)
+                                    .child("This is a system process, ending it may make the system unstable or log you out."),
+                            )
+                        })
+                        .when(!children.is_empty(), |this| {
+                            let view = view.clone();
+                            this.child(
+                                Checkbox::new("kill-children")
+                                    .label(format!(
+                                        "Also end {} child process(es)",
+                                        children.len()
+                                    ))
+                                    .checked(kill_children)
+                                    .on_click(move |checked, _, cx| {
+                                        _ = view.update(cx, |this, cx| {
+                                            this.kill_children = *checked;
+                                            cx.notify();
+                                        });
+                                    }),
+                            )
+                        }),
+                )
+                .on_result(move |confirmed, _, cx| {
+                    if !confirmed {
+                        return;
+                    }
+
+                    let mut pids = if kill_children { children.clone() } else { vec![] };
+                    // End the children first, so they are not re-parented.
+                    pids.reverse();
+                    pids.push(pid);
+
+                    let description = format!("\"{}\" (PID {})", name, pid);
+                    _ = view.update(cx, |this, cx| this.request_end_processes(description, pids, cx));
+                })
         });
     }
+
+    pub fn update_processes(&mut self, processes: Vec<ProcessInfo>, total_memory: u64, cx: &mut App) {
+        self.table_state.update(cx, |state, cx| {
+            // Follow the selected processes, their rows move when the processes above exit.
+            let selected_pids: Vec<u32> = state
+                .selected_rows()
+                .iter()
+                .filter_map(|&row_ix| state.delegate().process(row_ix))
+                .map(|p| p.pid)
+                .collect();
+
+            state.delegate_mut().update_processes(processes, total_memory);
+
+            let selected_rows = selected_pids
+                .iter()
+                .filter_map(|&pid| state.delegate().row_of(pid))
+                .collect();
+            state.set_selected_rows(selected_rows, cx);
+        });
+    }
+}
+
+/// Kill the pids by the `monitor`, and notify the result of ending the processes
+/// described by `description`, see [`ProcessesTabEvent::EndProcesses`].
+///
+/// The processes that exited before they were killed are reported apart from the failures.
+pub fn end_processes(
+    monitor: &mut SystemMonitor,
+    description: &str,
+    pids: &[u32],
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let mut errors = vec![];
+    let mut exited = 0;
+    for pid in pids {
+        match monitor.kill_process(*pid) {
+            Ok(true) => {}
+            Ok(false) => exited += 1,
+            Err(err) => errors.push(err),
+        }
+    }
+
+    let notification = if !errors.is_empty() {
+        Notification::error(errors.join("\n")).autohide_after(ERROR_NOTIFICATION_DURATION)
+    } else if exited == pids.len() {
+        Notification::info(format!("{} had already exited.", description))
+    } else if exited > 0 {
+        Notification::success(format!(
+            "Ended {}, {} of them had already exited.",
+            description, exited
+        ))
+    } else {
+        Notification::success(format!("Ended {}.", description))
+    };
+    window.push_notification(notification, cx);
 }
 
 impl Render for ProcessesTab {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected_count = self.table_state.read(cx).selected_rows().len();
+        let delegate = self.table_state.read(cx).delegate();
+        let baseline_changes = delegate.has_baseline().then(|| delegate.baseline_changes());
+
         v_flex()
+            .key_context(CONTEXT)
+            .on_action(cx.listener(Self::on_focus_search))
+            .on_action(cx.listener(Self::on_clear_search))
+            .on_action(cx.listener(Self::on_close_details))
             .size_full()
             .p_4()
             .gap_4()
@@ -273,19 +1683,200 @@ impl Render for ProcessesTab {
                             .child("Processes")
                     )
                     .child(
-                        div()
-                            .w_64()
-                            .child(Input::new(&self.search_input))
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Checkbox::new("tree-view")
+                                    .label("Tree view")
+                                    .checked(self.tree_view)
+                                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                        this.set_tree_view(*checked, cx);
+                                    })),
+                            )
+                            .child(
+                                Checkbox::new("group-by-name")
+                                    .label("Group by name")
+                                    .checked(self.group_by_name)
+                                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                        this.set_group_by_name(*checked, cx);
+                                    })),
+                            )
+                            .child(
+                                Checkbox::new("hide-idle")
+                                    .label(format!("Hide idle (<{}% CPU)", IDLE_CPU_PERCENT))
+                                    .checked(self.hide_idle)
+                                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                        this.set_hide_idle(*checked, cx);
+                                    })),
+                            )
+                            .child(
+                                Checkbox::new("show-details")
+                                    .label("Show command and path")
+                                    .checked(self.show_details)
+                                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                        this.set_show_details(*checked, cx);
+                                    })),
+                            )
+                            .child(
+                                div()
+                                    .w_64()
+                                    .child(Input::new(&self.search_input))
+                            )
+                            .child(
+                                Checkbox::new("regex")
+                                    .label("Regex")
+                                    .checked(self.regex)
+                                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                        this.set_regex(*checked, cx);
+                                    })),
+                            )
+                            .when_some(baseline_changes, |this, (started, exited)| {
+                                this.child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(format!("{} new, {} exited", started, exited))
+                                )
+                            })
+                            .child(
+                                Button::new("baseline")
+                                    .outline()
+                                    .map(|this| if baseline_changes.is_some() {
+                                        this.label("Clear snapshot")
+                                            .tooltip("Stop comparing with the snapshot")
+                                    } else {
+                                        this.label("Snapshot")
+                                            .tooltip("Compare the processes with their current usage")
+                                    })
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.toggle_baseline(cx);
+                                    }))
+                            )
+                            .child(
+                                Button::new("export-csv")
+                                    .outline()
+                                    .icon(IconName::Copy)
+                                    .label("Export CSV")
+                                    .tooltip("Copy the processes as CSV, to paste into a spreadsheet")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.export_csv(window, cx);
+                                    }))
+                            )
+                            .when(self.can_end_tasks, |this| {
+                                this.child(
+                                    Button::new("end-task")
+                                        .danger()
+                                        .map(|this| if selected_count > 1 {
+                                            this.label(format!("End {} tasks", selected_count))
+                                        } else {
+                                            this.label("End task")
+                                        })
+                                        .disabled(selected_count == 0)
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.end_task(window, cx);
+                                        }))
+                                )
+                            })
                     )
             )
             .child(
                 div()
                     .flex_1()
-                    .child(
-                        Table::new(&self.table_state)
+                    .w_full()
+                    .map(|this| {
+                        let table = Table::new(&self.table_state)
                             .stripe(true)
-                            .bordered(true)
-                    )
+                            .bordered(true);
+
+                        match self.render_details(cx) {
+                            Some(details) => this.child(
+                                SplitPane::horizontal("process-details-split")
+                                    .ratio(self.details_split_ratio)
+                                    .min_size(px(240.))
+                                    .on_resize(cx.listener(|this, ratio: &f32, _, cx| {
+                                        let ratio = *ratio;
+                                        this.details_split_ratio = ratio;
+                                        Config::update(|config| config.details_split_ratio = ratio);
+                                        cx.notify();
+                                    }))
+                                    .first(div().size_full().pr_2().child(table))
+                                    .second(div().size_full().pl_2().child(details))
+                            ),
+                            None => this.child(table),
+                        }
+                    })
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, cpu_usage: f32) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent_pid: None,
+            name: name.to_string(),
+            user: None,
+            cmd: vec![],
+            exe: None,
+            status: "Run".to_string(),
+            cpu_usage,
+            memory: 0,
+            disk_usage: 0,
+            start_time: 0,
+            run_time: 0,
+            threads: None,
+            accessible: true,
+        }
+    }
+
+    fn pids(processes: &[ProcessInfo]) -> Vec<u32> {
+        processes.iter().map(|p| p.pid).collect()
+    }
+
+    #[test]
+    fn test_build_groups() {
+        let mut delegate = ProcessesTableDelegate::new(
+            vec![
+                process(1, "chrome", 1.),
+                process(2, "bash", 5.),
+                process(3, "chrome", 2.),
+                process(4, "chrome", 3.),
+            ],
+            0,
+        );
+        delegate.set_group_by_name(true);
+
+        // The groups are sorted by the aggregated CPU usage, the first process is the busiest.
+        assert_eq!(delegate.tree.len(), 2);
+        assert_eq!(delegate.tree[0].process.pid, 4);
+        assert_eq!(delegate.tree[0].process.cpu_usage, 6.);
+        assert_eq!(delegate.tree[0].group_pids, vec![4, 3, 1]);
+        assert!(delegate.tree[0].has_children);
+        assert_eq!(delegate.tree[1].process.pid, 2);
+        assert_eq!(delegate.tree[1].group_pids, vec![2]);
+        assert!(!delegate.tree[1].has_children);
+
+        // A group row acts on all its processes, a group of one is a plain process.
+        assert!(delegate.is_group(0));
+        assert_eq!(pids(&delegate.row_processes(0)), vec![1, 3, 4]);
+        assert!(!delegate.is_group(1));
+        assert_eq!(pids(&delegate.row_processes(1)), vec![2]);
+
+        // The expanded processes act on themselves only.
+        delegate.toggle_group("chrome");
+        assert_eq!(delegate.tree.len(), 5);
+        assert!(delegate.is_group(0));
+        assert_eq!(pids(&delegate.row_processes(0)), vec![1, 3, 4]);
+        assert!(!delegate.is_group(1));
+        assert_eq!(pids(&delegate.row_processes(1)), vec![4]);
+        assert!(delegate.tree[1].group_pids.is_empty());
+
+        // Without grouping, the rows are the processes.
+        delegate.set_group_by_name(false);
+        assert!(!delegate.is_group(0));
+        assert_eq!(pids(&delegate.row_processes(0)), vec![2]);
+    }
+}
@@ -0,0 +1,170 @@
+//! The system tray icon, shows the CPU and memory usage and a menu to show/hide the window or quit.
+//!
+//! The tray is backed by the `tray-icon` crate, it is only available on Windows and macOS,
+//! because on Linux it requires a GTK event loop that GPUI does not run.
+//! On the other platforms [`Tray::new`] returns `None` and the app works without a tray.
+#![cfg_attr(
+    not(any(target_os = "windows", target_os = "macos")),
+    allow(dead_code)
+)]
+
+use monitor::{SystemSnapshot, format_bytes};
+
+/// The action triggered from the tray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    /// The tray icon was clicked, focus the main window.
+    Activate,
+    /// Toggle the main window visibility.
+    ToggleWindow,
+    /// Quit the app.
+    Quit,
+}
+
+/// Returns the tooltip text of the tray for the snapshot.
+pub fn tooltip(snapshot: &SystemSnapshot) -> String {
+    format!(
+        "CPU: {:.0}%\nMemory: {} / {} ({:.0}%)",
+        snapshot.global_cpu_usage,
+        format_bytes(snapshot.memory.used),
+        format_bytes(snapshot.memory.total),
+        snapshot.memory.used_percent(),
+    )
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub use platform::Tray;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod platform {
+    use tray_icon::{
+        Icon, MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent,
+        menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    };
+
+    use super::{TrayAction, tooltip};
+    use monitor::SystemSnapshot;
+
+    const ICON_SIZE: u32 = 16;
+
+    pub struct Tray {
+        icon: TrayIcon,
+        toggle_item: MenuItem,
+        quit_item: MenuItem,
+    }
+
+    impl Tray {
+        /// Create the tray icon, returns `None` if the platform failed to create it.
+        pub fn new() -> Option<Self> {
+            let toggle_item = MenuItem::new("Show/Hide Window", true, None);
+            let quit_item = MenuItem::new("Quit", true, None);
+
+            let menu = Menu::new();
+            if let Err(err) =
+                menu.append_items(&[&toggle_item, &PredefinedMenuItem::separator(), &quit_item])
+            {
+                log::warn!("Failed to create tray menu: {}", err);
+                return None;
+            }
+
+            let icon = TrayIconBuilder::new()
+                .with_menu(Box::new(menu))
+                .with_menu_on_left_click(false)
+                .with_tooltip("Task Manager")
+                .with_icon(icon(0.))
+                .build();
+
+            match icon {
+                Ok(icon) => Some(Self {
+                    icon,
+                    toggle_item,
+                    quit_item,
+                }),
+                Err(err) => {
+                    log::warn!("Failed to create tray icon: {}", err);
+                    None
+                }
+            }
+        }
+
+        /// Update the tooltip and icon by the snapshot.
+        pub fn update(&self, snapshot: &SystemSnapshot) {
+            _ = self.icon.set_tooltip(Some(tooltip(snapshot)));
+            _ = self.icon.set_icon(Some(icon(snapshot.global_cpu_usage)));
+            #[cfg(target_os = "macos")]
+            self.icon
+                .set_title(Some(format!("{:.0}%", snapshot.global_cpu_usage)));
+        }
+
+        /// Returns the pending actions from the tray icon and menu.
+        pub fn poll(&self) -> Vec<TrayAction> {
+            let mut actions = vec![];
+
+            while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+                if let TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                {
+                    actions.push(TrayAction::Activate);
+                }
+            }
+
+            while let Ok(event) = MenuEvent::receiver().try_recv() {
+                if event.id == self.toggle_item.id() {
+                    actions.push(TrayAction::ToggleWindow);
+                } else if event.id == self.quit_item.id() {
+                    actions.push(TrayAction::Quit);
+                }
+            }
+
+            actions
+        }
+    }
+
+    /// Draw a CPU meter icon, the bar height is the CPU usage.
+    fn icon(cpu_usage: f32) -> Icon {
+        let filled = ((cpu_usage.clamp(0., 100.) / 100.) * ICON_SIZE as f32).round() as u32;
+        let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+        for y in 0..ICON_SIZE {
+            for x in 0..ICON_SIZE {
+                let border = x == 0 || y == 0 || x == ICON_SIZE - 1 || y == ICON_SIZE - 1;
+                let pixel = if border {
+                    [0x80, 0x80, 0x80, 0xff]
+                } else if ICON_SIZE - y <= filled {
+                    [0x22, 0xc5, 0x5e, 0xff]
+                } else {
+                    [0, 0, 0, 0]
+                };
+                rgba.extend_from_slice(&pixel);
+            }
+        }
+
+        Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).expect("valid icon size")
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub use fallback::Tray;
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod fallback {
+    use super::TrayAction;
+    use monitor::SystemSnapshot;
+
+    /// The tray is not supported on this platform.
+    pub struct Tray;
+
+    impl Tray {
+        pub fn new() -> Option<Self> {
+            None
+        }
+
+        pub fn update(&self, _: &SystemSnapshot) {}
+
+        pub fn poll(&self) -> Vec<TrayAction> {
+            vec![]
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::rc::Rc;
 
-use gpui::{px, App, Background, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui::{
+    px, AnyElement, App, Background, Bounds, Hsla, Pixels, Point, SharedString, TextAlign, Window,
+};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
@@ -8,11 +10,21 @@ use crate::{
     plot::{
         scale::{Scale, ScaleLinear, ScalePoint, Sealed},
         shape::Area,
-        AxisText, Grid, Plot, PlotAxis, StrokeStyle, AXIS_GAP,
+        tooltip::{point_tooltip, TooltipValue},
+        label::TEXT_HEIGHT,
+        AxisText, Grid, Legend, Plot, PlotAxis, PlotSize, PlotSized, StrokeStyle, AXIS_GAP,
+        LEGEND_HEIGHT,
     },
     ActiveTheme, PixelsExt,
 };
 
+use super::{has_x_label, x_range, y_domain, y_ticks, Series};
+
+/// The stroke, fill, stroke style and Y accessor of an area.
+type AreaStyle<T, Y> = (Hsla, Background, StrokeStyle, Rc<dyn Fn(&T) -> Y>);
+/// The bottom and top of an area, the bottom is `None` for an area from the X axis.
+type AreaBounds<T, Y> = (Option<Rc<dyn Fn(&T) -> Y>>, Rc<dyn Fn(&T) -> Y>);
+
 #[derive(IntoPlot)]
 pub struct AreaChart<T, X, Y>
 where
@@ -23,10 +35,18 @@ where
     data: Vec<T>,
     x: Option<Rc<dyn Fn(&T) -> X>>,
     y: Vec<Rc<dyn Fn(&T) -> Y>>,
+    series: Vec<Series<T, Y>>,
     strokes: Vec<Hsla>,
     stroke_styles: Vec<StrokeStyle>,
     fills: Vec<Background>,
+    y_range: Option<(Y, Y)>,
+    y_axis: bool,
     tick_margin: usize,
+    /// The number of samples of the fixed X window, `None` to fit the data.
+    x_window: Option<usize>,
+    /// Whether each area is stacked on top of the previous areas.
+    stacked: bool,
+    size: PlotSize,
 }
 
 impl<T, X, Y> AreaChart<T, X, Y>
@@ -43,9 +63,15 @@ where
             stroke_styles: vec![],
             strokes: vec![],
             fills: vec![],
+            y_range: None,
+            y_axis: false,
             tick_margin: 1,
+            x_window: None,
+            stacked: false,
+            size: PlotSize::default(),
             x: None,
             y: vec![],
+            series: vec![],
         }
     }
 
@@ -59,6 +85,24 @@ where
         self
     }
 
+    /// Add a named series with its own color, listed in the legend.
+    ///
+    /// The series are drawn after the `y` areas and share their Y scale, the area is
+    /// filled with a translucent `color`.
+    pub fn series(
+        mut self,
+        name: impl Into<SharedString>,
+        color: impl Into<Hsla>,
+        y: impl Fn(&T) -> Y + 'static,
+    ) -> Self {
+        self.series.push(Series {
+            name: name.into(),
+            color: color.into(),
+            y: Rc::new(y),
+        });
+        self
+    }
+
     pub fn stroke(mut self, stroke: impl Into<Hsla>) -> Self {
         self.strokes.push(stroke.into());
         self
@@ -84,10 +128,159 @@ where
         self
     }
 
+    /// Fix the range of the Y axis, e.g. `0..100` for percentages, default is fit the data
+    /// and start from 0.
+    pub fn y_range(mut self, min: Y, max: Y) -> Self {
+        self.y_range = Some((min, max));
+        self
+    }
+
+    /// Show the value labels of the Y axis, with gridlines at the min, mid and max.
+    pub fn y_axis(mut self, y_axis: bool) -> Self {
+        self.y_axis = y_axis;
+        self
+    }
+
     pub fn tick_margin(mut self, tick_margin: usize) -> Self {
         self.tick_margin = tick_margin;
         self
     }
+
+    /// Show the last `samples` data points at a fixed spacing, the newest at the right edge.
+    ///
+    /// The line scrolls left as data arrives rather than stretching to fill the width,
+    /// and the X labels keep their positions. The older data points are dropped.
+    pub fn x_window(mut self, samples: usize) -> Self {
+        let excess = self.data.len().saturating_sub(samples);
+        self.data.drain(..excess);
+        self.x_window = Some(samples);
+        self
+    }
+
+    /// Stack each area on top of the previous areas, so the top of the stack is the total,
+    /// e.g. the received and sent bytes of the network.
+    ///
+    /// Without stacking the areas overlap, each from the X axis. A single area is the same
+    /// either way.
+    pub fn stacked(mut self, stacked: bool) -> Self {
+        self.stacked = stacked;
+        self
+    }
+
+    /// Returns the style of each area, the `y` areas first.
+    fn areas(&self, cx: &App) -> Vec<AreaStyle<T, Y>> {
+        let default_style = *self.stroke_styles.first().unwrap_or(&Default::default());
+
+        self.y
+            .iter()
+            .enumerate()
+            .map(|(i, y_fn)| {
+                let fill = *self
+                    .fills
+                    .get(i)
+                    .unwrap_or(&cx.theme().chart_2.opacity(0.4).into());
+                let stroke = *self.strokes.get(i).unwrap_or(&cx.theme().chart_2);
+                let stroke_style = *self.stroke_styles.get(i).unwrap_or(&default_style);
+
+                (stroke, fill, stroke_style, y_fn.clone())
+            })
+            .chain(self.series.iter().map(|series| {
+                (
+                    series.color,
+                    series.color.opacity(0.4).into(),
+                    default_style,
+                    series.y.clone(),
+                )
+            }))
+            .collect()
+    }
+
+    /// Returns the bottom and top of each area, the sum of the previous areas and of
+    /// the area itself in the stacked mode.
+    fn area_bounds(&self, areas: &[AreaStyle<T, Y>]) -> Vec<AreaBounds<T, Y>> {
+        let mut bottom: Option<Rc<dyn Fn(&T) -> Y>> = None;
+        areas
+            .iter()
+            .map(|(_, _, _, y_fn)| {
+                if !self.stacked {
+                    return (None, y_fn.clone());
+                }
+
+                let top: Rc<dyn Fn(&T) -> Y> = match bottom.clone() {
+                    Some(bottom) => {
+                        let y_fn = y_fn.clone();
+                        Rc::new(move |d| bottom(d) + y_fn(d))
+                    }
+                    None => y_fn.clone(),
+                };
+                (bottom.replace(top.clone()), top)
+            })
+            .collect()
+    }
+
+    /// Returns the min and max of the Y axis.
+    fn y_domain(&self, areas: &[AreaStyle<T, Y>]) -> (Y, Y) {
+        self.y_range.unwrap_or_else(|| {
+            let bounds = self.area_bounds(areas);
+            y_domain(
+                self.data
+                    .iter()
+                    .flat_map(|v| bounds.iter().map(|(_, top)| top(v))),
+            )
+        })
+    }
+
+    /// Returns the X and Y scales, and the height of the plot area.
+    fn scales(
+        &self,
+        bounds: &Bounds<Pixels>,
+        areas: &[AreaStyle<T, Y>],
+    ) -> Option<(ScalePoint<X>, ScaleLinear<Y>, f32)> {
+        let x_fn = self.x.as_ref()?;
+        if areas.is_empty() {
+            return None;
+        }
+
+        let width = bounds.size.width.as_f32();
+        // No axis and grid for compact size, e.g. sparkline.
+        let height = if self.size.is_compact() {
+            bounds.size.height.as_f32()
+        } else {
+            bounds.size.height.as_f32() - AXIS_GAP
+        };
+
+        // X scale
+        let x = ScalePoint::new(
+            self.data.iter().map(|v| x_fn(v)).collect(),
+            x_range(self.data.len(), self.x_window, width),
+        );
+
+        // Y scale over all the areas, ensure start from 0 unless the range is fixed.
+        let top = if self.has_legend() {
+            10. + LEGEND_HEIGHT
+        } else {
+            10.
+        };
+        let (min, max) = self.y_domain(areas);
+        let y = ScaleLinear::new(vec![min, max], vec![height, top]);
+
+        Some((x, y, height))
+    }
+
+    /// The legend is shown for the named series, except in compact size.
+    fn has_legend(&self) -> bool {
+        !self.series.is_empty() && !self.size.is_compact()
+    }
+}
+
+impl<T, X, Y> PlotSized for AreaChart<T, X, Y>
+where
+    X: Clone + PartialEq + Into<SharedString> + 'static,
+    Y: Clone + Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn plot_size_mut(&mut self) -> &mut PlotSize {
+        &mut self.size
+    }
 }
 
 impl<T, X, Y> Plot for AreaChart<T, X, Y>
@@ -95,34 +288,30 @@ where
     X: Clone + PartialEq + Into<SharedString> + 'static,
     Y: Clone + Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
+    fn plot_size(&self) -> PlotSize {
+        self.size
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         let Some(x_fn) = self.x.as_ref() else {
             return;
         };
 
-        if self.y.len() == 0 {
+        let areas = self.areas(cx);
+        let Some((x, y, height)) = self.scales(&bounds, &areas) else {
             return;
-        }
-
-        let width = bounds.size.width.as_f32();
-        let height = bounds.size.height.as_f32() - AXIS_GAP;
-
-        // X scale
-        let x = ScalePoint::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
-
-        // Y scale
-        let domain = self
-            .data
-            .iter()
-            .flat_map(|v| self.y.iter().map(|y_fn| y_fn(v)))
-            .chain(Some(Y::zero()))
-            .collect::<Vec<_>>();
-        let y = ScaleLinear::new(domain, vec![height, 10.]);
+        };
+        let compact = self.size.is_compact();
+        let value_ticks = if self.y_axis && !compact {
+            y_ticks(self.y_domain(&areas), &y)
+        } else {
+            vec![]
+        };
 
         // Draw X axis
         let data_len = self.data.len();
         let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
-            if (i + 1) % self.tick_margin == 0 {
+            if has_x_label(i, data_len, self.tick_margin, self.x_window.is_some()) {
                 x.tick(&x_fn(d)).map(|x_tick| {
                     let align = match i {
                         0 => {
@@ -142,47 +331,143 @@ where
             }
         });
 
-        PlotAxis::new()
-            .x(height)
-            .x_label(x_label)
-            .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
+        if !compact {
+            let y_label = value_ticks.iter().map(|(tick, label)| {
+                AxisText::new(label.clone(), tick - TEXT_HEIGHT, cx.theme().muted_foreground)
+            });
+
+            PlotAxis::new()
+                .x(height)
+                .x_label(x_label)
+                .y(px(0.))
+                .y_label(y_label)
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
 
-        // Draw grid
-        Grid::new()
-            .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
-            .stroke(cx.theme().border)
-            .dash_array(&[px(4.), px(2.)])
-            .paint(&bounds, window);
+            // Draw grid
+            let grid: Vec<f32> = if self.y_axis {
+                value_ticks.iter().map(|(tick, _)| *tick).collect()
+            } else {
+                (0..=3).map(|i| height * i as f32 / 4.0).collect()
+            };
+            Grid::new()
+                .y(grid)
+                .stroke(cx.theme().border)
+                .dash_array(&[px(4.), px(2.)])
+                .paint(&bounds, window);
+        }
+
+        if self.has_legend() {
+            Legend::new(
+                self.series
+                    .iter()
+                    .map(|series| (series.name.clone(), series.color)),
+            )
+            .text_color(cx.theme().muted_foreground)
+            .paint(&bounds, window, cx);
+        }
 
         // Draw area
-        for (i, y_fn) in self.y.iter().enumerate() {
+        let area_bounds = self.area_bounds(&areas);
+        for ((stroke, fill, stroke_style, _), (bottom, top)) in areas.into_iter().zip(area_bounds) {
             let x = x.clone();
-            let y = y.clone();
             let x_fn = x_fn.clone();
-            let y_fn = y_fn.clone();
 
-            let fill = *self
-                .fills
-                .get(i)
-                .unwrap_or(&cx.theme().chart_2.opacity(0.4).into());
-
-            let stroke = *self.strokes.get(i).unwrap_or(&cx.theme().chart_2);
-
-            let stroke_style = *self
-                .stroke_styles
-                .get(i)
-                .unwrap_or(self.stroke_styles.first().unwrap_or(&Default::default()));
-
-            Area::new()
+            let mut area = Area::new()
                 .data(&self.data)
                 .x(move |d| x.tick(&x_fn(d)))
-                .y0(height)
-                .y1(move |d| y.tick(&y_fn(d)))
+                .y1({
+                    let y = y.clone();
+                    move |d| y.tick(&top(d))
+                })
                 .stroke(stroke)
                 .stroke_style(stroke_style)
-                .fill(fill)
-                .paint(&bounds, window);
+                .fill(fill);
+            area = match bottom {
+                Some(bottom) => {
+                    let y = y.clone();
+                    area.y0_fn(move |d| y.tick(&bottom(d)))
+                }
+                None => area.y0(height),
+            };
+            area.paint(&bounds, window);
+        }
+    }
+
+    fn tooltip(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        position: Point<Pixels>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        if self.size.is_compact() || self.data.is_empty() {
+            return None;
         }
+
+        let x_fn = self.x.as_ref()?;
+        let areas = self.areas(cx);
+        let (x, y, height) = self.scales(&bounds, &areas)?;
+
+        let d = &self.data[x.least_index(position.x.as_f32())];
+        let x_value = x_fn(d);
+        let x_tick = x.tick(&x_value)?;
+        let values = areas
+            .iter()
+            .zip(self.area_bounds(&areas))
+            .map(|((stroke, _, _, y_fn), (_, top))| {
+                // The value of the area itself, at the top of the area in the stack.
+                let value = y_fn(d);
+                TooltipValue {
+                    color: *stroke,
+                    y: y.tick(&top(d)),
+                    value: value.to_f64().unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Some(point_tooltip(
+            x_value.into(),
+            x_tick,
+            bounds.size.width.as_f32(),
+            height,
+            values,
+            cx,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use gpui::{black, SharedString};
+
+    use super::{AreaChart, AreaStyle};
+    use crate::plot::StrokeStyle;
+
+    type Chart = AreaChart<(f64, f64), SharedString, f64>;
+
+    /// Returns the bottom and top of the two areas of the chart at `d`.
+    fn bounds_at(chart: &Chart, d: &(f64, f64)) -> Vec<(Option<f64>, f64)> {
+        let areas: Vec<AreaStyle<(f64, f64), f64>> = vec![
+            (black(), black().into(), StrokeStyle::Linear, Rc::new(|d: &(f64, f64)| d.0)),
+            (black(), black().into(), StrokeStyle::Linear, Rc::new(|d: &(f64, f64)| d.1)),
+        ];
+        chart
+            .area_bounds(&areas)
+            .iter()
+            .map(|(bottom, top)| (bottom.as_ref().map(|bottom| bottom(d)), top(d)))
+            .collect()
+    }
+
+    #[test]
+    fn test_area_bounds() {
+        let chart: Chart = AreaChart::new(vec![]).x(|_| SharedString::default());
+        assert_eq!(bounds_at(&chart, &(1., 2.)), vec![(None, 1.), (None, 2.)]);
+
+        // The top of the stack is the total.
+        let chart = chart.stacked(true);
+        assert_eq!(bounds_at(&chart, &(1., 2.)), vec![(None, 1.), (Some(1.), 3.)]);
     }
 }
@@ -7,7 +7,7 @@ use num_traits::{Num, ToPrimitive};
 use crate::{
     ActiveTheme, PixelsExt,
     plot::{
-        AXIS_GAP, AxisText, Grid, Plot, PlotAxis,
+        AXIS_GAP, AxisText, Grid, Plot, PlotAxis, PlotSize, PlotSized,
         label::Text,
         scale::{Scale, ScaleBand, ScaleLinear, Sealed},
         shape::Bar,
@@ -25,7 +25,9 @@ where
     x: Option<Rc<dyn Fn(&T) -> X>>,
     y: Option<Rc<dyn Fn(&T) -> Y>>,
     fill: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    spacing: f32,
     tick_margin: usize,
+    size: PlotSize,
     label: Option<Rc<dyn Fn(&T) -> SharedString>>,
 }
 
@@ -43,7 +45,9 @@ where
             x: None,
             y: None,
             fill: None,
+            spacing: 0.4,
             tick_margin: 1,
+            size: PlotSize::default(),
             label: None,
         }
     }
@@ -66,6 +70,12 @@ where
         self
     }
 
+    /// Set the space between the bars, as a ratio of the band width in `0..1`, default is 0.4.
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing.clamp(0., 1.);
+        self
+    }
+
     pub fn tick_margin(mut self, tick_margin: usize) -> Self {
         self.tick_margin = tick_margin;
         self
@@ -80,26 +90,46 @@ where
     }
 }
 
+impl<T, X, Y> PlotSized for BarChart<T, X, Y>
+where
+    X: PartialEq + Into<SharedString> + 'static,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn plot_size_mut(&mut self) -> &mut PlotSize {
+        &mut self.size
+    }
+}
+
 impl<T, X, Y> Plot for BarChart<T, X, Y>
 where
     X: PartialEq + Into<SharedString> + 'static,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
+    fn plot_size(&self) -> PlotSize {
+        self.size
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         let (Some(x_fn), Some(y_fn)) = (self.x.as_ref(), self.y.as_ref()) else {
             return;
         };
 
         let width = bounds.size.width.as_f32();
-        let height = bounds.size.height.as_f32() - AXIS_GAP;
+        // No axis and grid for compact size, e.g. sparkline.
+        let compact = self.size.is_compact();
+        let height = if compact {
+            bounds.size.height.as_f32()
+        } else {
+            bounds.size.height.as_f32() - AXIS_GAP
+        };
 
         // X scale
         let x = ScaleBand::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width])
-            .padding_inner(0.4)
+            .padding_inner(self.spacing)
             .padding_outer(0.2);
         let band_width = x.band_width();
 
-        // Y scale, ensure start from 0.
+        // Y scale, ensure start from 0, negative values go below the baseline.
         let y = ScaleLinear::new(
             self.data
                 .iter()
@@ -125,20 +155,23 @@ where
             }
         });
 
-        PlotAxis::new()
-            .x(height)
-            .x_label(x_label)
-            .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
-
-        // Draw grid
-        Grid::new()
-            .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
-            .stroke(cx.theme().border)
-            .dash_array(&[px(4.), px(2.)])
-            .paint(&bounds, window);
+        if !compact {
+            PlotAxis::new()
+                .x(height)
+                .x_label(x_label)
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            // Draw grid
+            Grid::new()
+                .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
+                .stroke(cx.theme().border)
+                .dash_array(&[px(4.), px(2.)])
+                .paint(&bounds, window);
+        }
 
-        // Draw bars
+        // Draw bars from the zero baseline
+        let baseline = y.tick(&Y::zero()).unwrap_or(height);
         let x_fn = x_fn.clone();
         let y_fn = y_fn.clone();
         let default_fill = cx.theme().chart_2;
@@ -148,7 +181,7 @@ where
             .data(&self.data)
             .band_width(band_width)
             .x(move |d| x.tick(&x_fn(d)))
-            .y0(move |_| height)
+            .y0(move |_| baseline)
             .y1(move |d| y.tick(&y_fn(d)))
             .fill(move |d| fill.as_ref().map(|f| f(d)).unwrap_or(default_fill));
 
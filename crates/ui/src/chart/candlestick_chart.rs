@@ -7,7 +7,7 @@ use num_traits::{Num, ToPrimitive};
 use crate::{
     ActiveTheme, PixelsExt,
     plot::{
-        AXIS_GAP, AxisText, Grid, Plot, PlotAxis, origin_point,
+        AXIS_GAP, AxisText, Grid, Plot, PlotAxis, PlotSize, PlotSized, origin_point,
         scale::{Scale, ScaleBand, ScaleLinear, Sealed},
     },
 };
@@ -26,6 +26,7 @@ where
     low: Option<Rc<dyn Fn(&T) -> Y>>,
     close: Option<Rc<dyn Fn(&T) -> Y>>,
     tick_margin: usize,
+    size: PlotSize,
     body_width_ratio: f32,
 }
 
@@ -46,6 +47,7 @@ where
             low: None,
             close: None,
             tick_margin: 1,
+            size: PlotSize::default(),
             body_width_ratio: 0.8,
         }
     }
@@ -86,11 +88,25 @@ where
     }
 }
 
+impl<T, X, Y> PlotSized for CandlestickChart<T, X, Y>
+where
+    X: PartialEq + Into<SharedString> + 'static,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn plot_size_mut(&mut self) -> &mut PlotSize {
+        &mut self.size
+    }
+}
+
 impl<T, X, Y> Plot for CandlestickChart<T, X, Y>
 where
     X: PartialEq + Into<SharedString> + 'static,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
+    fn plot_size(&self) -> PlotSize {
+        self.size
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         let (Some(x_fn), Some(open_fn), Some(high_fn), Some(low_fn), Some(close_fn)) = (
             self.x.as_ref(),
@@ -103,7 +119,13 @@ where
         };
 
         let width = bounds.size.width.as_f32();
-        let height = bounds.size.height.as_f32() - AXIS_GAP;
+        // No axis and grid for compact size, e.g. sparkline.
+        let compact = self.size.is_compact();
+        let height = if compact {
+            bounds.size.height.as_f32()
+        } else {
+            bounds.size.height.as_f32() - AXIS_GAP
+        };
 
         // X scale
         let x = ScaleBand::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width])
@@ -135,18 +157,20 @@ where
             }
         });
 
-        PlotAxis::new()
-            .x(height)
-            .x_label(x_label)
-            .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
-
-        // Draw grid
-        Grid::new()
-            .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
-            .stroke(cx.theme().border)
-            .dash_array(&[px(4.), px(2.)])
-            .paint(&bounds, window);
+        if !compact {
+            PlotAxis::new()
+                .x(height)
+                .x_label(x_label)
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            // Draw grid
+            Grid::new()
+                .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
+                .stroke(cx.theme().border)
+                .dash_array(&[px(4.), px(2.)])
+                .paint(&bounds, window);
+        }
 
         // Draw candlesticks
         let origin = bounds.origin;
@@ -1,6 +1,6 @@
 use std::rc::Rc;
 
-use gpui::{px, App, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui::{px, AnyElement, App, Bounds, Hsla, Pixels, Point, SharedString, TextAlign, Window};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
@@ -8,11 +8,16 @@ use crate::{
     plot::{
         scale::{Scale, ScaleLinear, ScalePoint, Sealed},
         shape::Line,
-        AxisText, Grid, Plot, PlotAxis, StrokeStyle, AXIS_GAP,
+        tooltip::{point_tooltip, TooltipValue},
+        label::TEXT_HEIGHT,
+        AxisText, Grid, Legend, Plot, PlotAxis, PlotSize, PlotSized, StrokeStyle, AXIS_GAP,
+        LEGEND_HEIGHT,
     },
     ActiveTheme, PixelsExt,
 };
 
+use super::{has_x_label, x_range, y_domain, y_ticks, Series};
+
 #[derive(IntoPlot)]
 pub struct LineChart<T, X, Y>
 where
@@ -24,9 +29,15 @@ where
     x: Option<Rc<dyn Fn(&T) -> X>>,
     y: Option<Rc<dyn Fn(&T) -> Y>>,
     stroke: Option<Hsla>,
+    series: Vec<Series<T, Y>>,
     stroke_style: StrokeStyle,
     dot: bool,
+    y_range: Option<(Y, Y)>,
+    y_axis: bool,
     tick_margin: usize,
+    /// The number of samples of the fixed X window, `None` to fit the data.
+    x_window: Option<usize>,
+    size: PlotSize,
 }
 
 impl<T, X, Y> LineChart<T, X, Y>
@@ -41,11 +52,16 @@ where
         Self {
             data: data.into_iter().collect(),
             stroke: None,
+            series: vec![],
             stroke_style: Default::default(),
             dot: false,
+            y_range: None,
+            y_axis: false,
             x: None,
             y: None,
             tick_margin: 1,
+            x_window: None,
+            size: PlotSize::default(),
         }
     }
 
@@ -64,6 +80,23 @@ where
         self
     }
 
+    /// Add a named series with its own color, listed in the legend.
+    ///
+    /// The series are drawn after the `y` line, if any, and share its Y scale.
+    pub fn series(
+        mut self,
+        name: impl Into<SharedString>,
+        color: impl Into<Hsla>,
+        y: impl Fn(&T) -> Y + 'static,
+    ) -> Self {
+        self.series.push(Series {
+            name: name.into(),
+            color: color.into(),
+            y: Rc::new(y),
+        });
+        self
+    }
+
     pub fn natural(mut self) -> Self {
         self.stroke_style = StrokeStyle::Natural;
         self
@@ -84,10 +117,106 @@ where
         self
     }
 
+    /// Fix the range of the Y axis, e.g. `0..100` for percentages, default is fit the data
+    /// and start from 0.
+    pub fn y_range(mut self, min: Y, max: Y) -> Self {
+        self.y_range = Some((min, max));
+        self
+    }
+
+    /// Show the value labels of the Y axis, with gridlines at the min, mid and max.
+    pub fn y_axis(mut self, y_axis: bool) -> Self {
+        self.y_axis = y_axis;
+        self
+    }
+
     pub fn tick_margin(mut self, tick_margin: usize) -> Self {
         self.tick_margin = tick_margin;
         self
     }
+
+    /// Show the last `samples` data points at a fixed spacing, the newest at the right edge.
+    ///
+    /// The line scrolls left as data arrives rather than stretching to fill the width,
+    /// and the X labels keep their positions. The older data points are dropped.
+    pub fn x_window(mut self, samples: usize) -> Self {
+        let excess = self.data.len().saturating_sub(samples);
+        self.data.drain(..excess);
+        self.x_window = Some(samples);
+        self
+    }
+
+    /// Returns the color and Y accessor of each line, the `y` line first.
+    fn lines(&self, cx: &App) -> Vec<(Hsla, Rc<dyn Fn(&T) -> Y>)> {
+        self.y
+            .iter()
+            .map(|y_fn| (self.stroke.unwrap_or(cx.theme().chart_2), y_fn.clone()))
+            .chain(self.series.iter().map(|series| (series.color, series.y.clone())))
+            .collect()
+    }
+
+    /// Returns the min and max of the Y axis.
+    fn y_domain(&self, lines: &[(Hsla, Rc<dyn Fn(&T) -> Y>)]) -> (Y, Y) {
+        self.y_range.unwrap_or_else(|| {
+            y_domain(
+                self.data
+                    .iter()
+                    .flat_map(|v| lines.iter().map(|(_, y_fn)| y_fn(v))),
+            )
+        })
+    }
+
+    /// Returns the X and Y scales, and the height of the plot area.
+    fn scales(
+        &self,
+        bounds: &Bounds<Pixels>,
+        lines: &[(Hsla, Rc<dyn Fn(&T) -> Y>)],
+    ) -> Option<(ScalePoint<X>, ScaleLinear<Y>, f32)> {
+        let x_fn = self.x.as_ref()?;
+        if lines.is_empty() {
+            return None;
+        }
+
+        let width = bounds.size.width.as_f32();
+        // No axis and grid for compact size, e.g. sparkline.
+        let height = if self.size.is_compact() {
+            bounds.size.height.as_f32()
+        } else {
+            bounds.size.height.as_f32() - AXIS_GAP
+        };
+
+        // X scale
+        let x = ScalePoint::new(
+            self.data.iter().map(|v| x_fn(v)).collect(),
+            x_range(self.data.len(), self.x_window, width),
+        );
+
+        // Y scale over all the lines, ensure start from 0 unless the range is fixed.
+        let top = if self.has_legend() {
+            10. + LEGEND_HEIGHT
+        } else {
+            10.
+        };
+        let (min, max) = self.y_domain(lines);
+        let y = ScaleLinear::new(vec![min, max], vec![height, top]);
+
+        Some((x, y, height))
+    }
+
+    /// The legend is shown for the named series, except in compact size.
+    fn has_legend(&self) -> bool {
+        !self.series.is_empty() && !self.size.is_compact()
+    }
+}
+
+impl<T, X, Y> PlotSized for LineChart<T, X, Y>
+where
+    X: PartialEq + Into<SharedString> + 'static,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn plot_size_mut(&mut self) -> &mut PlotSize {
+        &mut self.size
+    }
 }
 
 impl<T, X, Y> Plot for LineChart<T, X, Y>
@@ -95,31 +224,29 @@ where
     X: PartialEq + Into<SharedString> + 'static,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
+    fn plot_size(&self) -> PlotSize {
+        self.size
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
-        let (Some(x_fn), Some(y_fn)) = (self.x.as_ref(), self.y.as_ref()) else {
+        let Some(x_fn) = self.x.clone() else {
             return;
         };
-
-        let width = bounds.size.width.as_f32();
-        let height = bounds.size.height.as_f32() - AXIS_GAP;
-
-        // X scale
-        let x = ScalePoint::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
-
-        // Y scale, ensure start from 0.
-        let y = ScaleLinear::new(
-            self.data
-                .iter()
-                .map(|v| y_fn(v))
-                .chain(Some(Y::zero()))
-                .collect(),
-            vec![height, 10.],
-        );
+        let lines = self.lines(cx);
+        let Some((x, y, height)) = self.scales(&bounds, &lines) else {
+            return;
+        };
+        let compact = self.size.is_compact();
+        let value_ticks = if self.y_axis && !compact {
+            y_ticks(self.y_domain(&lines), &y)
+        } else {
+            vec![]
+        };
 
         // Draw X axis
         let data_len = self.data.len();
         let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
-            if (i + 1) % self.tick_margin == 0 {
+            if has_x_label(i, data_len, self.tick_margin, self.x_window.is_some()) {
                 x.tick(&x_fn(d)).map(|x_tick| {
                     let align = match i {
                         0 => {
@@ -139,35 +266,101 @@ where
             }
         });
 
-        PlotAxis::new()
-            .x(height)
-            .x_label(x_label)
-            .stroke(cx.theme().border)
+        if !compact {
+            let y_label = value_ticks.iter().map(|(tick, label)| {
+                AxisText::new(label.clone(), tick - TEXT_HEIGHT, cx.theme().muted_foreground)
+            });
+
+            PlotAxis::new()
+                .x(height)
+                .x_label(x_label)
+                .y(px(0.))
+                .y_label(y_label)
+                .stroke(cx.theme().border)
+                .paint(&bounds, window, cx);
+
+            // Draw grid
+            let grid: Vec<f32> = if self.y_axis {
+                value_ticks.iter().map(|(tick, _)| *tick).collect()
+            } else {
+                (0..=3).map(|i| height * i as f32 / 4.0).collect()
+            };
+            Grid::new()
+                .y(grid)
+                .stroke(cx.theme().border)
+                .dash_array(&[px(4.), px(2.)])
+                .paint(&bounds, window);
+        }
+
+        if self.has_legend() {
+            Legend::new(
+                self.series
+                    .iter()
+                    .map(|series| (series.name.clone(), series.color)),
+            )
+            .text_color(cx.theme().muted_foreground)
             .paint(&bounds, window, cx);
+        }
+
+        // Draw lines
+        let (x, y) = (Rc::new(x), Rc::new(y));
+        for (stroke, y_fn) in lines {
+            let x = x.clone();
+            let y = y.clone();
+            let x_fn = x_fn.clone();
+            let mut line = Line::new()
+                .data(&self.data)
+                .x(move |d| x.tick(&x_fn(d)))
+                .y(move |d| y.tick(&y_fn(d)))
+                .stroke(stroke)
+                .stroke_style(self.stroke_style)
+                .stroke_width(2.);
 
-        // Draw grid
-        Grid::new()
-            .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
-            .stroke(cx.theme().border)
-            .dash_array(&[px(4.), px(2.)])
-            .paint(&bounds, window);
-
-        // Draw line
-        let stroke = self.stroke.unwrap_or(cx.theme().chart_2);
-        let x_fn = x_fn.clone();
-        let y_fn = y_fn.clone();
-        let mut line = Line::new()
-            .data(&self.data)
-            .x(move |d| x.tick(&x_fn(d)))
-            .y(move |d| y.tick(&y_fn(d)))
-            .stroke(stroke)
-            .stroke_style(self.stroke_style)
-            .stroke_width(2.);
-
-        if self.dot {
-            line = line.dot().dot_size(8.).dot_fill_color(stroke);
+            if self.dot {
+                line = line.dot().dot_size(8.).dot_fill_color(stroke);
+            }
+
+            line.paint(&bounds, window);
         }
+    }
+
+    fn tooltip(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        position: Point<Pixels>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        if self.size.is_compact() || self.data.is_empty() {
+            return None;
+        }
+
+        let x_fn = self.x.as_ref()?;
+        let lines = self.lines(cx);
+        let (x, y, height) = self.scales(&bounds, &lines)?;
+
+        let d = &self.data[x.least_index(position.x.as_f32())];
+        let x_value = x_fn(d);
+        let x_tick = x.tick(&x_value)?;
+        let values = lines
+            .iter()
+            .map(|(color, y_fn)| {
+                let value = y_fn(d);
+                TooltipValue {
+                    color: *color,
+                    y: y.tick(&value),
+                    value: value.to_f64().unwrap_or_default(),
+                }
+            })
+            .collect();
 
-        line.paint(&bounds, window);
+        Some(point_tooltip(
+            x_value.into(),
+            x_tick,
+            bounds.size.width.as_f32(),
+            height,
+            values,
+            cx,
+        ))
     }
 }
@@ -3,9 +3,139 @@ mod bar_chart;
 mod candlestick_chart;
 mod line_chart;
 mod pie_chart;
+mod sparkline;
+
+use std::rc::Rc;
+
+use gpui::{Hsla, SharedString};
+use num_traits::{Num, ToPrimitive};
+
+use crate::plot::{
+    scale::{Scale, ScaleLinear, Sealed},
+    tooltip::format_value,
+};
 
 pub use area_chart::AreaChart;
 pub use bar_chart::BarChart;
 pub use candlestick_chart::CandlestickChart;
 pub use line_chart::LineChart;
 pub use pie_chart::PieChart;
+pub use sparkline::Sparkline;
+
+/// A named series of a chart, drawn with its own color and listed in the legend.
+struct Series<T, Y> {
+    name: SharedString,
+    color: Hsla,
+    y: Rc<dyn Fn(&T) -> Y>,
+}
+
+/// Returns the min and max of the values, including zero so the Y axis starts from 0.
+fn y_domain<Y>(values: impl IntoIterator<Item = Y>) -> (Y, Y)
+where
+    Y: Copy + PartialOrd + Num,
+{
+    values.into_iter().fold((Y::zero(), Y::zero()), |(min, max), v| {
+        (
+            if v < min { v } else { min },
+            if v > max { v } else { max },
+        )
+    })
+}
+
+/// Returns the X range of `len` points within the `width`.
+///
+/// The points fill the width, or with a fixed window of `window` samples the points are
+/// spaced by `width / (window - 1)` from the right edge, so the line scrolls left as the
+/// samples arrive instead of stretching.
+fn x_range(len: usize, window: Option<usize>, width: f32) -> Vec<f32> {
+    match window {
+        Some(window) if window > 1 && len < window => {
+            let step = width / (window - 1) as f32;
+            vec![width - len.saturating_sub(1) as f32 * step, width]
+        }
+        _ => vec![0., width],
+    }
+}
+
+/// Returns true if the point at `ix` of `len` points has an X label, every `tick_margin` points.
+///
+/// With a fixed window the labels are counted from the last point, so they keep their
+/// position while the line scrolls.
+fn has_x_label(ix: usize, len: usize, tick_margin: usize, fixed_window: bool) -> bool {
+    let tick_margin = tick_margin.max(1);
+    if fixed_window {
+        (len - 1 - ix) % tick_margin == 0
+    } else {
+        (ix + 1) % tick_margin == 0
+    }
+}
+
+/// Returns the ticks and labels of the min, mid and max of the Y domain, for the Y axis.
+fn y_ticks<Y>((min, max): (Y, Y), y: &ScaleLinear<Y>) -> Vec<(f32, SharedString)>
+where
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed,
+{
+    let mid = (min + max) / (Y::one() + Y::one());
+    [min, mid, max]
+        .into_iter()
+        .filter_map(|v| {
+            let tick = y.tick(&v)?;
+            Some((tick, format_value(v.to_f64()?)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{has_x_label, x_range, y_domain, y_ticks};
+    use crate::plot::scale::ScaleLinear;
+
+    #[test]
+    fn test_y_domain() {
+        assert_eq!(y_domain([3., 8., 5.]), (0., 8.));
+        assert_eq!(y_domain([-2., 4.]), (-2., 4.));
+        assert_eq!(y_domain(Vec::<f64>::new()), (0., 0.));
+    }
+
+    #[test]
+    fn test_x_range() {
+        assert_eq!(x_range(3, None, 100.), vec![0., 100.]);
+        // A partial window starts from the right edge.
+        assert_eq!(x_range(3, Some(5), 100.), vec![50., 100.]);
+        assert_eq!(x_range(1, Some(5), 100.), vec![100., 100.]);
+        assert_eq!(x_range(5, Some(5), 100.), vec![0., 100.]);
+    }
+
+    #[test]
+    fn test_has_x_label() {
+        let labels = |len, fixed_window| {
+            (0..len)
+                .filter(|&ix| has_x_label(ix, len, 2, fixed_window))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(5, false), vec![1, 3]);
+        assert_eq!(labels(5, true), vec![0, 2, 4]);
+        assert_eq!(labels(4, true), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_y_ticks() {
+        let y = ScaleLinear::new(vec![0., 100.], vec![100., 0.]);
+        let ticks = y_ticks((0., 100.), &y);
+        assert_eq!(
+            ticks
+                .iter()
+                .map(|(tick, label)| (*tick, label.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                (100., "0".to_string()),
+                (50., "50".to_string()),
+                (0., "100".to_string()),
+            ]
+        );
+
+        // No ticks for an empty domain.
+        let y = ScaleLinear::new(vec![0., 0.], vec![100., 0.]);
+        assert!(y_ticks((0., 0.), &y).is_empty());
+    }
+}
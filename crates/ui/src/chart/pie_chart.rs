@@ -1,13 +1,14 @@
-use std::rc::Rc;
+use std::{f32::consts::TAU, rc::Rc};
 
-use gpui::{App, Bounds, Hsla, Pixels, Window};
+use gpui::{point, px, App, Bounds, FontWeight, Hsla, Pixels, SharedString, TextAlign, Window};
 use gpui_component_macros::IntoPlot;
 use num_traits::Zero;
 
 use crate::{
     plot::{
+        label::{PlotLabel, Text},
         shape::{Arc, ArcData, Pie},
-        Plot,
+        Plot, PlotSize, PlotSized,
     },
     ActiveTheme, PixelsExt,
 };
@@ -22,6 +23,8 @@ pub struct PieChart<T: 'static> {
     pad_angle: f32,
     value: Option<Rc<dyn Fn(&T) -> f32>>,
     color: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    center_label: Option<SharedString>,
+    size: PlotSize,
 }
 
 impl<T> PieChart<T> {
@@ -38,6 +41,8 @@ impl<T> PieChart<T> {
             pad_angle: 0.,
             value: None,
             color: None,
+            center_label: None,
+            size: PlotSize::default(),
         }
     }
 
@@ -106,9 +111,25 @@ impl<T> PieChart<T> {
         self.color = Some(Rc::new(move |t| color(t).into()));
         self
     }
+
+    /// Set the text drawn in the center, e.g. the percentage of a donut chart.
+    pub fn center_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.center_label = Some(label.into());
+        self
+    }
+}
+
+impl<T> PlotSized for PieChart<T> {
+    fn plot_size_mut(&mut self) -> &mut PlotSize {
+        &mut self.size
+    }
 }
 
 impl<T> Plot for PieChart<T> {
+    fn plot_size(&self) -> PlotSize {
+        self.size
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         let Some(value_fn) = self.value.as_ref() else {
             return;
@@ -128,6 +149,19 @@ impl<T> Plot for PieChart<T> {
         pie = pie.pad_angle(self.pad_angle);
         let arcs = pie.arcs(&self.data);
 
+        // Draw an empty ring if there is no positive value, so the chart keeps its shape.
+        if arcs.is_empty() {
+            let empty = ArcData {
+                data: &(),
+                index: 0,
+                value: 0.,
+                start_angle: 0.,
+                end_angle: TAU,
+                pad_angle: 0.,
+            };
+            arc.paint(&empty, cx.theme().muted, None, None, &bounds, window);
+        }
+
         for a in &arcs {
             let inner_radius = self.get_inner_radius(a);
             let outer_radius = self.get_outer_radius(a);
@@ -144,5 +178,18 @@ impl<T> Plot for PieChart<T> {
                 window,
             );
         }
+
+        if let Some(label) = self.center_label.clone() {
+            let font_size = px(14.);
+            let origin = point(
+                bounds.size.width / 2.,
+                (bounds.size.height - font_size) / 2.,
+            );
+            PlotLabel::new(vec![Text::new(label, origin, cx.theme().foreground)
+                .font_size(font_size)
+                .font_weight(FontWeight::SEMIBOLD)
+                .align(TextAlign::Center)])
+            .paint(&bounds, window, cx);
+        }
     }
 }
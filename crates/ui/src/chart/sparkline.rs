@@ -0,0 +1,124 @@
+use gpui::{px, App, Bounds, Hsla, Pixels, Window};
+use gpui_component_macros::IntoPlot;
+
+use crate::{
+    plot::{shape::Line, Plot, PlotSize, PlotSized, StrokeStyle},
+    PixelsExt,
+};
+
+use super::y_domain;
+
+/// A minimal line chart without axes, grid or tooltip, e.g. an inline trend in a table cell.
+///
+/// The values are evenly spaced across the width, and the Y axis is scaled from 0 to
+/// the max value unless a [`Sparkline::y_range`] is set.
+///
+/// ```ignore
+/// Sparkline::new(&cpu_history, cx.theme().chart_2)
+///     .y_range(0., 100.)
+///     .width(px(100.))
+///     .xsmall()
+/// ```
+#[derive(IntoPlot)]
+pub struct Sparkline {
+    values: Vec<f64>,
+    color: Hsla,
+    y_range: Option<(f64, f64)>,
+    size: PlotSize,
+}
+
+impl Sparkline {
+    pub fn new(values: &[f64], color: impl Into<Hsla>) -> Self {
+        Self {
+            values: values.to_vec(),
+            color: color.into(),
+            y_range: None,
+            size: PlotSize::default(),
+        }
+    }
+
+    /// Set a fixed min and max of the Y axis, e.g. `0..100` for percentages.
+    pub fn y_range(mut self, min: f64, max: f64) -> Self {
+        self.y_range = Some((min, max));
+        self
+    }
+}
+
+impl PlotSized for Sparkline {
+    fn plot_size_mut(&mut self) -> &mut PlotSize {
+        &mut self.size
+    }
+}
+
+/// Returns the points of the values within the `width` and `height`, inset by half the
+/// stroke so the line is not clipped at the top and bottom.
+fn points(values: &[f64], (min, max): (f64, f64), width: f32, height: f32) -> Vec<(f32, f32)> {
+    const INSET: f32 = 1.;
+
+    let step = width / (values.len().saturating_sub(1).max(1)) as f32;
+    let span = max - min;
+    values
+        .iter()
+        .enumerate()
+        .map(|(ix, value)| {
+            let ratio = if span > 0. {
+                ((value - min) / span).clamp(0., 1.) as f32
+            } else {
+                0.
+            };
+            (ix as f32 * step, height - INSET - ratio * (height - INSET * 2.))
+        })
+        .collect()
+}
+
+impl Plot for Sparkline {
+    fn plot_size(&self) -> PlotSize {
+        self.size
+    }
+
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, _: &mut App) {
+        if self.values.len() < 2 {
+            return;
+        }
+
+        let domain = self
+            .y_range
+            .unwrap_or_else(|| y_domain(self.values.iter().copied()));
+        let points = points(
+            &self.values,
+            domain,
+            bounds.size.width.as_f32(),
+            bounds.size.height.as_f32(),
+        );
+
+        Line::new()
+            .data(points)
+            .x(|(x, _)| Some(*x))
+            .y(|(_, y)| Some(*y))
+            .stroke(self.color)
+            .stroke_width(px(1.))
+            .stroke_style(StrokeStyle::Linear)
+            .paint(&bounds, window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::points;
+
+    #[test]
+    fn test_points() {
+        assert_eq!(
+            points(&[0., 50., 100.], (0., 100.), 100., 12.),
+            vec![(0., 11.), (50., 6.), (100., 1.)]
+        );
+
+        // Out of range values are clamped, a flat domain is drawn at the bottom.
+        assert_eq!(
+            points(&[-10., 200.], (0., 100.), 10., 12.),
+            vec![(0., 11.), (10., 1.)]
+        );
+        assert_eq!(points(&[0., 0.], (0., 0.), 10., 12.), vec![(0., 11.), (10., 11.)]);
+        assert!(points(&[], (0., 1.), 10., 12.).is_empty());
+    }
+}
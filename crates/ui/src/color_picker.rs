@@ -1,18 +1,20 @@
 use gpui::{
     App, AppContext, Context, Corner, Div, ElementId, Entity, EventEmitter, FocusHandle, Focusable,
     Hsla, InteractiveElement as _, IntoElement, KeyBinding, ParentElement, Render, RenderOnce,
-    SharedString, Stateful, StatefulInteractiveElement as _, StyleRefinement, Styled, Subscription,
-    Window, div, prelude::FluentBuilder as _,
+    Rgba, SharedString, Stateful, StatefulInteractiveElement as _, StyleRefinement, Styled,
+    Subscription, Task, Window, div, hsla, prelude::FluentBuilder as _, px, radians,
 };
+use std::{f32::consts::FRAC_PI_4, rc::Rc};
 
 use crate::{
-    ActiveTheme as _, Colorize as _, Icon, Sizable, Size, StyleSized,
+    ActiveTheme as _, Colorize as _, Icon, IconName, Sizable, Size, StyleSized, StyledExt as _,
     actions::Confirm,
-    button::{Button, ButtonVariants},
+    button::{Button, ButtonGroup, ButtonVariants},
     divider::Divider,
     h_flex,
     input::{Input, InputEvent, InputState},
     popover::Popover,
+    slider::{Slider, SliderEvent, SliderState},
     tooltip::Tooltip,
     v_flex,
 };
@@ -60,12 +62,158 @@ fn color_palettes() -> Vec<Vec<Hsla>> {
     ]
 }
 
+/// The input format of the [`ColorPicker`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFormat {
+    /// A hex input, e.g. `#FF0000` or `#FF000080`.
+    #[default]
+    Hex,
+    /// Red, green and blue inputs in `0..=255`.
+    Rgb,
+    /// Hue in `0..=360`, saturation and lightness in `0..=100`.
+    Hsl,
+}
+
+impl ColorFormat {
+    fn all() -> [Self; 3] {
+        [Self::Hex, Self::Rgb, Self::Hsl]
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Hex => "HEX",
+            Self::Rgb => "RGB",
+            Self::Hsl => "HSL",
+        }
+    }
+
+    /// The label and max value of each channel, empty for [`ColorFormat::Hex`].
+    fn channels(&self) -> &'static [(&'static str, f32)] {
+        match self {
+            Self::Hex => &[],
+            Self::Rgb => &[("R", 255.), ("G", 255.), ("B", 255.)],
+            Self::Hsl => &[("H", 360.), ("S", 100.), ("L", 100.)],
+        }
+    }
+
+    /// Parse the channel values to a color with the alpha,
+    /// returns `None` if any value is invalid or out of range.
+    fn parse(&self, values: [&str; 3], alpha: f32) -> Option<Hsla> {
+        let channels = self.channels();
+        if channels.is_empty() {
+            return None;
+        }
+
+        let mut parsed = [0.; 3];
+        for ((value, (_, max)), parsed) in values.iter().zip(channels).zip(parsed.iter_mut()) {
+            let value = value.trim().parse::<f32>().ok()?;
+            if !(0. ..=*max).contains(&value) {
+                return None;
+            }
+            *parsed = value;
+        }
+
+        let [x, y, z] = parsed;
+        match self {
+            Self::Hex => None,
+            Self::Rgb => Some(
+                Rgba {
+                    r: x / 255.,
+                    g: y / 255.,
+                    b: z / 255.,
+                    a: alpha,
+                }
+                .into(),
+            ),
+            Self::Hsl => Some(hsla(x / 360., y / 100., z / 100., alpha)),
+        }
+    }
+
+    /// Format the color to the channel values.
+    fn format(&self, color: Hsla) -> [String; 3] {
+        let values = match self {
+            Self::Hex => return Default::default(),
+            Self::Rgb => {
+                let rgb = color.to_rgb();
+                [rgb.r * 255., rgb.g * 255., rgb.b * 255.]
+            }
+            Self::Hsl => [color.h * 360., color.s * 100., color.l * 100.],
+        };
+        values.map(|value| (value.round() as u32).to_string())
+    }
+}
+
+/// Move the color to the front of the recent colors, and keep at most `max` colors.
+fn push_recent_color(colors: &mut Vec<Hsla>, color: Hsla, max: usize) {
+    let hex = color.to_hex();
+    colors.retain(|c| c.to_hex() != hex);
+    colors.insert(0, color);
+    colors.truncate(max);
+}
+
+/// The cell size of the checkerboard behind the semi-transparent colors.
+const CHECKERBOARD_CELL_SIZE: f32 = 4.;
+
+/// Render a checkerboard to fill the parent, to show the transparency of the color above it.
+///
+/// The parent must be `relative` and `overflow_hidden`.
+fn checkerboard() -> Div {
+    let light = hsla(0., 0., 1., 1.);
+    let dark = hsla(0., 0., 0.8, 1.);
+
+    v_flex()
+        .absolute()
+        .top_0()
+        .left_0()
+        .size_full()
+        .children((0..8).map(|row| {
+            h_flex().flex_shrink_0().children((0..8).map(move |col| {
+                div()
+                    .flex_shrink_0()
+                    .size(px(CHECKERBOARD_CELL_SIZE))
+                    .bg(if (row + col) % 2 == 0 { light } else { dark })
+            }))
+        }))
+}
+
+/// Render the color over a checkerboard if it is semi-transparent.
+fn color_swatch(color: Hsla) -> Div {
+    div()
+        .relative()
+        .overflow_hidden()
+        .when(color.a < 1., |this| {
+            this.child(checkerboard()).child(
+                div()
+                    .absolute()
+                    .top_0()
+                    .left_0()
+                    .size_full()
+                    .bg(color),
+            )
+        })
+        .when(color.a >= 1., |this| this.bg(color))
+}
+
+/// A function to sample a color from the screen, resolves to `None` if cancelled.
+///
+/// See [`ColorPickerState::eyedropper`].
+pub type ColorSampler = Rc<dyn Fn(&mut Window, &mut App) -> Task<Option<Hsla>>>;
+
 /// State of the [`ColorPicker`].
 pub struct ColorPickerState {
     focus_handle: FocusHandle,
     value: Option<Hsla>,
     hovered_color: Option<Hsla>,
     state: Entity<InputState>,
+    /// The alpha of the color in percent.
+    alpha: Entity<SliderState>,
+    format: ColorFormat,
+    /// The inputs of each channel for the [`ColorFormat::Rgb`] and [`ColorFormat::Hsl`].
+    channel_inputs: Vec<Entity<InputState>>,
+    /// The max count of recent colors, `None` to disable tracking.
+    max_recent: Option<usize>,
+    recent_colors: Vec<Hsla>,
+    eyedropper: Option<ColorSampler>,
     open: bool,
     _subscriptions: Vec<Subscription>,
 }
@@ -77,32 +225,81 @@ impl ColorPickerState {
             InputState::new(window, cx).pattern(regex::Regex::new(r"^#[0-9a-fA-F]{0,8}$").unwrap())
         });
 
-        let _subscriptions = vec![cx.subscribe_in(
-            &state,
-            window,
-            |this, state, ev: &InputEvent, window, cx| match ev {
+        let alpha = cx.new(|_| {
+            SliderState::new()
+                .min(0.)
+                .max(100.)
+                .step(1.)
+                .default_value(100.)
+        });
+
+        let channel_inputs = (0..3)
+            .map(|_| {
+                cx.new(|cx| {
+                    InputState::new(window, cx)
+                        .pattern(regex::Regex::new(r"^[0-9]{0,3}$").unwrap())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut _subscriptions = vec![
+            cx.subscribe_in(
+                &alpha,
+                window,
+                |this, _, ev: &SliderEvent, window, cx| match ev {
+                    SliderEvent::Change(value) => this.update_alpha(value.end() / 100., window, cx),
+                },
+            ),
+            cx.subscribe_in(
+                &state,
+                window,
+                |this, state, ev: &InputEvent, window, cx| match ev {
+                    InputEvent::Change => {
+                        let value = state.read(cx).value();
+                        if let Ok(color) = Hsla::parse_hex(value.as_str()) {
+                            this.hovered_color = Some(color);
+                        }
+                    }
+                    InputEvent::PressEnter { .. } => {
+                        let val = this.state.read(cx).value();
+                        if let Ok(color) = Hsla::parse_hex(&val) {
+                            this.open = false;
+                            this.update_value(Some(color), true, window, cx);
+                        }
+                    }
+                    _ => {}
+                },
+            ),
+        ];
+        _subscriptions.extend(channel_inputs.iter().map(|input| {
+            cx.subscribe_in(input, window, |this, _, ev: &InputEvent, window, cx| match ev {
                 InputEvent::Change => {
-                    let value = state.read(cx).value();
-                    if let Ok(color) = Hsla::parse_hex(value.as_str()) {
+                    if let Some(color) = this.parse_channels(cx) {
                         this.hovered_color = Some(color);
+                        cx.notify();
                     }
                 }
                 InputEvent::PressEnter { .. } => {
-                    let val = this.state.read(cx).value();
-                    if let Ok(color) = Hsla::parse_hex(&val) {
+                    if let Some(color) = this.parse_channels(cx) {
                         this.open = false;
                         this.update_value(Some(color), true, window, cx);
                     }
                 }
                 _ => {}
-            },
-        )];
+            })
+        }));
 
         Self {
             focus_handle: cx.focus_handle(),
             value: None,
             hovered_color: None,
             state,
+            alpha,
+            format: ColorFormat::default(),
+            channel_inputs,
+            max_recent: None,
+            recent_colors: vec![],
+            eyedropper: None,
             open: false,
             _subscriptions,
         }
@@ -114,6 +311,52 @@ impl ColorPickerState {
         self
     }
 
+    /// Track the recently confirmed colors, and show them as the featured colors.
+    ///
+    /// Keep at most `max` colors, the latest first.
+    /// The [`ColorPicker::featured_colors`] still take precedence if set.
+    pub fn track_recent(mut self, max: usize) -> Self {
+        self.max_recent = Some(max);
+        self.recent_colors.truncate(max);
+        self
+    }
+
+    /// Enable the eyedropper button to pick a color from the screen.
+    ///
+    /// GPUI has no API to read the screen pixels, so the host app provides the sampler,
+    /// e.g. by a platform screen capture crate. The button is hidden if not set,
+    /// so only set it on the platforms the sampler supports.
+    ///
+    /// The sampled color is previewed like hovering a palette color,
+    /// and confirmed by pressing `enter`.
+    pub fn eyedropper(
+        mut self,
+        sampler: impl Fn(&mut Window, &mut App) -> Task<Option<Hsla>> + 'static,
+    ) -> Self {
+        self.eyedropper = Some(Rc::new(sampler));
+        self
+    }
+
+    /// Get the recently confirmed colors, the latest first.
+    ///
+    /// The host app can persist them, and restore with [`ColorPickerState::set_recent_colors`].
+    pub fn recent_colors(&self) -> &[Hsla] {
+        &self.recent_colors
+    }
+
+    /// Set the recent colors, e.g. restored from the app settings.
+    pub fn set_recent_colors(&mut self, colors: Vec<Hsla>, cx: &mut Context<Self>) {
+        self.recent_colors.clear();
+        for color in colors.into_iter().rev() {
+            push_recent_color(
+                &mut self.recent_colors,
+                color,
+                self.max_recent.unwrap_or(usize::MAX),
+            );
+        }
+        cx.notify();
+    }
+
     /// Set current color value.
     pub fn set_value(
         &mut self,
@@ -129,11 +372,100 @@ impl ColorPickerState {
         self.value
     }
 
-    fn on_confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
-        self.open = !self.open;
+    /// Set the input format, default is [`ColorFormat::Hex`].
+    pub fn set_format(&mut self, format: ColorFormat, window: &mut Window, cx: &mut Context<Self>) {
+        self.format = format;
+        self.sync_channels(window, cx);
+        cx.notify();
+    }
+
+    /// Get the input format.
+    pub fn format(&self) -> ColorFormat {
+        self.format
+    }
+
+    /// Parse the channel inputs to a color, keeping the alpha of the current value.
+    fn parse_channels(&self, cx: &App) -> Option<Hsla> {
+        let values = self
+            .channel_inputs
+            .iter()
+            .map(|input| input.read(cx).value())
+            .collect::<Vec<_>>();
+        let alpha = self.value.map_or(1., |value| value.a);
+
+        self.format.parse(
+            [values[0].as_ref(), values[1].as_ref(), values[2].as_ref()],
+            alpha,
+        )
+    }
+
+    fn sync_channels(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(color) = self.value.or(self.hovered_color) else {
+            return;
+        };
+
+        let values = self.format.format(color);
+        for (input, value) in self.channel_inputs.iter().zip(values) {
+            input.update(cx, |input, cx| input.set_value(value, window, cx));
+        }
+    }
+
+    /// Preview the color without changing the value, e.g. on hover.
+    fn preview_color(&mut self, color: Hsla, window: &mut Window, cx: &mut Context<Self>) {
+        self.hovered_color = Some(color);
+        self.state.update(cx, |input, cx| {
+            input.set_value(color.to_hex(), window, cx);
+        });
+        cx.notify();
+    }
+
+    fn pick_from_screen(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(sampler) = self.eyedropper.clone() else {
+            return;
+        };
+
+        let task = sampler(window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            if let Some(color) = task.await {
+                _ = this.update_in(cx, |this, window, cx| {
+                    this.preview_color(color, window, cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    fn on_confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_open(!self.open, window, cx);
+    }
+
+    fn set_open(&mut self, open: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.open = open;
+        if open {
+            // The value may be set by `default_value` without syncing the slider.
+            self.sync_alpha(window, cx);
+        }
         cx.notify();
     }
 
+    /// Update the alpha of the current color.
+    fn update_alpha(&mut self, alpha: f32, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(color) = self.value.or(self.hovered_color) else {
+            return;
+        };
+
+        self.update_value(Some(Hsla { a: alpha, ..color }), true, window, cx);
+    }
+
+    fn sync_alpha(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let alpha = self.value.map_or(100., |value| (value.a * 100.).round());
+        self.alpha.update(cx, |slider, cx| {
+            if slider.value().end() != alpha {
+                slider.set_value(alpha, window, cx);
+            }
+        });
+    }
+
     fn update_value(
         &mut self,
         value: Option<Hsla>,
@@ -150,7 +482,12 @@ impl ColorPickerState {
                 view.set_value("", window, cx);
             }
         });
+        self.sync_alpha(window, cx);
+        self.sync_channels(window, cx);
         if emit {
+            if let (Some(value), Some(max)) = (value, self.max_recent) {
+                push_recent_color(&mut self.recent_colors, value, max);
+            }
             cx.emit(ColorPickerEvent::Change(value));
         }
         cx.notify();
@@ -241,11 +578,10 @@ impl ColorPicker {
         _: &mut App,
     ) -> Stateful<Div> {
         let state = self.state.clone();
-        div()
+        color_swatch(color)
             .id(SharedString::from(format!("color-{}", color.to_hex())))
             .h_5()
             .w_5()
-            .bg(color)
             .border_1()
             .border_color(color.darken(0.1))
             .when(clickable, |this| {
@@ -256,11 +592,7 @@ impl ColorPicker {
                 })
                 .active(|this| this.border_color(color.darken(0.5)).bg(color.darken(0.2)))
                 .on_mouse_move(window.listener_for(&state, move |state, _, window, cx| {
-                    state.hovered_color = Some(color);
-                    state.state.update(cx, |input, cx| {
-                        input.set_value(color.to_hex(), window, cx);
-                    });
-                    cx.notify();
+                    state.preview_color(color, window, cx);
                 }))
                 .on_click(window.listener_for(
                     &state,
@@ -273,8 +605,62 @@ impl ColorPicker {
             })
     }
 
+    /// Render the "None" item to clear the color.
+    fn render_none_item(&self, window: &mut Window, cx: &mut App) -> Stateful<Div> {
+        let border = cx.theme().border;
+        let hover_border = cx.theme().muted_foreground;
+
+        div()
+            .id("color-none")
+            .h_5()
+            .w_5()
+            .flex_shrink_0()
+            .overflow_hidden()
+            .bg(cx.theme().background)
+            .border_1()
+            .border_color(border)
+            .hover(|this| this.border_color(hover_border))
+            .child(
+                Icon::new(IconName::Minus)
+                    .size_full()
+                    .text_color(cx.theme().danger)
+                    .rotate(radians(-FRAC_PI_4)),
+            )
+            .tooltip(|window, cx| Tooltip::new("No color").build(window, cx))
+            .on_click(window.listener_for(&self.state, |state, _, window, cx| {
+                state.open = false;
+                state.update_value(None, true, window, cx);
+            }))
+    }
+
+    fn render_format_toggle(&self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let format = self.state.read(cx).format;
+
+        ButtonGroup::new("format")
+            .xsmall()
+            .outline()
+            .children(ColorFormat::all().into_iter().map(|item| {
+                Button::new(item.label())
+                    .label(item.label())
+                    .selected(item == format)
+            }))
+            .on_click(window.listener_for(
+                &self.state,
+                |state, clicks: &Vec<usize>, window, cx| {
+                    let format = clicks
+                        .first()
+                        .and_then(|ix| ColorFormat::all().get(*ix).copied());
+                    if let Some(format) = format {
+                        state.set_format(format, window, cx);
+                    }
+                },
+            ))
+    }
+
     fn render_colors(&self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let featured_colors = self.featured_colors.clone().unwrap_or(vec![
+        let recent_colors = &self.state.read(cx).recent_colors;
+        let recent_colors = (!recent_colors.is_empty()).then(|| recent_colors.clone());
+        let featured_colors = self.featured_colors.clone().or(recent_colors).unwrap_or(vec![
             cx.theme().red,
             cx.theme().red_light,
             cx.theme().blue,
@@ -293,11 +679,28 @@ impl ColorPicker {
             .p_0p5()
             .gap_3()
             .child(
-                h_flex().gap_1().children(
-                    featured_colors
-                        .iter()
-                        .map(|color| self.render_item(*color, true, window, cx)),
-                ),
+                h_flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .child(self.render_none_item(window, cx))
+                    .when(self.state.read(cx).eyedropper.is_some(), |this| {
+                        this.child(
+                            Button::new("eyedropper")
+                                .xsmall()
+                                .ghost()
+                                .icon(IconName::Pipette)
+                                .tooltip("Pick from screen")
+                                .on_click(window.listener_for(
+                                    &self.state,
+                                    |state, _, window, cx| state.pick_from_screen(window, cx),
+                                )),
+                        )
+                    })
+                    .children(
+                        featured_colors
+                            .iter()
+                            .map(|color| self.render_item(*color, true, window, cx)),
+                    ),
             )
             .child(Divider::horizontal())
             .child(
@@ -312,22 +715,52 @@ impl ColorPicker {
                         )
                     })),
             )
+            .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child("Alpha"),
+                    )
+                    .child(div().flex_1().child(Slider::new(&self.state.read(cx).alpha))),
+            )
             .when_some(self.state.read(cx).hovered_color, |this, hovered_color| {
                 this.child(Divider::horizontal()).child(
                     h_flex()
                         .gap_2()
                         .items_center()
                         .child(
-                            div()
-                                .bg(hovered_color)
+                            color_swatch(hovered_color)
                                 .flex_shrink_0()
                                 .border_1()
                                 .border_color(hovered_color.darken(0.2))
                                 .size_5()
                                 .rounded(cx.theme().radius),
                         )
-                        .child(Input::new(&self.state.read(cx).state).small()),
+                        .map(|this| {
+                            let state = self.state.read(cx);
+                            if state.format == ColorFormat::Hex {
+                                return this.child(Input::new(&state.state).small());
+                            }
+
+                            let channels = state.format.channels().iter();
+                            this.children(channels.zip(&state.channel_inputs).map(
+                                |((label, _), input)| {
+                                    Input::new(input).small().prefix(
+                                        div()
+                                            .text_xs()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(*label),
+                                    )
+                                },
+                            ))
+                        }),
                 )
+                .child(self.render_format_toggle(window, cx))
             })
     }
 }
@@ -373,9 +806,8 @@ impl RenderOnce for ColorPicker {
                     .open(state.open)
                     .w_72()
                     .on_open_change(
-                        window.listener_for(&self.state, |this, open: &bool, _, cx| {
-                            this.open = *open;
-                            cx.notify();
+                        window.listener_for(&self.state, |this, open: &bool, window, cx| {
+                            this.set_open(*open, window, cx);
                         }),
                     )
                     .trigger(
@@ -397,10 +829,22 @@ impl RenderOnce for ColorPicker {
                                         .overflow_hidden()
                                         .size_with(self.size)
                                         .when_some(state.value, |this, value| {
-                                            this.bg(value)
+                                            this.relative()
+                                                .when(value.a < 1., |this| {
+                                                    this.child(checkerboard()).child(
+                                                        div()
+                                                            .absolute()
+                                                            .top_0()
+                                                            .left_0()
+                                                            .size_full()
+                                                            .bg(value),
+                                                    )
+                                                })
+                                                .when(value.a >= 1., |this| this.bg(value))
                                                 .border_color(value.darken(0.3))
                                                 .when(state.open, |this| this.border_2())
                                         })
+                                        .focused_border_for(&focus_handle, window, cx)
                                         .when(!display_title.is_empty(), |this| {
                                             this.tooltip(move |_, cx| {
                                                 cx.new(|_| Tooltip::new(display_title.clone()))
@@ -415,3 +859,45 @@ impl RenderOnce for ColorPicker {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorFormat, push_recent_color};
+    use gpui::hsla;
+
+    #[test]
+    fn test_push_recent_color() {
+        let red = hsla(0., 1., 0.5, 1.);
+        let green = hsla(120. / 360., 1., 0.5, 1.);
+        let blue = hsla(240. / 360., 1., 0.5, 1.);
+
+        let mut colors = vec![];
+        push_recent_color(&mut colors, red, 2);
+        push_recent_color(&mut colors, green, 2);
+        assert_eq!(colors, vec![green, red]);
+
+        // Re-selected color moves to the front.
+        push_recent_color(&mut colors, red, 2);
+        assert_eq!(colors, vec![red, green]);
+
+        push_recent_color(&mut colors, blue, 2);
+        assert_eq!(colors, vec![blue, red]);
+    }
+
+    #[test]
+    fn test_color_format_parse() {
+        let color = ColorFormat::Rgb.parse(["255", "0", "0"], 1.).unwrap();
+        assert_eq!(color, hsla(0., 1., 0.5, 1.));
+        assert_eq!(ColorFormat::Rgb.format(color), ["255", "0", "0"]);
+
+        let color = ColorFormat::Hsl.parse(["120", "100", "50"], 0.5).unwrap();
+        assert_eq!(color, hsla(120. / 360., 1., 0.5, 0.5));
+        assert_eq!(ColorFormat::Hsl.format(color), ["120", "100", "50"]);
+
+        assert_eq!(ColorFormat::Rgb.parse(["256", "0", "0"], 1.), None);
+        assert_eq!(ColorFormat::Hsl.parse(["361", "0", "0"], 1.), None);
+        assert_eq!(ColorFormat::Hsl.parse(["0", "101", "0"], 1.), None);
+        assert_eq!(ColorFormat::Rgb.parse(["", "0", "0"], 1.), None);
+        assert_eq!(ColorFormat::Hex.parse(["0", "0", "0"], 1.), None);
+    }
+}
@@ -219,6 +219,27 @@ impl Dialog {
         self
     }
 
+    /// Sets one callback for the result of a [`Self::confirm`] dialog, instead of
+    /// [`Self::on_ok`] and [`Self::on_cancel`].
+    ///
+    /// The callback is called with `true` if confirmed, `false` if canceled (including by escape),
+    /// and the dialog is closed either way.
+    ///
+    /// This replaces the callbacks set by earlier [`Self::on_ok`] or [`Self::on_cancel`] calls,
+    /// and a later call of either replaces its half of the result.
+    pub fn on_result(self, on_result: impl Fn(bool, &mut Window, &mut App) + 'static) -> Self {
+        let on_result = Rc::new(on_result);
+        let on_cancel = on_result.clone();
+        self.on_ok(move |_, window, cx| {
+            on_result(true, window, cx);
+            true
+        })
+        .on_cancel(move |_, window, cx| {
+            on_cancel(false, window, cx);
+            true
+        })
+    }
+
     /// Sets the false to hide close icon, default: true
     pub fn close_button(mut self, close_button: bool) -> Self {
         self.close_button = close_button;
@@ -2,12 +2,14 @@ use crate::highlighter::{HighlightTheme, LanguageRegistry};
 use crate::input::RopeExt;
 
 use anyhow::{anyhow, Context, Result};
-use gpui::{HighlightStyle, SharedString};
+use gpui::{App, HighlightStyle, SharedString, Task};
 
 use ropey::{ChunkCursor, Rope};
 use std::{
+    cell::RefCell,
     collections::{BTreeSet, HashMap},
     ops::Range,
+    sync::Arc,
     usize,
 };
 use sum_tree::Bias;
@@ -22,6 +24,8 @@ pub struct SyntaxHighlighter {
     language: SharedString,
     query: Option<Query>,
     injection_queries: HashMap<SharedString, Query>,
+    /// The node kinds of the bracket pairs.
+    brackets: Vec<(SharedString, SharedString)>,
 
     locals_pattern_index: usize,
     highlights_pattern_index: usize,
@@ -39,6 +43,25 @@ pub struct SyntaxHighlighter {
     parser: Parser,
     /// The last parsed tree.
     tree: Option<Tree>,
+    /// The parsed trees of the injections, keyed by the byte range of the content node.
+    ///
+    /// The injections not highlighted since the last [`SyntaxHighlighter::evict_unvisited`]
+    /// are dropped by it.
+    injection_layers: RefCell<HashMap<Range<usize>, InjectionLayer>>,
+    /// The number of times the injections have been parsed.
+    #[cfg(test)]
+    injection_parse_count: std::cell::Cell<usize>,
+}
+
+/// A cached parse tree of an injection.
+struct InjectionLayer {
+    language: SharedString,
+    /// The tree of the injection content, the offsets are relative to the content start.
+    tree: Tree,
+    /// Whether the tree has been edited and needs to be reparsed.
+    dirty: bool,
+    /// Whether the injection has been highlighted since the last eviction.
+    visited: bool,
 }
 
 struct TextProvider<'a>(&'a Rope);
@@ -289,6 +312,7 @@ impl SyntaxHighlighter {
             language: config.name.clone(),
             query: Some(query),
             injection_queries,
+            brackets: config.brackets.clone(),
 
             locals_pattern_index,
             highlights_pattern_index,
@@ -302,6 +326,44 @@ impl SyntaxHighlighter {
             text: Rope::new(),
             parser,
             tree: None,
+            injection_layers: RefCell::new(HashMap::new()),
+            #[cfg(test)]
+            injection_parse_count: std::cell::Cell::new(0),
+        })
+    }
+
+    /// Parse the text and compute the highlight styles of the `range` on the background executor.
+    ///
+    /// The `Tree` and `Parser` are not shared across threads, so this is a one-shot highlighter
+    /// that does a full parse: the text (cheap to clone as a `Rope`) and the language are moved
+    /// to the background, and the owned styles are returned, see [`SyntaxHighlighter::styles`].
+    ///
+    /// This is meant for large texts that are too slow to highlight on the UI thread,
+    /// for incremental updates, keep a [`SyntaxHighlighter`] and use [`SyntaxHighlighter::update`].
+    ///
+    /// ```ignore
+    /// let task = SyntaxHighlighter::background_styles("rust", text, 0..len, theme, cx);
+    /// cx.spawn(async move |this, cx| {
+    ///     let styles = task.await;
+    ///     _ = this.update(cx, |this, cx| {
+    ///         this.styles = styles;
+    ///         cx.notify();
+    ///     });
+    /// })
+    /// .detach();
+    /// ```
+    pub fn background_styles(
+        language: impl Into<SharedString>,
+        text: Rope,
+        range: Range<usize>,
+        theme: Arc<HighlightTheme>,
+        cx: &App,
+    ) -> Task<Vec<(Range<usize>, HighlightStyle)>> {
+        let language = language.into();
+        cx.background_spawn(async move {
+            let mut highlighter = SyntaxHighlighter::new(&language);
+            highlighter.update(None, &text);
+            highlighter.styles(&range, &theme)
         })
     }
 
@@ -317,6 +379,11 @@ impl SyntaxHighlighter {
             return;
         }
 
+        match &edit {
+            Some(edit) => self.edit_injection_layers(edit),
+            None => self.injection_layers.get_mut().clear(),
+        }
+
         let edit = edit.unwrap_or(InputEdit {
             start_byte: 0,
             old_end_byte: 0,
@@ -353,6 +420,101 @@ impl SyntaxHighlighter {
         self.text = text.clone();
     }
 
+    /// Returns the byte range of the bracket matching the bracket at the given offset.
+    ///
+    /// The bracket pairs are the node kinds in [`LanguageConfig::brackets`] of the language,
+    /// and the partner is searched in the siblings of the bracket node.
+    ///
+    /// Returns `None` if the offset is not on a bracket, the bracket is unmatched,
+    /// or the text has not been parsed.
+    ///
+    /// [`LanguageConfig::brackets`]: crate::highlighter::LanguageConfig::brackets
+    pub fn matching_bracket(&self, offset: usize) -> Option<Range<usize>> {
+        let tree = self.tree.as_ref()?;
+        if offset >= self.text.len() {
+            return None;
+        }
+
+        let node = tree.root_node().descendant_for_byte_range(offset, offset + 1)?;
+        if node.start_byte() != offset {
+            return None;
+        }
+
+        let kind = node.kind();
+        let (open, close, forward) = self.brackets.iter().find_map(|(open, close)| {
+            if open.as_ref() == kind {
+                Some((open, close, true))
+            } else if close.as_ref() == kind {
+                Some((open, close, false))
+            } else {
+                None
+            }
+        })?;
+
+        // Count the nested brackets of the same kind in the siblings.
+        let mut depth = 0;
+        let mut sibling = Some(node);
+        while let Some(current) = sibling {
+            if current.kind() == open.as_ref() {
+                depth += if forward { 1 } else { -1 };
+            } else if current.kind() == close.as_ref() {
+                depth += if forward { -1 } else { 1 };
+            }
+
+            if depth == 0 {
+                return Some(current.byte_range());
+            }
+
+            sibling = if forward {
+                current.next_sibling()
+            } else {
+                current.prev_sibling()
+            };
+        }
+
+        None
+    }
+
+    /// Apply the edit to the cached injection trees.
+    ///
+    /// The layers after the edit are shifted, the layers containing the edit are edited
+    /// to be reparsed incrementally, and the layers partially covered by the edit are dropped.
+    fn edit_injection_layers(&mut self, edit: &InputEdit) {
+        let layers = std::mem::take(self.injection_layers.get_mut());
+        let old_text = &self.text;
+
+        let layers = layers
+            .into_iter()
+            .filter_map(|(range, mut layer)| {
+                if edit.start_byte > range.end {
+                    Some((range, layer))
+                } else if edit.old_end_byte < range.start {
+                    let range = range.start - edit.old_end_byte + edit.new_end_byte
+                        ..range.end - edit.old_end_byte + edit.new_end_byte;
+                    Some((range, layer))
+                } else if range.start < edit.start_byte && edit.old_end_byte <= range.end {
+                    let origin = old_text.offset_to_point(range.start);
+                    layer.tree.edit(&InputEdit {
+                        start_byte: edit.start_byte - range.start,
+                        old_end_byte: edit.old_end_byte - range.start,
+                        new_end_byte: edit.new_end_byte - range.start,
+                        start_position: relative_point(edit.start_position, origin),
+                        old_end_position: relative_point(edit.old_end_position, origin),
+                        new_end_position: relative_point(edit.new_end_position, origin),
+                    });
+                    layer.dirty = true;
+
+                    let range = range.start..range.end - edit.old_end_byte + edit.new_end_byte;
+                    Some((range, layer))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        *self.injection_layers.get_mut() = layers;
+    }
+
     /// Match the visible ranges of nodes in the Tree for highlighting.
     fn match_styles(&self, range: Range<usize>) -> Vec<HighlightItem> {
         let mut highlights = vec![];
@@ -429,7 +591,10 @@ impl SyntaxHighlighter {
         highlights
     }
 
-    /// TODO: Use incremental parsing to handle the injection.
+    /// Parse and highlight the injection content.
+    ///
+    /// The trees are cached by the range of the content node, and reparsed incrementally
+    /// after they have been edited by [`SyntaxHighlighter::update`].
     fn handle_injection(
         &self,
         injection_language: &str,
@@ -451,18 +616,44 @@ impl SyntaxHighlighter {
         // FIXME: Avoid to_string.
         let content = content.to_string();
 
-        let Some(config) = LanguageRegistry::singleton().language(injection_language) else {
-            return cache;
-        };
-        let mut parser = Parser::new();
-        if parser.set_language(&config.language).is_err() {
-            return cache;
-        }
-
         let source = content.as_bytes();
-        let Some(tree) = parser.parse(source, None) else {
-            return cache;
+
+        let key = start_offset..end_offset;
+        let mut layers = self.injection_layers.borrow_mut();
+        let old_layer = layers
+            .remove(&key)
+            .filter(|layer| layer.language.as_ref() == injection_language);
+        let tree = match old_layer {
+            Some(layer) if !layer.dirty => layer.tree,
+            old_layer => {
+                let Some(config) = LanguageRegistry::singleton().language(injection_language)
+                else {
+                    return cache;
+                };
+                let mut parser = Parser::new();
+                if parser.set_language(&config.language).is_err() {
+                    return cache;
+                }
+
+                #[cfg(test)]
+                self.injection_parse_count.set(self.injection_parse_count.get() + 1);
+                let Some(tree) = parser.parse(source, old_layer.as_ref().map(|layer| &layer.tree))
+                else {
+                    return cache;
+                };
+                tree
+            }
         };
+        layers.insert(
+            key.clone(),
+            InjectionLayer {
+                language: SharedString::from(injection_language.to_string()),
+                tree,
+                dirty: false,
+                visited: true,
+            },
+        );
+        let tree = &layers[&key].tree;
 
         let mut query_cursor = QueryCursor::new();
         let mut matches = query_cursor.matches(query, tree.root_node(), source);
@@ -628,6 +819,55 @@ impl SyntaxHighlighter {
 
         styles
     }
+
+    /// Drop the cached trees of the injections that have not been highlighted since the last
+    /// call, e.g. scrolled out of view or removed, so the cache doesn't grow with the document.
+    ///
+    /// Call this once after a highlight pass, e.g. after [`SyntaxHighlighter::styles`] has been
+    /// called for each visible line, not after each call, or the injections of the previous
+    /// lines in the pass would be parsed again on the next pass.
+    pub fn evict_unvisited(&self) {
+        self.injection_layers
+            .borrow_mut()
+            .retain(|_, layer| std::mem::take(&mut layer.visited));
+    }
+
+    /// Returns the highlight capture names for a range of text, before resolving by the theme.
+    ///
+    /// The argument `range` is the range of bytes in the text, the returned ranges are
+    /// clamped to it and may overlap, in the order of the matches.
+    /// The names are the captures in the highlights query, e.g. `keyword`, `string.escape`.
+    ///
+    /// This is useful for debugging themes, or mapping the captures to another styling system.
+    pub fn captures(&self, range: &Range<usize>) -> Vec<(Range<usize>, SharedString)> {
+        self.match_styles(range.clone())
+            .into_iter()
+            .map(|item| {
+                let start = item.range.start.max(range.start);
+                let end = item.range.end.min(range.end).max(start);
+                (start..end, item.name)
+            })
+            .filter(|(range, _)| !range.is_empty())
+            .collect()
+    }
+
+    /// Returns the syntax highlight styles for a range of lines (0-based, end exclusive).
+    ///
+    /// The line numbers are converted to byte offsets of the last parsed text, the range
+    /// covers the start of the first line to the start of the line after the last one,
+    /// line numbers out of range are clamped to the end of the text.
+    ///
+    /// See [`SyntaxHighlighter::styles`] for the returned value.
+    pub fn styles_for_lines(
+        &self,
+        lines: Range<usize>,
+        theme: &HighlightTheme,
+    ) -> Vec<(Range<usize>, HighlightStyle)> {
+        let start = self.text.line_start_offset(lines.start);
+        let end = self.text.line_start_offset(lines.end).max(start);
+
+        self.styles(&(start..end), theme)
+    }
 }
 
 /// To merge intersection ranges, let the subsequent range cover
@@ -719,6 +959,15 @@ pub(crate) fn unique_styles(
     merged
 }
 
+/// Returns the point relative to the `origin` point.
+fn relative_point(point: Point, origin: Point) -> Point {
+    if point.row == origin.row {
+        Point::new(0, point.column.saturating_sub(origin.column))
+    } else {
+        Point::new(point.row.saturating_sub(origin.row), point.column)
+    }
+}
+
 /// Merge other style (Other on top)
 fn merge_highlight_style(style: &mut HighlightStyle, other: &HighlightStyle) {
     if let Some(color) = other.color {
@@ -802,6 +1051,177 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_styles_for_lines() {
+        let code = "{\n  \"a\": 1,\n  \"b\": true\n}";
+        let rope = Rope::from(code);
+        let mut highlighter = SyntaxHighlighter::new("json");
+        highlighter.update(None, &rope);
+        let theme = HighlightTheme::default_dark();
+
+        let line_start = code.find("  \"b\"").unwrap();
+        let styles = highlighter.styles_for_lines(2..3, &theme);
+        assert_eq!(styles.first().unwrap().0.start, line_start);
+        assert_eq!(styles.last().unwrap().0.end, code.len() - 1);
+        assert_eq!(
+            highlighter.styles_for_lines(2..3, &theme),
+            highlighter.styles(&(line_start..code.len() - 1), &theme)
+        );
+
+        // Out of range lines are clamped to the end of the text.
+        let styles = highlighter.styles_for_lines(2..100, &theme);
+        assert_eq!(styles.first().unwrap().0.start, line_start);
+        assert_eq!(styles.last().unwrap().0.end, code.len());
+    }
+
+    #[cfg(feature = "tree-sitter-languages")]
+    #[test]
+    fn test_incremental_injection_parsing() {
+        let theme = HighlightTheme::default_dark();
+        let code = "# Title\n\nHello **world**\n\nSecond paragraph\n\nThird `code`\n";
+        let mut rope = Rope::from(code);
+        let mut highlighter = SyntaxHighlighter::new("markdown");
+        highlighter.update(None, &rope);
+        highlighter.styles(&(0..rope.len()), &theme);
+        let parse_count = highlighter.injection_parse_count.get();
+        assert!(parse_count >= 3);
+
+        // Unchanged injections are not parsed again.
+        highlighter.styles(&(0..rope.len()), &theme);
+        assert_eq!(highlighter.injection_parse_count.get(), parse_count);
+
+        // Only the edited injection is reparsed.
+        let offset = code.find("paragraph").unwrap();
+        let start_position = rope.offset_to_point(offset);
+        rope.replace(offset..offset, "long ");
+        highlighter.update(
+            Some(InputEdit {
+                start_byte: offset,
+                old_end_byte: offset,
+                new_end_byte: offset + 5,
+                start_position,
+                old_end_position: start_position,
+                new_end_position: rope.offset_to_point(offset + 5),
+            }),
+            &rope,
+        );
+        highlighter.styles(&(0..rope.len()), &theme);
+        assert_eq!(highlighter.injection_parse_count.get(), parse_count + 1);
+
+        // A full update drops the cached trees.
+        highlighter.update(None, &Rope::from(code));
+        highlighter.styles(&(0..code.len()), &theme);
+        assert_eq!(highlighter.injection_parse_count.get(), parse_count * 2 + 1);
+
+        // The injections out of the highlighted range are evicted, and parsed again later.
+        let parse_count = highlighter.injection_parse_count.get();
+        let layers_count = highlighter.injection_layers.borrow().len();
+        highlighter.evict_unvisited();
+        highlighter.styles(&(0.."# Title".len()), &theme);
+        highlighter.evict_unvisited();
+        let kept_count = highlighter.injection_layers.borrow().len();
+        assert!(kept_count < layers_count);
+        assert_eq!(highlighter.injection_parse_count.get(), parse_count);
+
+        highlighter.styles(&(0..code.len()), &theme);
+        assert_eq!(highlighter.injection_layers.borrow().len(), layers_count);
+        assert_eq!(
+            highlighter.injection_parse_count.get(),
+            parse_count + layers_count - kept_count
+        );
+    }
+
+    #[cfg(feature = "tree-sitter-languages")]
+    #[test]
+    fn test_injection_cache_across_lines() {
+        let theme = HighlightTheme::default_dark();
+        let code = "# Title\n\nHello **world**\n\nSecond paragraph\n\nThird `code`\n";
+        let rope = Rope::from(code);
+        let mut highlighter = SyntaxHighlighter::new("markdown");
+        highlighter.update(None, &rope);
+
+        // Highlight line by line like the editor, then evict once for the pass.
+        let highlight_pass = |highlighter: &SyntaxHighlighter| {
+            let mut offset = 0;
+            for line in code.split_inclusive('\n') {
+                highlighter.styles(&(offset..offset + line.len()), &theme);
+                offset += line.len();
+            }
+            highlighter.evict_unvisited();
+        };
+
+        highlight_pass(&highlighter);
+        let parse_count = highlighter.injection_parse_count.get();
+        assert!(parse_count >= 3);
+        assert_eq!(highlighter.injection_layers.borrow().len(), parse_count);
+
+        highlight_pass(&highlighter);
+        assert_eq!(highlighter.injection_parse_count.get(), parse_count);
+    }
+
+    #[gpui::test]
+    async fn test_background_styles(cx: &mut gpui::TestAppContext) {
+        let code = (0..200)
+            .map(|i| format!("  {{\"id\": {}, \"name\": \"item {}\", \"active\": true}},\n", i, i))
+            .collect::<String>();
+        let code = format!("[\n{}  null\n]\n", code);
+        assert!(code.len() > 8 * 1024);
+
+        let rope = Rope::from(code.as_str());
+        let theme = HighlightTheme::default_dark();
+        let range = 0..code.len();
+
+        let task = cx.update(|cx| {
+            SyntaxHighlighter::background_styles(
+                "json",
+                rope.clone(),
+                range.clone(),
+                theme.clone(),
+                cx,
+            )
+        });
+        let styles = task.await;
+
+        let mut highlighter = SyntaxHighlighter::new("json");
+        highlighter.update(None, &rope);
+        assert_eq!(styles, highlighter.styles(&range, &theme));
+        assert!(styles.len() > 1000);
+    }
+
+    #[test]
+    fn test_captures() {
+        let code = r#"{"a": true}"#;
+        let mut highlighter = SyntaxHighlighter::new("json");
+        highlighter.update(None, &Rope::from(code));
+
+        let captures = highlighter.captures(&(0..code.len()));
+        assert!(captures.iter().any(|(range, _)| range == &(1..4)));
+        assert!(captures.contains(&(6..10, "boolean".into())));
+
+        // Clamped to the given range
+        let captures = highlighter.captures(&(7..9));
+        assert_eq!(captures, vec![(7..9, "boolean".into())]);
+    }
+
+    #[test]
+    fn test_matching_bracket() {
+        let code = r#"{"a": [1, [2]], "b": {}}"#;
+        let mut highlighter = SyntaxHighlighter::new("json");
+        assert_eq!(highlighter.matching_bracket(0), None);
+
+        highlighter.update(None, &Rope::from(code));
+        assert_eq!(highlighter.matching_bracket(0), Some(23..24));
+        assert_eq!(highlighter.matching_bracket(23), Some(0..1));
+        assert_eq!(highlighter.matching_bracket(6), Some(13..14));
+        assert_eq!(highlighter.matching_bracket(13), Some(6..7));
+        assert_eq!(highlighter.matching_bracket(10), Some(12..13));
+        assert_eq!(highlighter.matching_bracket(21), Some(22..23));
+        // Not on a bracket
+        assert_eq!(highlighter.matching_bracket(1), None);
+        assert_eq!(highlighter.matching_bracket(7), None);
+        assert_eq!(highlighter.matching_bracket(100), None);
+    }
+
     #[test]
     fn test_unique_styles() {
         let red = color_style(gpui::red());
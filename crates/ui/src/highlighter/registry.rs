@@ -1,4 +1,5 @@
-use gpui::{App, FontWeight, HighlightStyle, Hsla, SharedString};
+use anyhow::{bail, Context as _, Result};
+use gpui::{px, App, FontWeight, HighlightStyle, Hsla, SharedString, UnderlineStyle};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -10,7 +11,7 @@ use std::{
 
 use crate::{
     highlighter::{languages, Language},
-    ActiveTheme, ThemeMode, DEFAULT_THEME_COLORS,
+    ActiveTheme, Colorize as _, ThemeMode, DEFAULT_THEME_COLORS,
 };
 
 pub(super) const HIGHLIGHT_NAMES: [&str; 40] = [
@@ -64,6 +65,8 @@ pub struct LanguageConfig {
     pub highlights: SharedString,
     pub injections: SharedString,
     pub locals: SharedString,
+    /// The node kinds of the bracket pairs, used to find matching brackets.
+    pub brackets: Vec<(SharedString, SharedString)>,
 }
 
 impl LanguageConfig {
@@ -82,8 +85,21 @@ impl LanguageConfig {
             highlights: SharedString::from(highlights.to_string()),
             injections: SharedString::from(injections.to_string()),
             locals: SharedString::from(locals.to_string()),
+            brackets: vec![
+                ("(".into(), ")".into()),
+                ("[".into(), "]".into()),
+                ("{".into(), "}".into()),
+            ],
         }
     }
+
+    /// Set the node kinds of the bracket pairs, default is `()`, `[]` and `{}`.
+    ///
+    /// This is for the grammars that use other node kinds for the brackets.
+    pub fn brackets(mut self, brackets: Vec<(SharedString, SharedString)>) -> Self {
+        self.brackets = brackets;
+        self
+    }
 }
 
 /// Theme for Tree-sitter Highlight
@@ -210,6 +226,10 @@ impl From<ThemeStyle> for HighlightStyle {
             color: style.color,
             font_weight: style.font_weight.map(Into::into),
             font_style: style.font_style.map(Into::into),
+            underline: (style.font_style == Some(FontStyle::Underline)).then(|| UnderlineStyle {
+                thickness: px(1.),
+                ..Default::default()
+            }),
             ..Default::default()
         }
     }
@@ -286,6 +306,54 @@ impl SyntaxColors {
     pub fn style_for_index(&self, index: usize) -> Option<HighlightStyle> {
         HIGHLIGHT_NAMES.get(index).and_then(|name| self.style(name))
     }
+
+    fn style_mut(&mut self, name: &str) -> Option<&mut Option<ThemeStyle>> {
+        let style = match name {
+            "attribute" => &mut self.attribute,
+            "boolean" => &mut self.boolean,
+            "comment" => &mut self.comment,
+            "comment.doc" => &mut self.comment_doc,
+            "constant" => &mut self.constant,
+            "constructor" => &mut self.constructor,
+            "embedded" => &mut self.embedded,
+            "emphasis" => &mut self.emphasis,
+            "emphasis.strong" => &mut self.emphasis_strong,
+            "enum" => &mut self.enum_,
+            "function" => &mut self.function,
+            "hint" => &mut self.hint,
+            "keyword" => &mut self.keyword,
+            "label" => &mut self.label,
+            "link_text" => &mut self.link_text,
+            "link_uri" => &mut self.link_uri,
+            "number" => &mut self.number,
+            "operator" => &mut self.operator,
+            "predictive" => &mut self.predictive,
+            "preproc" => &mut self.preproc,
+            "primary" => &mut self.primary,
+            "property" => &mut self.property,
+            "punctuation" => &mut self.punctuation,
+            "punctuation.bracket" => &mut self.punctuation_bracket,
+            "punctuation.delimiter" => &mut self.punctuation_delimiter,
+            "punctuation.list_marker" => &mut self.punctuation_list_marker,
+            "punctuation.special" => &mut self.punctuation_special,
+            "string" => &mut self.string,
+            "string.escape" => &mut self.string_escape,
+            "string.regex" => &mut self.string_regex,
+            "string.special" => &mut self.string_special,
+            "string.special.symbol" => &mut self.string_special_symbol,
+            "tag" => &mut self.tag,
+            "tag.doctype" => &mut self.tag_doctype,
+            "text.literal" => &mut self.text_literal,
+            "title" => &mut self.title,
+            "type" => &mut self.type_,
+            "variable" => &mut self.variable,
+            "variable.special" => &mut self.variable_special,
+            "variant" => &mut self.variant,
+            _ => return None,
+        };
+
+        Some(style)
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
@@ -456,6 +524,98 @@ impl HighlightTheme {
     pub fn default_light() -> Arc<Self> {
         DEFAULT_THEME_COLORS[&ThemeMode::Light].1.clone()
     }
+
+    /// Load a highlight theme from a JSON map of capture names to style attributes.
+    ///
+    /// The capture names are the ones supported by [`SyntaxColors`], e.g. `keyword`,
+    /// `string.escape`, and the attributes are:
+    ///
+    /// - `color`: The HEX color string, `#RRGGBB` or `#RRGGBBAA`.
+    /// - `bold`, `italic`, `underline`: Boolean flags, `italic` and `underline` can't be combined.
+    ///
+    /// Returns an error for unknown capture names or attributes and malformed values.
+    ///
+    /// ```
+    /// use gpui_component::highlighter::HighlightTheme;
+    ///
+    /// let theme = HighlightTheme::from_json(
+    ///     r##"{
+    ///         "keyword": { "color": "#C678DD", "bold": true },
+    ///         "comment": { "color": "#5C6370", "italic": true }
+    ///     }"##,
+    /// )
+    /// .unwrap();
+    /// assert!(theme.style("keyword").is_some());
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self> {
+        let captures: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(json).context("invalid highlight theme JSON")?;
+
+        let mut syntax = SyntaxColors::default();
+        for (name, attributes) in captures {
+            let Some(slot) = syntax.style_mut(&name) else {
+                bail!("unknown capture name {:?}", name);
+            };
+            let Some(attributes) = attributes.as_object() else {
+                bail!("style of {:?} must be an object", name);
+            };
+
+            let mut style = ThemeStyle {
+                color: None,
+                font_style: None,
+                font_weight: None,
+            };
+            let mut italic = false;
+            let mut underline = false;
+            for (key, value) in attributes {
+                match key.as_str() {
+                    "color" => {
+                        let color = value
+                            .as_str()
+                            .filter(|color| {
+                                color.starts_with('#')
+                                    && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+                            })
+                            .and_then(|color| Hsla::parse_hex(color).ok());
+                        let Some(color) = color else {
+                            bail!("invalid color {} of {:?}, expected `#RRGGBB`", value, name);
+                        };
+                        style.color = Some(color);
+                    }
+                    "bold" | "italic" | "underline" => {
+                        let Some(enabled) = value.as_bool() else {
+                            bail!("invalid {} {} of {:?}, expected a boolean", key, value, name);
+                        };
+                        match key.as_str() {
+                            "bold" => {
+                                style.font_weight = enabled.then_some(FontWeightContent::Bold)
+                            }
+                            "italic" => italic = enabled,
+                            _ => underline = enabled,
+                        }
+                    }
+                    _ => bail!("unknown attribute {:?} of {:?}", key, name),
+                }
+            }
+
+            style.font_style = match (italic, underline) {
+                (true, true) => bail!("italic and underline of {:?} can't be combined", name),
+                (true, false) => Some(FontStyle::Italic),
+                (false, true) => Some(FontStyle::Underline),
+                (false, false) => None,
+            };
+            *slot = Some(style);
+        }
+
+        Ok(Self {
+            name: "Custom".into(),
+            appearance: ThemeMode::default(),
+            style: HighlightThemeStyle {
+                syntax,
+                ..Default::default()
+            },
+        })
+    }
 }
 
 /// Registry for code highlighter languages.
@@ -521,4 +681,43 @@ mod tests {
         assert!(registry.language("javascript").is_some());
         assert!(registry.language("js").is_some());
     }
+
+    #[test]
+    fn test_highlight_theme_from_json() {
+        use super::HighlightTheme;
+        use crate::Colorize as _;
+        use gpui::{FontStyle, FontWeight};
+
+        let theme = HighlightTheme::from_json(
+            r##"{
+                "keyword": { "color": "#FF0000", "bold": true },
+                "comment.doc": { "color": "#00ff0080", "italic": true },
+                "string": { "underline": true }
+            }"##,
+        )
+        .unwrap();
+
+        let keyword = theme.style("keyword").unwrap();
+        assert_eq!(keyword.color.unwrap().to_hex(), "#FF0000");
+        assert_eq!(keyword.font_weight, Some(FontWeight::BOLD));
+        let doc = theme.style("comment.doc").unwrap();
+        assert_eq!(doc.color.unwrap().a, 128. / 255.);
+        assert_eq!(doc.font_style, Some(FontStyle::Italic));
+        assert!(theme.style("string").unwrap().underline.is_some());
+        assert!(theme.style("function").is_none());
+
+        for json in [
+            r#"{ "keyword": { "color": "red" } }"#,
+            r#"{ "keyword": { "color": "#FF00" } }"#,
+            r#"{ "keyword": { "color": "#GG0000" } }"#,
+            r#"{ "keyword": { "bold": "yes" } }"#,
+            r#"{ "keyword": { "size": 12 } }"#,
+            r#"{ "keyword": { "italic": true, "underline": true } }"#,
+            r#"{ "unknown": { "bold": true } }"#,
+            r#"{ "keyword": "#FF0000" }"#,
+            r#"[]"#,
+        ] {
+            assert!(HighlightTheme::from_json(json).is_err(), "{}", json);
+        }
+    }
 }
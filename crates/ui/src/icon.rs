@@ -1,9 +1,10 @@
 use crate::{ActiveTheme, Sizable, Size};
 use gpui::{
-    AnyElement, App, AppContext, Context, Entity, Hsla, IntoElement, Radians, Render, RenderOnce,
-    SharedString, StyleRefinement, Styled, Svg, Transformation, Window,
-    prelude::FluentBuilder as _, svg,
+    Animation, AnimationExt as _, AnyElement, App, AppContext, Context, ElementId, Entity, Hsla,
+    IntoElement, Radians, Render, RenderOnce, SharedString, StyleRefinement, Styled, Svg,
+    Transformation, Window, ease_in_out, percentage, prelude::FluentBuilder as _, svg,
 };
+use std::time::Duration;
 
 /// Types implementing this trait can automatically be converted to [`Icon`].
 ///
@@ -91,6 +92,7 @@ pub enum IconName {
     PanelRight,
     PanelRightClose,
     PanelRightOpen,
+    Pipette,
     Plus,
     Redo,
     Redo2,
@@ -204,6 +206,7 @@ impl IconNamed for IconName {
             Self::PanelRight => "icons/panel-right.svg",
             Self::PanelRightClose => "icons/panel-right-close.svg",
             Self::PanelRightOpen => "icons/panel-right-open.svg",
+            Self::Pipette => "icons/pipette.svg",
             Self::Plus => "icons/plus.svg",
             Self::Redo => "icons/redo.svg",
             Self::Redo2 => "icons/redo-2.svg",
@@ -246,6 +249,25 @@ impl RenderOnce for IconName {
     }
 }
 
+#[derive(Clone)]
+enum IconAnimation {
+    /// Continuously rotate the icon, one turn per duration.
+    Spin(Duration),
+    /// Rotate to the given degrees, with a transition from the previous degrees.
+    RotateTo {
+        id: ElementId,
+        degrees: f32,
+        duration: Duration,
+    },
+}
+
+/// The rotation state of [`Icon::rotate_to`], kept between renders.
+struct IconRotationState {
+    from: f32,
+    to: f32,
+    generation: usize,
+}
+
 #[derive(IntoElement)]
 pub struct Icon {
     base: Svg,
@@ -254,6 +276,7 @@ pub struct Icon {
     text_color: Option<Hsla>,
     size: Option<Size>,
     rotation: Option<Radians>,
+    animation: Option<IconAnimation>,
 }
 
 impl Default for Icon {
@@ -265,6 +288,7 @@ impl Default for Icon {
             text_color: None,
             size: None,
             rotation: None,
+            animation: None,
         }
     }
 }
@@ -276,6 +300,7 @@ impl Clone for Icon {
         this.rotation = self.rotation;
         this.size = self.size;
         this.text_color = self.text_color;
+        this.animation = self.animation.clone();
         this
     }
 }
@@ -318,6 +343,32 @@ impl Icon {
             .with_transformation(Transformation::rotate(radians));
         self
     }
+
+    /// Continuously rotate the icon, e.g. for a loading indicator.
+    ///
+    /// See also [`Icon::spin_with_speed`].
+    pub fn spin(self) -> Self {
+        self.spin_with_speed(Duration::from_secs_f64(0.8))
+    }
+
+    /// Continuously rotate the icon, making one turn per `speed`.
+    pub fn spin_with_speed(mut self, speed: Duration) -> Self {
+        self.animation = Some(IconAnimation::Spin(speed));
+        self
+    }
+
+    /// Rotate the icon to the given angle (in degrees) with a smooth transition.
+    ///
+    /// The `id` is used to keep the previous angle between renders,
+    /// so the transition starts from where the icon was.
+    pub fn rotate_to(mut self, id: impl Into<ElementId>, degrees: f32) -> Self {
+        self.animation = Some(IconAnimation::RotateTo {
+            id: id.into(),
+            degrees,
+            duration: Duration::from_millis(150),
+        });
+        self
+    }
 }
 
 impl Styled for Icon {
@@ -339,7 +390,7 @@ impl Sizable for Icon {
 }
 
 impl RenderOnce for Icon {
-    fn render(self, window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let text_color = self.text_color.unwrap_or_else(|| window.text_style().color);
         let text_size = window.text_style().font_size.to_pixels(window.rem_size());
         let has_base_size = self.style.size.width.is_some() || self.style.size.height.is_some();
@@ -347,7 +398,8 @@ impl RenderOnce for Icon {
         let mut base = self.base;
         *base.style() = self.style;
 
-        base.flex_shrink_0()
+        let base = base
+            .flex_shrink_0()
             .text_color(text_color)
             .when(!has_base_size, |this| this.size(text_size))
             .when_some(self.size, |this, size| match size {
@@ -357,7 +409,50 @@ impl RenderOnce for Icon {
                 Size::Medium => this.size_4(),
                 Size::Large => this.size_6(),
             })
-            .path(self.path)
+            .path(self.path);
+
+        match self.animation {
+            None => base.into_any_element(),
+            Some(IconAnimation::Spin(speed)) => base
+                .with_animation(
+                    "spin",
+                    Animation::new(speed).repeat().with_easing(ease_in_out),
+                    |this, delta| this.with_transformation(Transformation::rotate(percentage(delta))),
+                )
+                .into_any_element(),
+            Some(IconAnimation::RotateTo {
+                id,
+                degrees,
+                duration,
+            }) => {
+                let state = window.use_keyed_state(id, cx, |_, _| IconRotationState {
+                    from: degrees,
+                    to: degrees,
+                    generation: 0,
+                });
+                if state.read(cx).to != degrees {
+                    state.update(cx, |state, _| {
+                        state.from = state.to;
+                        state.to = degrees;
+                        state.generation += 1;
+                    });
+                }
+
+                let state = state.read(cx);
+                let (from, to) = (state.from, state.to);
+                base.with_animation(
+                    SharedString::from(format!("rotate-{}", state.generation)),
+                    Animation::new(duration).with_easing(ease_in_out),
+                    move |this, delta| {
+                        let degrees = from + (to - from) * delta;
+                        this.with_transformation(Transformation::rotate(percentage(
+                            degrees / 360.,
+                        )))
+                    },
+                )
+                .into_any_element()
+            }
+        }
     }
 }
 
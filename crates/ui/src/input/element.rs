@@ -794,6 +794,7 @@ impl TextElement {
 
             offset = range.end;
         }
+        highlighter.evict_unvisited();
 
         let diagnostic_styles = diagnostics.styles_for_range(&visible_byte_range, cx);
 
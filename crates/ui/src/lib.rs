@@ -61,6 +61,7 @@ pub mod sidebar;
 pub mod skeleton;
 pub mod slider;
 pub mod spinner;
+pub mod split_pane;
 pub mod stepper;
 pub mod switch;
 pub mod tab;
@@ -110,6 +111,7 @@ pub fn init(cx: &mut App) {
     dialog::init(cx);
     popover::init(cx);
     menu::init(cx);
+    sidebar::init(cx);
     table::init(cx);
     text::init(cx);
     tree::init(cx);
@@ -73,6 +73,8 @@ pub struct Notification {
     message: Option<SharedString>,
     icon: Option<Icon>,
     autohide: bool,
+    /// The delay before the notification is auto hidden.
+    autohide_duration: Duration,
     action_builder: Option<Rc<dyn Fn(&mut Self, &mut Window, &mut Context<Self>) -> Button>>,
     content_builder: Option<Rc<dyn Fn(&mut Self, &mut Window, &mut Context<Self>) -> AnyElement>>,
     on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
@@ -127,6 +129,7 @@ impl Notification {
             type_: None,
             icon: None,
             autohide: true,
+            autohide_duration: Duration::from_secs(5),
             action_builder: None,
             content_builder: None,
             on_click: None,
@@ -213,6 +216,13 @@ impl Notification {
         self
     }
 
+    /// Auto hide the notification after the duration, default is 5 seconds.
+    pub fn autohide_after(mut self, duration: Duration) -> Self {
+        self.autohide = true;
+        self.autohide_duration = duration;
+        self
+    }
+
     /// Set the click callback of the notification.
     pub fn on_click(
         mut self,
@@ -442,7 +452,7 @@ impl NotificationList {
     ) {
         let notification = notification.into();
         let id = notification.id.clone();
-        let autohide = notification.autohide;
+        let autohide = notification.autohide.then_some(notification.autohide_duration);
 
         // Remove the notification by id, for keep unique.
         self.notifications.retain(|note| note.read(cx).id != id);
@@ -458,10 +468,9 @@ impl NotificationList {
         );
 
         self.notifications.push_back(notification.clone());
-        if autohide {
-            // Sleep for 5 seconds to autohide the notification
+        if let Some(duration) = autohide {
             cx.spawn_in(window, async move |_, cx| {
-                Timer::after(Duration::from_secs(5)).await;
+                Timer::after(duration).await;
 
                 if let Err(err) =
                     notification.update_in(cx, |note, window, cx| note.dismiss(window, cx))
@@ -0,0 +1,74 @@
+use gpui::{
+    fill, point, px, size, App, Bounds, Hsla, Pixels, SharedString, TextAlign, TextRun, Window,
+};
+
+use super::label::{TEXT_GAP, TEXT_HEIGHT, TEXT_SIZE};
+
+/// The height reserved on top of a plot for the [`Legend`].
+pub const LEGEND_HEIGHT: f32 = TEXT_HEIGHT + TEXT_GAP;
+
+const SWATCH_SIZE: f32 = 8.;
+const SWATCH_GAP: f32 = 4.;
+const ITEM_GAP: f32 = 12.;
+
+/// A legend of named series, painted at the top right of the plot.
+pub struct Legend {
+    items: Vec<(SharedString, Hsla)>,
+    text_color: Hsla,
+}
+
+impl Legend {
+    pub fn new(items: impl IntoIterator<Item = (SharedString, Hsla)>) -> Self {
+        Self {
+            items: items.into_iter().collect(),
+            text_color: Default::default(),
+        }
+    }
+
+    /// Set the color of the series names.
+    pub fn text_color(mut self, text_color: impl Into<Hsla>) -> Self {
+        self.text_color = text_color.into();
+        self
+    }
+
+    /// Paint the Legend, right to left from the top right corner of the bounds.
+    pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        let font_size = px(TEXT_SIZE);
+        let top = bounds.top();
+        let mut right = bounds.right();
+
+        for (name, color) in self.items.iter().rev() {
+            let text_run = TextRun {
+                len: name.len(),
+                font: window.text_style().font(),
+                color: self.text_color,
+                background_color: None,
+                underline: None,
+                strikethrough: None,
+            };
+
+            let Ok(lines) =
+                window
+                    .text_system()
+                    .shape_text(name.clone(), font_size, &[text_run], None, None)
+            else {
+                continue;
+            };
+
+            for line in lines {
+                right -= line.size(font_size).width;
+                let _ = line.paint(point(right, top), font_size, TextAlign::Left, None, window, cx);
+            }
+
+            right -= px(SWATCH_GAP + SWATCH_SIZE);
+            window.paint_quad(fill(
+                Bounds::new(
+                    point(right, top + px((TEXT_SIZE - SWATCH_SIZE) / 2.)),
+                    size(px(SWATCH_SIZE), px(SWATCH_SIZE)),
+                ),
+                *color,
+            ));
+            right -= px(ITEM_GAP);
+        }
+    }
+}
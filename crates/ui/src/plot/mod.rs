@@ -1,6 +1,7 @@
 mod axis;
 mod grid;
 pub mod label;
+mod legend;
 pub mod scale;
 pub mod shape;
 pub mod tooltip;
@@ -9,16 +10,123 @@ pub use gpui_component_macros::IntoPlot;
 
 use std::{fmt::Debug, ops::Add};
 
-use gpui::{point, px, App, Bounds, IntoElement, Path, PathBuilder, Pixels, Point, Window};
+use gpui::{
+    point, px, relative, AnyElement, App, Bounds, IntoElement, Length, Path, PathBuilder, Pixels,
+    Point, Window,
+};
 
-use crate::PixelsExt;
+use crate::{PixelsExt, Sizable, Size};
 
 pub use axis::{AxisText, PlotAxis, AXIS_GAP};
 pub use grid::Grid;
 pub use label::PlotLabel;
+pub use legend::{Legend, LEGEND_HEIGHT};
 
 pub trait Plot: IntoElement {
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App);
+
+    /// Returns the tooltip to show while the pointer hovers the plot, default is none.
+    ///
+    /// The `position` is relative to the plot bounds, and the returned element is laid
+    /// out over the plot bounds.
+    fn tooltip(
+        &mut self,
+        _bounds: Bounds<Pixels>,
+        _position: Point<Pixels>,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Option<AnyElement> {
+        None
+    }
+
+    /// Returns the layout size of the plot, default is fill the parent.
+    fn plot_size(&self) -> PlotSize {
+        PlotSize::default()
+    }
+}
+
+/// The layout size of a [`Plot`].
+///
+/// By default the plot fills its parent, set a `width` / `height` or a [`Size`]
+/// to give the plot a fixed size, e.g. an inline sparkline in a table cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlotSize {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    size: Size,
+}
+
+impl PlotSize {
+    /// Set a fixed width.
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = Some(width.into());
+        self
+    }
+
+    /// Set a fixed height.
+    pub fn height(mut self, height: impl Into<Pixels>) -> Self {
+        self.height = Some(height.into());
+        self
+    }
+
+    /// Set the [`Size`], `XSmall` and `Small` are compact sizes with a fixed height.
+    pub fn size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// Returns true if the plot is compact, the axis and grid should not be painted.
+    pub fn is_compact(&self) -> bool {
+        matches!(self.size, Size::XSmall | Size::Small)
+    }
+
+    /// Returns the resolved layout size.
+    pub fn layout_size(&self) -> gpui::Size<Length> {
+        let height = self.height.or(match self.size {
+            Size::XSmall => Some(px(24.)),
+            Size::Small => Some(px(48.)),
+            Size::Size(height) => Some(height),
+            Size::Medium | Size::Large => None,
+        });
+
+        gpui::Size {
+            width: self.width.map_or(relative(1.).into(), Into::into),
+            height: height.map_or(relative(1.).into(), Into::into),
+        }
+    }
+}
+
+/// A [`Plot`] with a [`PlotSize`], provides the `width` and `height` builders,
+/// and [`Sizable`] to give the plot a fixed size.
+///
+/// ```ignore
+/// Sparkline::new(&values, color).width(px(100.)).xsmall()
+/// ```
+pub trait PlotSized: Sized {
+    /// Returns the [`PlotSize`] of the plot to update.
+    fn plot_size_mut(&mut self) -> &mut PlotSize;
+
+    /// Set a fixed width of the plot, default is fill the parent.
+    fn width(mut self, width: impl Into<Pixels>) -> Self {
+        let size = self.plot_size_mut();
+        *size = size.width(width);
+        self
+    }
+
+    /// Set a fixed height of the plot, default is fill the parent.
+    fn height(mut self, height: impl Into<Pixels>) -> Self {
+        let size = self.plot_size_mut();
+        *size = size.height(height);
+        self
+    }
+}
+
+impl<P: PlotSized> Sizable for P {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        let plot_size = self.plot_size_mut();
+        *plot_size = plot_size.size(size);
+        self
+    }
 }
 
 #[derive(Clone, Copy, Default)]
@@ -8,6 +8,8 @@ use super::Scale;
 #[derive(Clone)]
 pub struct ScalePoint<T> {
     domain: Vec<T>,
+    /// The start of the range, the first point.
+    range_start: f32,
     range_tick: f32,
 }
 
@@ -17,23 +19,24 @@ where
 {
     pub fn new(domain: Vec<T>, range: Vec<f32>) -> Self {
         let len = domain.len();
+        let (range_start, range_diff) = range
+            .iter()
+            .minmax()
+            .into_option()
+            .map_or((0., 0.), |(min, max)| (*min, max - min));
         let range_tick = if len.is_zero() {
             0.
+        } else if len == 1 {
+            range_diff
         } else {
-            let range_diff = range
-                .iter()
-                .minmax()
-                .into_option()
-                .map_or(0., |(min, max)| max - min);
-
-            if len == 1 {
-                range_diff
-            } else {
-                range_diff / len.saturating_sub(1) as f32
-            }
+            range_diff / len.saturating_sub(1) as f32
         };
 
-        Self { domain, range_tick }
+        Self {
+            domain,
+            range_start,
+            range_tick,
+        }
     }
 }
 
@@ -43,10 +46,10 @@ where
 {
     fn tick(&self, value: &T) -> Option<f32> {
         if self.domain.len() == 1 {
-            Some(self.range_tick / 2.)
+            Some(self.range_start + self.range_tick / 2.)
         } else {
             let index = self.domain.iter().position(|v| v == value)?;
-            Some(index as f32 * self.range_tick)
+            Some(self.range_start + index as f32 * self.range_tick)
         }
     }
 
@@ -55,7 +58,7 @@ where
             return 0;
         }
 
-        let index = (tick / self.range_tick).round() as usize;
+        let index = ((tick - self.range_start) / self.range_tick).round().max(0.) as usize;
         index.min(self.domain.len().saturating_sub(1))
     }
 }
@@ -90,4 +93,14 @@ mod tests {
         let scale = ScalePoint::new(vec![1], vec![0., 100.]);
         assert_eq!(scale.tick(&1), Some(50.));
     }
+
+    #[test]
+    fn test_scale_point_offset() {
+        let scale = ScalePoint::new(vec![1, 2, 3], vec![50., 100.]);
+        assert_eq!(scale.tick(&1), Some(50.));
+        assert_eq!(scale.tick(&3), Some(100.));
+        assert_eq!(scale.least_index(0.), 0);
+        assert_eq!(scale.least_index(74.), 1);
+        assert_eq!(scale.least_index(100.), 2);
+    }
 }
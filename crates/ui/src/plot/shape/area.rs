@@ -9,6 +9,7 @@ pub struct Area<T> {
     data: Vec<T>,
     x: Box<dyn Fn(&T) -> Option<f32>>,
     y0: Option<f32>,
+    y0_fn: Option<Box<dyn Fn(&T) -> Option<f32>>>,
     y1: Box<dyn Fn(&T) -> Option<f32>>,
     fill: Background,
     stroke: Background,
@@ -21,6 +22,7 @@ impl<T> Default for Area<T> {
             data: Vec::new(),
             x: Box::new(|_| None),
             y0: None,
+            y0_fn: None,
             y1: Box::new(|_| None),
             fill: Default::default(),
             stroke: Default::default(),
@@ -58,6 +60,15 @@ impl<T> Area<T> {
         self
     }
 
+    /// Set the y0 of the Area by each data point, e.g. the top of the area below in a stack.
+    pub fn y0_fn<F>(mut self, y0: F) -> Self
+    where
+        F: Fn(&T) -> Option<f32> + 'static,
+    {
+        self.y0_fn = Some(Box::new(y0));
+        self
+    }
+
     /// Set the y1 of the Area.
     pub fn y1<F>(mut self, y1: F) -> Self
     where
@@ -91,15 +102,17 @@ impl<T> Area<T> {
         let mut line_builder = PathBuilder::stroke(px(1.));
 
         let mut points = vec![];
+        let mut baseline = vec![];
 
         for v in self.data.iter() {
             let x_tick = (self.x)(v);
             let y_tick = (self.y1)(v);
 
             if let (Some(x), Some(y)) = (x_tick, y_tick) {
-                let pos = origin_point(px(x), px(y), origin);
-
-                points.push(pos);
+                points.push(origin_point(px(x), px(y), origin));
+                if let Some(y0) = self.y0_fn.as_ref().and_then(|y0_fn| y0_fn(v)) {
+                    baseline.push(origin_point(px(x), px(y0), origin));
+                }
             }
         }
 
@@ -107,63 +120,27 @@ impl<T> Area<T> {
             return (None, None);
         }
 
+        area_builder.move_to(points[0]);
+        line_builder.move_to(points[0]);
         if points.len() == 1 {
-            area_builder.move_to(points[0]);
-            line_builder.move_to(points[0]);
             return (area_builder.build().ok(), line_builder.build().ok());
         }
 
-        match self.stroke_style {
-            StrokeStyle::Natural => {
-                area_builder.move_to(points[0]);
-                line_builder.move_to(points[0]);
-                let n = points.len();
-                for i in 0..n - 1 {
-                    let p0 = if i == 0 { points[0] } else { points[i - 1] };
-                    let p1 = points[i];
-                    let p2 = points[i + 1];
-                    let p3 = if i + 2 < n {
-                        points[i + 2]
-                    } else {
-                        points[n - 1]
-                    };
-
-                    // Catmull-Rom to Bezier
-                    let c1 = Point::new(p1.x + (p2.x - p0.x) / 6.0, p1.y + (p2.y - p0.y) / 6.0);
-                    let c2 = Point::new(p2.x - (p3.x - p1.x) / 6.0, p2.y - (p3.y - p1.y) / 6.0);
-
-                    area_builder.cubic_bezier_to(p2, c1, c2);
-                    line_builder.cubic_bezier_to(p2, c1, c2);
-                }
-            }
-            StrokeStyle::Linear => {
-                area_builder.move_to(points[0]);
-                line_builder.move_to(points[0]);
-                for p in &points[1..] {
-                    area_builder.line_to(*p);
-                    line_builder.line_to(*p);
-                }
-            }
-            StrokeStyle::StepAfter => {
-                area_builder.move_to(points[0]);
-                line_builder.move_to(points[0]);
-                for p in points.windows(2) {
-                    area_builder.line_to(Point::new(p[1].x, p[0].y));
-                    area_builder.line_to(Point::new(p[1].x, p[1].y));
-                    line_builder.line_to(Point::new(p[1].x, p[0].y));
-                    line_builder.line_to(Point::new(p[1].x, p[1].y));
-                }
-            }
-        }
-
-        // Close path
-        if let Some(last) = self.data.last() {
-            let x_tick = (self.x)(last);
-            if let (Some(x), Some(y)) = (x_tick, self.y0) {
-                area_builder.line_to(origin_point(px(x), px(y), bounds.origin));
-                area_builder.line_to(origin_point(px(0.), px(y), bounds.origin));
-                area_builder.close();
-            }
+        curve_to(&mut area_builder, &points, self.stroke_style, false);
+        curve_to(&mut line_builder, &points, self.stroke_style, false);
+
+        // Close path, back along the baseline of each point, or a flat baseline from the last
+        // to the first point, which is not at the left edge in a fixed window.
+        if self.y0_fn.is_some() && baseline.len() == points.len() {
+            baseline.reverse();
+            area_builder.line_to(baseline[0]);
+            curve_to(&mut area_builder, &baseline, self.stroke_style, true);
+            area_builder.close();
+        } else if let Some(y) = self.y0 {
+            let (first, last) = (points[0], points[points.len() - 1]);
+            area_builder.line_to(Point::new(last.x, origin.y + px(y)));
+            area_builder.line_to(Point::new(first.x, origin.y + px(y)));
+            area_builder.close();
         }
 
         (area_builder.build().ok(), line_builder.build().ok())
@@ -181,3 +158,52 @@ impl<T> Area<T> {
         }
     }
 }
+
+/// Draw the curve through the `points` from the current position at the first point.
+///
+/// `reversed` draws the points from right to left, e.g. the baseline of a stacked area,
+/// so the steps match the steps drawn from left to right.
+fn curve_to(
+    builder: &mut PathBuilder,
+    points: &[Point<Pixels>],
+    stroke_style: StrokeStyle,
+    reversed: bool,
+) {
+    match stroke_style {
+        StrokeStyle::Natural => {
+            let n = points.len();
+            for i in 0..n - 1 {
+                let p0 = if i == 0 { points[0] } else { points[i - 1] };
+                let p1 = points[i];
+                let p2 = points[i + 1];
+                let p3 = if i + 2 < n {
+                    points[i + 2]
+                } else {
+                    points[n - 1]
+                };
+
+                // Catmull-Rom to Bezier
+                let c1 = Point::new(p1.x + (p2.x - p0.x) / 6.0, p1.y + (p2.y - p0.y) / 6.0);
+                let c2 = Point::new(p2.x - (p3.x - p1.x) / 6.0, p2.y - (p3.y - p1.y) / 6.0);
+
+                builder.cubic_bezier_to(p2, c1, c2);
+            }
+        }
+        StrokeStyle::Linear => {
+            for p in &points[1..] {
+                builder.line_to(*p);
+            }
+        }
+        StrokeStyle::StepAfter => {
+            for p in points.windows(2) {
+                let corner = if reversed {
+                    Point::new(p[0].x, p[1].y)
+                } else {
+                    Point::new(p[1].x, p[0].y)
+                };
+                builder.line_to(corner);
+                builder.line_to(p[1]);
+            }
+        }
+    }
+}
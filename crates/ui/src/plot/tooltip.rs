@@ -1,9 +1,9 @@
 use gpui::{
-    div, prelude::FluentBuilder, px, AnyElement, App, Div, Hsla, IntoElement, ParentElement,
-    Pixels, Point, RenderOnce, StyleRefinement, Styled, Window,
+    div, point, prelude::FluentBuilder, px, AnyElement, App, Div, Hsla, IntoElement,
+    ParentElement, Pixels, Point, RenderOnce, SharedString, StyleRefinement, Styled, Window,
 };
 
-use crate::{v_flex, ActiveTheme};
+use crate::{h_flex, v_flex, ActiveTheme, StyledExt};
 
 #[derive(Default)]
 pub enum CrossLineAxis {
@@ -289,3 +289,76 @@ impl RenderOnce for Tooltip {
             }))
     }
 }
+
+/// A series value under the pointer, shown as a row of a chart tooltip.
+pub(crate) struct TooltipValue {
+    /// The color of the series.
+    pub color: Hsla,
+    /// The Y tick of the value, `None` to skip the dot.
+    pub y: Option<f32>,
+    pub value: f64,
+}
+
+/// Build the hover tooltip of a point chart: a vertical cross line at `x`, a dot on
+/// each series and a box with the X `label` and the series values.
+///
+/// The box is placed on the side away from the pointer so it doesn't cover the cross line.
+pub(crate) fn point_tooltip(
+    label: SharedString,
+    x: f32,
+    width: f32,
+    height: f32,
+    values: Vec<TooltipValue>,
+    cx: &App,
+) -> AnyElement {
+    let position = if x > width / 2. {
+        TooltipPosition::Left
+    } else {
+        TooltipPosition::Right
+    };
+
+    Tooltip::new()
+        .position(position)
+        .gap(px(8.))
+        .top(px(8.))
+        .cross_line(CrossLine::new(point(px(x), px(0.))).height(height))
+        .dots(values.iter().filter_map(|value| {
+            value.y.map(|y| {
+                Dot::new(point(px(x), px(y)))
+                    .size(px(8.))
+                    .stroke(value.color)
+                    .fill(cx.theme().background)
+            })
+        }))
+        .gap_1()
+        .child(div().text_xs().font_semibold().child(label))
+        .children(values.into_iter().map(|value| {
+            h_flex()
+                .gap_2()
+                .text_xs()
+                .child(div().size_2().rounded_full().bg(value.color))
+                .child(format_value(value.value))
+        }))
+        .into_any_element()
+}
+
+/// Format a tooltip or axis value, whole numbers without decimals.
+pub(crate) fn format_value(value: f64) -> SharedString {
+    if value.fract() == 0. {
+        format!("{:.0}", value).into()
+    } else {
+        format!("{:.2}", value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_value;
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(42.).as_ref(), "42");
+        assert_eq!(format_value(1.23456).as_ref(), "1.23");
+        assert_eq!(format_value(-0.5).as_ref(), "-0.50");
+    }
+}
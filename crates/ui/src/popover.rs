@@ -1,14 +1,19 @@
 use gpui::{
     AnyElement, App, Bounds, Context, Corner, DismissEvent, ElementId, EventEmitter, FocusHandle,
     Focusable, InteractiveElement as _, IntoElement, KeyBinding, MouseButton, ParentElement,
-    Pixels, Point, Render, RenderOnce, StyleRefinement, Styled, Subscription, Window, anchored,
-    deferred, div, prelude::FluentBuilder as _, px,
+    Pixels, Point, Render, RenderOnce, StatefulInteractiveElement as _, StyleRefinement, Styled,
+    Subscription, Task, Window, anchored, deferred, div, prelude::FluentBuilder as _, px,
 };
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 
-use crate::{ElementExt, Selectable, StyledExt as _, actions::Cancel, v_flex};
+use crate::{
+    ElementExt, Selectable, StyledExt as _, WindowExt as _, actions::Cancel,
+    scroll::ScrollableElement as _, v_flex,
+};
 
 const CONTEXT: &str = "Popover";
+const DEFAULT_OPEN_DELAY: Duration = Duration::from_millis(300);
+const DEFAULT_CLOSE_DELAY: Duration = Duration::from_millis(200);
 pub(crate) fn init(cx: &mut App) {
     cx.bind_keys([KeyBinding::new("escape", Cancel, Some(CONTEXT))])
 }
@@ -21,6 +26,7 @@ pub struct Popover {
     anchor: Corner,
     default_open: bool,
     open: Option<bool>,
+    reset_key: Option<ElementId>,
     tracked_focus_handle: Option<FocusHandle>,
     trigger: Option<Box<dyn FnOnce(bool, &Window, &App) -> AnyElement + 'static>>,
     content: Option<
@@ -34,7 +40,11 @@ pub struct Popover {
     /// This is used for hotfix the trigger element style to support w_full.
     trigger_style: Option<StyleRefinement>,
     mouse_button: MouseButton,
+    trigger_on_hover: bool,
+    open_delay: Duration,
+    close_delay: Duration,
     appearance: bool,
+    max_height: Option<Pixels>,
     overlay_closable: bool,
     on_open_change: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
 }
@@ -52,10 +62,15 @@ impl Popover {
             tracked_focus_handle: None,
             children: vec![],
             mouse_button: MouseButton::Left,
+            trigger_on_hover: false,
+            open_delay: DEFAULT_OPEN_DELAY,
+            close_delay: DEFAULT_CLOSE_DELAY,
             appearance: true,
+            max_height: None,
             overlay_closable: true,
             default_open: false,
             open: None,
+            reset_key: None,
             on_open_change: None,
         }
     }
@@ -72,6 +87,29 @@ impl Popover {
         self
     }
 
+    /// Set whether to open the popover when hovering the trigger, default is `false`.
+    ///
+    /// The popover will close when the pointer leaves both the trigger and the content.
+    /// In this mode, the `mouse_button` click trigger is disabled.
+    pub fn trigger_on_hover(mut self, trigger_on_hover: bool) -> Self {
+        self.trigger_on_hover = trigger_on_hover;
+        self
+    }
+
+    /// Set the delay to open the popover on hover, default is 300ms.
+    pub fn open_delay(mut self, delay: Duration) -> Self {
+        self.open_delay = delay;
+        self
+    }
+
+    /// Set the delay to close the popover after the pointer leaves, default is 200ms.
+    ///
+    /// This allows the pointer to move from the trigger to the content.
+    pub fn close_delay(mut self, delay: Duration) -> Self {
+        self.close_delay = delay;
+        self
+    }
+
     /// Set the trigger element of the popover.
     pub fn trigger<T>(mut self, trigger: T) -> Self
     where
@@ -104,6 +142,15 @@ impl Popover {
         self
     }
 
+    /// Set a key to reset the internal state (e.g. open) of the popover when it changes.
+    ///
+    /// This is useful when the popover `id` is reused for different content,
+    /// e.g. in a recycled table row, use the row's data id as the reset key.
+    pub fn reset_key(mut self, key: impl Into<ElementId>) -> Self {
+        self.reset_key = Some(key.into());
+        self
+    }
+
     /// Add a callback to be called when the open state changes.
     ///
     /// The first `&bool` parameter is the **new open state**.
@@ -123,6 +170,12 @@ impl Popover {
         self
     }
 
+    /// Set the max height of the popover, the content will be scrollable if exceeded.
+    pub fn max_height(mut self, max_height: impl Into<Pixels>) -> Self {
+        self.max_height = Some(max_height.into());
+        self
+    }
+
     /// Set whether clicking outside the popover will dismiss it, default is `true`.
     pub fn overlay_closable(mut self, closable: bool) -> Self {
         self.overlay_closable = closable;
@@ -195,6 +248,8 @@ pub struct PopoverState {
     trigger_bounds: Option<Bounds<Pixels>>,
     open: bool,
     on_open_change: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
+    /// The pending delayed open or close in hover mode, dropped to cancel.
+    _hover_task: Option<Task<()>>,
 
     _dismiss_subscription: Option<Subscription>,
 }
@@ -207,6 +262,7 @@ impl PopoverState {
             trigger_bounds: None,
             open: default_open,
             on_open_change: None,
+            _hover_task: None,
             _dismiss_subscription: None,
         }
     }
@@ -230,6 +286,34 @@ impl PopoverState {
         }
     }
 
+    /// Open or close the popover after the delay, replacing the pending one.
+    fn set_open_after(
+        &mut self,
+        open: bool,
+        delay: Duration,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.open == open {
+            // e.g. the pointer re-enters before the close delay fires.
+            self._hover_task = None;
+            return;
+        }
+
+        self._hover_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            _ = this.update_in(cx, |this, window, cx| {
+                this._hover_task = None;
+                if open {
+                    this.show(window, cx);
+                } else {
+                    this.dismiss(window, cx);
+                }
+                window.refresh();
+            });
+        }));
+    }
+
     fn toggle_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.open = !self.open;
         if self.open {
@@ -285,9 +369,10 @@ impl RenderOnce for Popover {
         let force_open = self.open;
         let default_open = self.default_open;
         let tracked_focus_handle = self.tracked_focus_handle.clone();
-        let state = window.use_keyed_state(self.id.clone(), cx, |_, cx| {
-            PopoverState::new(default_open, cx)
-        });
+        let state =
+            window.use_keyed_state_with_reset(self.id.clone(), self.reset_key.clone(), cx, |_, cx| {
+                PopoverState::new(default_open, cx)
+            });
 
         state.update(cx, |state, _| {
             if let Some(tracked_focus_handle) = tracked_focus_handle {
@@ -308,22 +393,36 @@ impl RenderOnce for Popover {
         };
 
         let parent_view_id = window.current_view();
+        let trigger_on_hover = self.trigger_on_hover;
+        let on_hover = {
+            let state = state.clone();
+            let (open_delay, close_delay) = (self.open_delay, self.close_delay);
+            move |hovered: &bool, window: &mut Window, cx: &mut App| {
+                let delay = if *hovered { open_delay } else { close_delay };
+                state.update(cx, |state, cx| {
+                    state.set_open_after(*hovered, delay, window, cx);
+                });
+            }
+        };
 
         let el = div()
             .id(self.id)
             .child((trigger)(open, window, cx))
-            .on_mouse_up(self.mouse_button, {
-                let state = state.clone();
-                move |_, window, cx| {
-                    cx.stop_propagation();
-                    state.update(cx, |state, cx| {
-                        // We force set open to false to toggle it correctly.
-                        // Because if the mouse down out will toggle open first.
-                        state.open = open;
-                        state.toggle_open(window, cx);
-                    });
-                    cx.notify(parent_view_id);
-                }
+            .when(trigger_on_hover, |this| this.on_hover(on_hover.clone()))
+            .when(!trigger_on_hover, |this| {
+                this.on_mouse_up(self.mouse_button, {
+                    let state = state.clone();
+                    move |_, window, cx| {
+                        cx.stop_propagation();
+                        state.update(cx, |state, cx| {
+                            // We force set open to false to toggle it correctly.
+                            // Because if the mouse down out will toggle open first.
+                            state.open = open;
+                            state.toggle_open(window, cx);
+                        });
+                        cx.notify(parent_view_id);
+                    }
+                })
             })
             .on_prepaint({
                 let state = state.clone();
@@ -338,6 +437,11 @@ impl RenderOnce for Popover {
             return el;
         }
 
+        let content = self
+            .content
+            .map(|content| state.update(cx, |state, cx| (content)(state, window, cx)));
+        let children = content.into_iter().chain(self.children);
+
         el.child(
             deferred(
                 anchored()
@@ -355,17 +459,23 @@ impl RenderOnce for Popover {
                             .size_full()
                             .occlude()
                             .tab_group()
+                            // Keep open while the pointer is on the content.
+                            .when(trigger_on_hover, |this| this.on_hover(on_hover))
                             .when(self.appearance, |this| this.popover_style(cx).p_3())
                             .map(|this| match self.anchor {
                                 Corner::TopLeft | Corner::TopRight => this.top_1(),
                                 Corner::BottomLeft | Corner::BottomRight => this.bottom_1(),
                             })
-                            .when_some(self.content, |this, content| {
-                                this.child(
-                                    state.update(cx, |state, cx| (content)(state, window, cx)),
-                                )
+                            .map(|this| match self.max_height {
+                                Some(max_height) => this.max_h(max_height).child(
+                                    v_flex()
+                                        .id("scrollable-content")
+                                        .min_h_0()
+                                        .children(children)
+                                        .overflow_y_scrollbar(),
+                                ),
+                                None => this.children(children),
                             })
-                            .children(self.children)
                             .when(self.overlay_closable, |this| {
                                 this.on_mouse_up_out(MouseButton::Left, {
                                     let state = state.clone();
@@ -1,8 +1,8 @@
-use crate::{ActiveTheme, StyledExt};
+use crate::{ActiveTheme, StyledExt, WindowExt as _};
 use gpui::{
-    Animation, AnimationExt as _, App, ElementId, Hsla, InteractiveElement as _, IntoElement,
-    ParentElement, RenderOnce, StyleRefinement, Styled, Window, div, prelude::FluentBuilder, px,
-    relative,
+    Animation, AnimationExt as _, App, Div, ElementId, Hsla, InteractiveElement as _, IntoElement,
+    ParentElement, RenderOnce, SharedString, StyleRefinement, Styled, Window, black, div,
+    prelude::FluentBuilder, px, relative, white,
 };
 use std::time::Duration;
 
@@ -13,6 +13,9 @@ pub struct Progress {
     style: StyleRefinement,
     color: Option<Hsla>,
     value: f32,
+    label: Option<SharedString>,
+    show_percentage: bool,
+    reset_key: Option<ElementId>,
 }
 
 impl Progress {
@@ -22,6 +25,9 @@ impl Progress {
             id: id.into(),
             value: Default::default(),
             color: None,
+            label: None,
+            show_percentage: false,
+            reset_key: None,
             style: StyleRefinement::default().h(px(8.)).rounded(px(4.)),
         }
     }
@@ -39,6 +45,62 @@ impl Progress {
         self.value = value.clamp(0., 100.);
         self
     }
+
+    /// Set a label to display centered over the progress bar.
+    ///
+    /// The height is increased to 16px to fit the text, set `h` after this to override.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self.h(px(16.))
+    }
+
+    /// Set whether to display the percentage value centered over the progress bar,
+    /// after the label if any, default is `false`.
+    ///
+    /// The height is increased to 16px to fit the text, set `h` after this to override.
+    pub fn show_percentage(mut self, show_percentage: bool) -> Self {
+        self.show_percentage = show_percentage;
+        if show_percentage { self.h(px(16.)) } else { self }
+    }
+
+    /// Set a key to reset the animated value of the progress bar when it changes.
+    ///
+    /// The previous value is kept by the `id` to animate from it, use this when the `id`
+    /// is reused for different content, e.g. in a recycled table row, then the bar shows
+    /// the new value without animating from the stale one.
+    pub fn reset_key(mut self, key: impl Into<ElementId>) -> Self {
+        self.reset_key = Some(key.into());
+        self
+    }
+
+    fn text(
+        label: Option<&SharedString>,
+        show_percentage: bool,
+        value: f32,
+    ) -> Option<SharedString> {
+        match (label, show_percentage) {
+            (Some(label), true) => Some(format!("{} {:.1}%", label, value).into()),
+            (Some(label), false) => Some(label.clone()),
+            (None, true) => Some(format!("{:.1}%", value).into()),
+            (None, false) => None,
+        }
+    }
+}
+
+/// The text over the progress bar, centered in the given width.
+fn text_layer(text: SharedString, color: Hsla) -> Div {
+    div()
+        .absolute()
+        .top_0()
+        .left_0()
+        .h_full()
+        .flex()
+        .items_center()
+        .justify_center()
+        .text_xs()
+        .whitespace_nowrap()
+        .text_color(color)
+        .child(text)
 }
 
 impl Styled for Progress {
@@ -60,65 +122,72 @@ impl RenderOnce for Progress {
         let color = self.color.unwrap_or(cx.theme().progress_bar);
         let value = self.value;
 
-        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| ProgressState { value });
+        let state =
+            window.use_keyed_state_with_reset(self.id.clone(), self.reset_key.clone(), cx, |_, _| {
+                ProgressState { value }
+            });
         let prev_value = state.read(cx).value;
 
-        div()
-            .id(self.id)
-            .w_full()
-            .relative()
-            .rounded_full()
-            .refine_style(&self.style)
-            .bg(color.opacity(0.2))
+        // The text is drawn twice, over the unfilled and clipped in the filled portion,
+        // so it is readable against both.
+        let label = self.label;
+        let show_percentage = self.show_percentage;
+        let text_color = cx.theme().foreground;
+        let filled_text_color = if color.l > 0.6 { black() } else { white() };
+        let render_bar = move |this: Div, current_value: f32| {
+            let ratio = (current_value / 100.).clamp(0., 1.);
+            let text = Progress::text(label.as_ref(), show_percentage, current_value);
+
+            this.when_some(text.clone(), |this, text| {
+                this.child(text_layer(text, text_color).w_full())
+            })
             .child(
                 div()
                     .absolute()
                     .top_0()
                     .left_0()
                     .h_full()
+                    .overflow_hidden()
                     .bg(color)
                     .refine_style(&inner_style)
-                    .map(|this| match value {
-                        v if v >= 100. => this,
-                        _ => this.rounded_r_none(),
-                    })
-                    .map(|this| {
-                        if prev_value != value {
-                            // Animate from prev_value to value
-                            let duration = Duration::from_secs_f64(0.15);
-                            cx.spawn({
-                                let state = state.clone();
-                                async move |cx| {
-                                    cx.background_executor().timer(duration).await;
-                                    _ = state.update(cx, |this, _| this.value = value);
-                                }
-                            })
-                            .detach();
-
-                            this.with_animation(
-                                "progress-animation",
-                                Animation::new(duration),
-                                move |this, delta| {
-                                    let current_value =
-                                        prev_value + (value - prev_value) * delta;
-                                    let relative_w = relative(match current_value {
-                                        v if v < 0. => 0.,
-                                        v if v > 100. => 1.,
-                                        v => v / 100.,
-                                    });
-                                    this.w(relative_w)
-                                },
-                            )
-                            .into_any_element()
-                        } else {
-                            let relative_w = relative(match value {
-                                v if v < 0. => 0.,
-                                v if v > 100. => 1.,
-                                v => v / 100.,
-                            });
-                            this.w(relative_w).into_any_element()
-                        }
+                    .when(ratio < 1., |this| this.rounded_r_none())
+                    .w(relative(ratio))
+                    .when_some(text.filter(|_| ratio > 0.), |this, text| {
+                        // Full width of the bar, relative to the filled width.
+                        this.child(text_layer(text, filled_text_color).w(relative(1. / ratio)))
                     }),
             )
+        };
+
+        let bar = div().absolute().top_0().left_0().size_full();
+
+        div()
+            .id(self.id)
+            .w_full()
+            .relative()
+            .rounded_full()
+            .refine_style(&self.style)
+            .bg(color.opacity(0.2))
+            .child(if prev_value != value {
+                // Animate from prev_value to value
+                let duration = Duration::from_secs_f64(0.15);
+                cx.spawn({
+                    let state = state.clone();
+                    async move |cx| {
+                        cx.background_executor().timer(duration).await;
+                        _ = state.update(cx, |this, _| this.value = value);
+                    }
+                })
+                .detach();
+
+                bar.with_animation(
+                    "progress-animation",
+                    Animation::new(duration),
+                    move |this, delta| render_bar(this, prev_value + (value - prev_value) * delta),
+                )
+                .into_any_element()
+            } else {
+                render_bar(bar, value).into_any_element()
+            })
     }
 }
@@ -0,0 +1,140 @@
+use gpui::{
+    AnyElement, App, ElementId, InteractiveElement as _, IntoElement, ParentElement, RenderOnce,
+    Pixels, ScrollHandle, StatefulInteractiveElement as _, StyleRefinement, Styled, Window, div,
+    prelude::FluentBuilder as _,
+};
+
+use crate::StyledExt as _;
+
+use super::{Scrollbar, ScrollbarAxis, ScrollbarShow};
+
+/// A container that wraps any content with the themed scrollbars.
+///
+/// ```ignore
+/// ScrollView::vertical("details")
+///     .h(px(300.))
+///     .scrollbar_show(ScrollbarShow::Hover)
+///     .scrollbar_thickness(px(6.))
+///     .child(content)
+/// ```
+///
+/// See also [`super::ScrollableElement`] for adding scrollbars to an existing `Div`.
+#[derive(IntoElement)]
+pub struct ScrollView {
+    id: ElementId,
+    style: StyleRefinement,
+    axis: ScrollbarAxis,
+    scrollbar_show: Option<ScrollbarShow>,
+    scrollbar_thickness: Option<Pixels>,
+    scroll_handle: Option<ScrollHandle>,
+    children: Vec<AnyElement>,
+}
+
+impl ScrollView {
+    /// Create a new [`ScrollView`] that scrolls in both directions.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            axis: ScrollbarAxis::Both,
+            scrollbar_show: None,
+            scrollbar_thickness: None,
+            scroll_handle: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Create a new [`ScrollView`] that only scrolls vertically.
+    pub fn vertical(id: impl Into<ElementId>) -> Self {
+        Self::new(id).axis(ScrollbarAxis::Vertical)
+    }
+
+    /// Create a new [`ScrollView`] that only scrolls horizontally.
+    pub fn horizontal(id: impl Into<ElementId>) -> Self {
+        Self::new(id).axis(ScrollbarAxis::Horizontal)
+    }
+
+    /// Set the scroll axis, default is [`ScrollbarAxis::Both`].
+    pub fn axis(mut self, axis: impl Into<ScrollbarAxis>) -> Self {
+        self.axis = axis.into();
+        self
+    }
+
+    /// Set the scrollbar show mode, if not set use the `cx.theme().scrollbar_show`.
+    pub fn scrollbar_show(mut self, scrollbar_show: ScrollbarShow) -> Self {
+        self.scrollbar_show = Some(scrollbar_show);
+        self
+    }
+
+    /// Set the thickness of the scrollbar thumb, see [`Scrollbar::thickness`].
+    pub fn scrollbar_thickness(mut self, thickness: impl Into<Pixels>) -> Self {
+        self.scrollbar_thickness = Some(thickness.into());
+        self
+    }
+
+    /// Use an external [`ScrollHandle`] to read or control the scroll offset.
+    ///
+    /// If not set, an internal handle is kept per `id`.
+    pub fn track_scroll(mut self, scroll_handle: &ScrollHandle) -> Self {
+        self.scroll_handle = Some(scroll_handle.clone());
+        self
+    }
+}
+
+impl Styled for ScrollView {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl ParentElement for ScrollView {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl RenderOnce for ScrollView {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let scroll_handle = match self.scroll_handle {
+            Some(scroll_handle) => scroll_handle,
+            None => window
+                .use_keyed_state(self.id.clone(), cx, |_, _| ScrollHandle::default())
+                .read(cx)
+                .clone(),
+        };
+        let is_inspector_picking = window.is_inspector_picking(cx);
+
+        div()
+            .id(self.id)
+            .size_full()
+            .relative()
+            .refine_style(&self.style)
+            .child(
+                div()
+                    .id("scroll-area")
+                    .size_full()
+                    .track_scroll(&scroll_handle)
+                    .map(|this| match self.axis {
+                        ScrollbarAxis::Vertical => this.flex().flex_col().overflow_y_scroll(),
+                        ScrollbarAxis::Horizontal => this.flex().flex_row().overflow_x_scroll(),
+                        ScrollbarAxis::Both => this.overflow_scroll(),
+                    })
+                    .children(self.children),
+            )
+            .when(!is_inspector_picking, |this| {
+                this.child(
+                    div().absolute().top_0().left_0().right_0().bottom_0().child(
+                        Scrollbar::new(&scroll_handle)
+                            .id("scrollbar")
+                            .axis(self.axis)
+                            .when_some(self.scrollbar_show, |this, show| {
+                                this.scrollbar_show(show)
+                            })
+                            .when_some(self.scrollbar_thickness, |this, thickness| {
+                                this.thickness(thickness)
+                            }),
+                    ),
+                )
+            })
+    }
+}
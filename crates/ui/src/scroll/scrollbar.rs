@@ -21,12 +21,11 @@ use serde::{Deserialize, Serialize};
 const WIDTH: Pixels = px(4. * 2. + 8.);
 const MIN_THUMB_SIZE: f32 = 48.;
 
-const THUMB_WIDTH: Pixels = px(6.);
-const THUMB_RADIUS: Pixels = px(6. / 2.);
+/// The width of the idle thumb in [`ScrollbarShow::Scrolling`] mode, relative to the active thumb.
+const THUMB_IDLE_RATIO: f32 = 6. / 8.;
 const THUMB_INSET: Pixels = px(4.);
 
 const THUMB_ACTIVE_WIDTH: Pixels = px(8.);
-const THUMB_ACTIVE_RADIUS: Pixels = px(8. / 2.);
 const THUMB_ACTIVE_INSET: Pixels = px(4.);
 
 const FADE_OUT_DURATION: f32 = 3.0;
@@ -313,6 +312,8 @@ pub struct Scrollbar {
     scrollbar_show: Option<ScrollbarShow>,
     scroll_handle: Rc<dyn ScrollbarHandle>,
     scroll_size: Option<Size<Pixels>>,
+    /// The width of the active thumb.
+    thickness: Pixels,
     /// Maximum frames per second for scrolling by drag. Default is 120 FPS.
     ///
     /// This is used to limit the update rate of the scrollbar when it is
@@ -334,6 +335,7 @@ impl Scrollbar {
             scroll_handle: Rc::new(scroll_handle.clone()),
             max_fps: 120,
             scroll_size: None,
+            thickness: THUMB_ACTIVE_WIDTH,
         }
     }
 
@@ -377,6 +379,14 @@ impl Scrollbar {
         self
     }
 
+    /// Set the thickness of the thumb when hovered or dragged, default is 8px.
+    ///
+    /// The idle thumb is slightly thinner, and the bar adds a 4px inset on both sides.
+    pub fn thickness(mut self, thickness: impl Into<Pixels>) -> Self {
+        self.thickness = thickness.into();
+        self
+    }
+
     /// Set maximum frames per second for scrolling by drag. Default is 120 FPS.
     ///
     /// If you have very high CPU usage, consider reducing this value to improve performance.
@@ -387,51 +397,71 @@ impl Scrollbar {
         self
     }
 
-    // Get the width of the scrollbar.
+    // Get the default width of the scrollbar, see [`Self::thickness`].
     pub(crate) const fn width() -> Pixels {
         WIDTH
     }
 
-    fn style_for_active(cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
+    /// Returns the width of the bar, the thumb with the insets.
+    fn bar_width(&self) -> Pixels {
+        THUMB_ACTIVE_INSET * 2. + self.thickness
+    }
+
+    /// Returns the width, inset and radius of the hovered or dragged thumb.
+    fn active_thumb(&self) -> (Pixels, Pixels, Pixels) {
+        (self.thickness, THUMB_ACTIVE_INSET, self.thickness / 2.)
+    }
+
+    /// Returns the width, inset and radius of the thumb that is not hovered.
+    fn idle_thumb(&self, cx: &App) -> (Pixels, Pixels, Pixels) {
+        let scrollbar_show = self.scrollbar_show.unwrap_or(cx.theme().scrollbar_show);
+        match scrollbar_show {
+            ScrollbarShow::Scrolling => {
+                let width = self.thickness * THUMB_IDLE_RATIO;
+                (width, THUMB_INSET, width / 2.)
+            }
+            _ => self.active_thumb(),
+        }
+    }
+
+    fn style_for_active(&self, cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
+        let (width, inset, radius) = self.active_thumb();
         (
             cx.theme().scrollbar_thumb_hover,
             cx.theme().scrollbar,
             cx.theme().border,
-            THUMB_ACTIVE_WIDTH,
-            THUMB_ACTIVE_INSET,
-            THUMB_ACTIVE_RADIUS,
+            width,
+            inset,
+            radius,
         )
     }
 
-    fn style_for_hovered_thumb(cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
+    fn style_for_hovered_thumb(&self, cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
+        let (width, inset, radius) = self.active_thumb();
         (
             cx.theme().scrollbar_thumb_hover,
             cx.theme().scrollbar,
             cx.theme().border,
-            THUMB_ACTIVE_WIDTH,
-            THUMB_ACTIVE_INSET,
-            THUMB_ACTIVE_RADIUS,
+            width,
+            inset,
+            radius,
         )
     }
 
-    fn style_for_hovered_bar(cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
+    fn style_for_hovered_bar(&self, cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
+        let (width, inset, radius) = self.active_thumb();
         (
             cx.theme().scrollbar_thumb,
             cx.theme().scrollbar,
             gpui::transparent_black(),
-            THUMB_ACTIVE_WIDTH,
-            THUMB_ACTIVE_INSET,
-            THUMB_ACTIVE_RADIUS,
+            width,
+            inset,
+            radius,
         )
     }
 
     fn style_for_normal(&self, cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
-        let scrollbar_show = self.scrollbar_show.unwrap_or(cx.theme().scrollbar_show);
-        let (width, inset, radius) = match scrollbar_show {
-            ScrollbarShow::Scrolling => (THUMB_WIDTH, THUMB_INSET, THUMB_RADIUS),
-            _ => (THUMB_ACTIVE_WIDTH, THUMB_ACTIVE_INSET, THUMB_ACTIVE_RADIUS),
-        };
-
+        let (width, inset, radius) = self.idle_thumb(cx);
         (
             cx.theme().scrollbar_thumb,
             cx.theme().scrollbar,
@@ -443,12 +473,7 @@ impl Scrollbar {
     }
 
     fn style_for_idle(&self, cx: &App) -> (Hsla, Hsla, Hsla, Pixels, Pixels, Pixels) {
-        let scrollbar_show = self.scrollbar_show.unwrap_or(cx.theme().scrollbar_show);
-        let (width, inset, radius) = match scrollbar_show {
-            ScrollbarShow::Scrolling => (THUMB_WIDTH, THUMB_INSET, THUMB_RADIUS),
-            _ => (THUMB_ACTIVE_WIDTH, THUMB_ACTIVE_INSET, THUMB_ACTIVE_RADIUS),
-        };
-
+        let (width, inset, radius) = self.idle_thumb(cx);
         (
             gpui::transparent_black(),
             gpui::transparent_black(),
@@ -542,6 +567,7 @@ impl Element for Scrollbar {
 
         let mut states = vec![];
         let mut has_both = self.axis.is_both();
+        let bar_width = self.bar_width();
         let scroll_size = self
             .scroll_size
             .unwrap_or(self.scroll_handle.content_size());
@@ -564,7 +590,7 @@ impl Element for Scrollbar {
 
             // The horizontal scrollbar is set avoid overlapping with the vertical scrollbar, if the vertical scrollbar is visible.
             let margin_end = if has_both && !is_vertical {
-                WIDTH
+                bar_width
             } else {
                 px(0.)
             };
@@ -583,23 +609,23 @@ impl Element for Scrollbar {
 
             let bounds = Bounds {
                 origin: if is_vertical {
-                    point(hitbox.origin.x + hitbox.size.width - WIDTH, hitbox.origin.y)
+                    point(hitbox.origin.x + hitbox.size.width - bar_width, hitbox.origin.y)
                 } else {
                     point(
                         hitbox.origin.x,
-                        hitbox.origin.y + hitbox.size.height - WIDTH,
+                        hitbox.origin.y + hitbox.size.height - bar_width,
                     )
                 },
                 size: gpui::Size {
                     width: if is_vertical {
-                        WIDTH
+                        bar_width
                     } else {
                         hitbox.size.width
                     },
                     height: if is_vertical {
                         hitbox.size.height
                     } else {
-                        WIDTH
+                        bar_width
                     },
                 },
             };
@@ -613,20 +639,20 @@ impl Element for Scrollbar {
 
             let (thumb_bg, bar_bg, bar_border, thumb_width, inset, radius) =
                 if state.get().dragged_axis == Some(axis) {
-                    Self::style_for_active(cx)
+                    self.style_for_active(cx)
                 } else if is_hover_to_show && (is_hovered_on_bar || is_hovered_on_thumb) {
                     if is_hovered_on_thumb {
-                        Self::style_for_hovered_thumb(cx)
+                        self.style_for_hovered_thumb(cx)
                     } else {
-                        Self::style_for_hovered_bar(cx)
+                        self.style_for_hovered_bar(cx)
                     }
                 } else if is_offset_changed {
                     self.style_for_normal(cx)
                 } else if is_always_to_show {
                     if is_hovered_on_thumb {
-                        Self::style_for_hovered_thumb(cx)
+                        self.style_for_hovered_thumb(cx)
                     } else {
-                        Self::style_for_hovered_bar(cx)
+                        self.style_for_hovered_bar(cx)
                     }
                 } else {
                     let mut idle_state = self.style_for_idle(cx);
@@ -636,9 +662,9 @@ impl Element for Scrollbar {
                         if is_hovered_on_bar {
                             state.set(state.get().with_last_scroll_time(Some(Instant::now())));
                             idle_state = if is_hovered_on_thumb {
-                                Self::style_for_hovered_thumb(cx)
+                                self.style_for_hovered_thumb(cx)
                             } else {
-                                Self::style_for_hovered_bar(cx)
+                                self.style_for_hovered_bar(cx)
                             };
                         } else if elapsed < FADE_OUT_DELAY {
                             idle_state.0 = cx.theme().scrollbar_thumb;
@@ -673,13 +699,13 @@ impl Element for Scrollbar {
                 Bounds::from_corner_and_size(
                     Corner::TopRight,
                     bounds.top_right() + point(-inset, inset + thumb_start),
-                    size(WIDTH, thumb_length),
+                    size(bar_width, thumb_length),
                 )
             } else {
                 Bounds::from_corner_and_size(
                     Corner::BottomLeft,
                     bounds.bottom_left() + point(inset + thumb_start, -inset),
-                    size(thumb_length, WIDTH),
+                    size(thumb_length, bar_width),
                 )
             };
 
@@ -1,16 +1,40 @@
+use std::rc::Rc;
+
 use gpui::{
-    prelude::FluentBuilder as _, Div, InteractiveElement, IntoElement, ParentElement, RenderOnce,
-    Styled,
+    div, prelude::FluentBuilder as _, AnyElement, App, ClickEvent, Div, InteractiveElement,
+    IntoElement, MouseButton, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Styled, Window,
 };
 
-use crate::{h_flex, menu::DropdownMenu, ActiveTheme as _, Collapsible, Selectable};
+use crate::{
+    avatar::Avatar, h_flex, menu::DropdownMenu, v_flex, ActiveTheme as _, Collapsible, Selectable,
+    Sizable as _,
+};
 
 /// Footer for the [`super::Sidebar`].
+///
+/// Either add any children, or use the profile row of an [`Avatar`], a title,
+/// a description and a trailing action, that collapses to the avatar
+/// when the sidebar is collapsed.
+///
+/// ```ignore
+/// SidebarFooter::new()
+///     .avatar(Avatar::new().name("Jane Doe"))
+///     .title("Jane Doe")
+///     .description("jane@example.com")
+///     .action(Button::new("settings").ghost().icon(IconName::Settings))
+///     .on_click(|_, window, cx| open_profile(window, cx))
+/// ```
 #[derive(IntoElement)]
 pub struct SidebarFooter {
     base: Div,
     selected: bool,
     collapsed: bool,
+    avatar: Option<Avatar>,
+    title: Option<SharedString>,
+    description: Option<SharedString>,
+    action: Option<AnyElement>,
+    on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
 }
 
 impl SidebarFooter {
@@ -20,8 +44,48 @@ impl SidebarFooter {
             base: h_flex().gap_2().w_full(),
             selected: false,
             collapsed: false,
+            avatar: None,
+            title: None,
+            description: None,
+            action: None,
+            on_click: None,
         }
     }
+
+    /// Set the avatar of the profile row, the only part visible when collapsed.
+    pub fn avatar(mut self, avatar: Avatar) -> Self {
+        self.avatar = Some(avatar);
+        self
+    }
+
+    /// Set the primary label of the profile row, e.g. the user name.
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the secondary muted label of the profile row, e.g. the email.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the trailing action of the profile row, e.g. a settings button.
+    ///
+    /// Clicking the action does not trigger [`SidebarFooter::on_click`].
+    pub fn action(mut self, action: impl IntoElement) -> Self {
+        self.action = Some(action.into_any_element());
+        self
+    }
+
+    /// Add a click handler to the whole footer.
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
 }
 
 impl Selectable for SidebarFooter {
@@ -68,6 +132,9 @@ impl DropdownMenu for SidebarFooter {}
 
 impl RenderOnce for SidebarFooter {
     fn render(self, _: &mut gpui::Window, cx: &mut gpui::App) -> impl gpui::IntoElement {
+        let collapsed = self.collapsed;
+        let has_labels = self.title.is_some() || self.description.is_some();
+
         h_flex()
             .id("sidebar-footer")
             .gap_2()
@@ -83,6 +150,42 @@ impl RenderOnce for SidebarFooter {
                 this.bg(cx.theme().sidebar_accent)
                     .text_color(cx.theme().sidebar_accent_foreground)
             })
+            .when(collapsed && self.avatar.is_some(), |this| this.justify_center())
+            .when_some(self.avatar, |this, avatar| this.child(avatar.small()))
+            .when(!collapsed && has_labels, |this| {
+                this.child(
+                    v_flex()
+                        .flex_1()
+                        .overflow_x_hidden()
+                        .when_some(self.title, |this, title| {
+                            this.child(div().text_sm().font_medium().truncate().child(title))
+                        })
+                        .when_some(self.description, |this, description| {
+                            this.child(
+                                div()
+                                    .text_xs()
+                                    .truncate()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(description),
+                            )
+                        }),
+                )
+            })
             .child(self.base)
+            .when(!collapsed, |this| {
+                this.when_some(self.action, |this, action| {
+                    this.child(
+                        div()
+                            .flex_shrink_0()
+                            // Avoid triggering the click of the footer.
+                            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                            .child(action),
+                    )
+                })
+            })
+            .when_some(self.on_click, |this, on_click| {
+                this.cursor_pointer()
+                    .on_click(move |ev, window, cx| on_click(ev, window, cx))
+            })
     }
 }
@@ -1,7 +1,7 @@
-use crate::{ActiveTheme, Collapsible, h_flex, v_flex};
+use crate::{ActiveTheme, Collapsible, Icon, IconName, Sizable as _, h_flex, v_flex};
 use gpui::{
-    App, Div, IntoElement, ParentElement, RenderOnce, SharedString, Styled as _, Window, div,
-    prelude::FluentBuilder as _,
+    App, Div, InteractiveElement as _, IntoElement, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Styled as _, Window, div, prelude::FluentBuilder as _,
 };
 
 /// A group of items in the [`super::Sidebar`].
@@ -10,6 +10,8 @@ pub struct SidebarGroup<E: Collapsible + IntoElement + 'static> {
     base: Div,
     label: SharedString,
     collapsed: bool,
+    collapsible: bool,
+    default_open: bool,
     children: Vec<E>,
 }
 
@@ -20,10 +22,29 @@ impl<E: Collapsible + IntoElement> SidebarGroup<E> {
             base: div().gap_2().flex_col(),
             label: label.into(),
             collapsed: false,
+            collapsible: false,
+            default_open: true,
             children: Vec::new(),
         }
     }
 
+    /// Set whether clicking the group label shows or hides the children, default is `false`.
+    ///
+    /// The open state is kept by the label, and the children are always visible
+    /// when the sidebar is collapsed, since the label is hidden.
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    /// Set the default open state of a collapsible group, default is `true`.
+    ///
+    /// This only used on initial render, the internal state will be used afterwards.
+    pub fn default_open(mut self, open: bool) -> Self {
+        self.default_open = open;
+        self
+    }
+
     /// Add a child to the sidebar group, the child should implement [`Collapsible`] + [`IntoElement`].
     pub fn child(mut self, child: E) -> Self {
         self.children.push(child);
@@ -51,27 +72,57 @@ impl<E: Collapsible + IntoElement> Collapsible for SidebarGroup<E> {
 }
 
 impl<E: Collapsible + IntoElement> RenderOnce for SidebarGroup<E> {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let open_state = self.collapsible.then(|| {
+            let default_open = self.default_open;
+            window.use_keyed_state(
+                SharedString::from(format!("sidebar-group-{}", self.label)),
+                cx,
+                |_, _| default_open,
+            )
+        });
+        let is_open = open_state.as_ref().is_none_or(|state| *state.read(cx));
+        let show_children = self.collapsed || is_open;
+
         v_flex()
             .relative()
             .when(!self.collapsed, |this| {
                 this.child(
                     h_flex()
+                        .id("label")
                         .flex_shrink_0()
+                        .justify_between()
                         .px_2()
                         .rounded(cx.theme().radius)
                         .text_xs()
                         .text_color(cx.theme().sidebar_foreground.opacity(0.7))
                         .h_8()
-                        .child(self.label),
+                        .child(self.label)
+                        .when_some(open_state, |this, open_state| {
+                            this.cursor_pointer()
+                                .hover(|this| this.bg(cx.theme().sidebar_accent.opacity(0.8)))
+                                .child(
+                                    Icon::new(IconName::ChevronRight)
+                                        .xsmall()
+                                        .rotate_to("caret-rotation", if is_open { 90. } else { 0. }),
+                                )
+                                .on_click(move |_, _, cx| {
+                                    open_state.update(cx, |is_open, cx| {
+                                        *is_open = !*is_open;
+                                        cx.notify();
+                                    })
+                                })
+                        }),
+                )
+            })
+            .when(show_children, |this| {
+                this.child(
+                    self.base.children(
+                        self.children
+                            .into_iter()
+                            .map(|child| child.collapsed(self.collapsed)),
+                    ),
                 )
             })
-            .child(
-                self.base.children(
-                    self.children
-                        .into_iter()
-                        .map(|child| child.collapsed(self.collapsed)),
-                ),
-            )
     }
 }
@@ -1,9 +1,13 @@
 use gpui::{
-    div, prelude::FluentBuilder as _, AnyElement, Div, InteractiveElement, IntoElement,
+    div, prelude::FluentBuilder as _, AnyElement, Div, Entity, InteractiveElement, IntoElement,
     ParentElement, RenderOnce, StyleRefinement, Styled,
 };
 
-use crate::{menu::DropdownMenu, ActiveTheme as _, Collapsible, Selectable, StyledExt};
+use crate::{
+    input::{Input, InputState},
+    menu::DropdownMenu,
+    ActiveTheme as _, Collapsible, Icon, IconName, Selectable, Sizable as _, StyledExt,
+};
 
 /// Header for the [`super::Sidebar`]
 #[derive(IntoElement)]
@@ -98,3 +102,53 @@ impl RenderOnce for SidebarHeader {
             .children(self.children)
     }
 }
+
+/// A search box for the top of the [`super::Sidebar`], hidden when the sidebar is collapsed.
+///
+/// Subscribe to the [`crate::input::InputEvent::Change`] of the `state` for the query changes,
+/// and filter the menu by [`super::SidebarMenu::filter`].
+///
+/// ```ignore
+/// Sidebar::left()
+///     .header(SidebarSearch::new(&self.search_state))
+///     .child(SidebarMenu::new().filter(&self.search_state.read(cx).value()).children(items))
+/// ```
+#[derive(IntoElement)]
+pub struct SidebarSearch {
+    state: Entity<InputState>,
+    collapsed: bool,
+}
+
+impl SidebarSearch {
+    /// Create a new [`SidebarSearch`] with the input state.
+    pub fn new(state: &Entity<InputState>) -> Self {
+        Self {
+            state: state.clone(),
+            collapsed: false,
+        }
+    }
+}
+
+impl Collapsible for SidebarSearch {
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+}
+
+impl RenderOnce for SidebarSearch {
+    fn render(self, _: &mut gpui::Window, _: &mut gpui::App) -> impl gpui::IntoElement {
+        div().w_full().when(!self.collapsed, |this| {
+            this.child(
+                Input::new(&self.state)
+                    .small()
+                    .cleanable(true)
+                    .prefix(Icon::new(IconName::Search).small()),
+            )
+        })
+    }
+}
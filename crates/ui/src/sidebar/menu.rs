@@ -1,20 +1,58 @@
 use crate::{
+    actions::{Confirm, SelectDown, SelectLeft, SelectRight, SelectUp},
     button::{Button, ButtonVariants as _},
-    h_flex, v_flex, ActiveTheme as _, Collapsible, Icon, IconName, Sizable as _, StyledExt,
+    h_flex,
+    tooltip::Tooltip,
+    v_flex, ActiveTheme as _, Collapsible, ElementExt as _, FocusableExt as _, Icon, IconName,
+    Sizable as _, StyledExt, WindowExt as _,
 };
 use gpui::{
-    div, percentage, prelude::FluentBuilder as _, AnyElement, App, ClickEvent, ElementId,
-    InteractiveElement as _, IntoElement, ParentElement as _, RenderOnce, SharedString,
-    StatefulInteractiveElement as _, StyleRefinement, Styled, Window,
+    div, ease_in_out, prelude::FluentBuilder as _, px, Animation, AnimationExt as _, AnyElement,
+    App, ClickEvent, ElementId, Entity, FocusHandle, InteractiveElement as _, IntoElement,
+    KeyBinding, MouseButton, ParentElement as _, Pixels, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Task, Window,
 };
-use std::rc::Rc;
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+const SUBMENU_ANIMATION_DURATION: Duration = Duration::from_millis(150);
+const CONTEXT: &str = "SidebarMenu";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", SelectUp, Some(CONTEXT)),
+        KeyBinding::new("down", SelectDown, Some(CONTEXT)),
+        KeyBinding::new("left", SelectLeft, Some(CONTEXT)),
+        KeyBinding::new("right", SelectRight, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+    ]);
+}
+
+type SelectHandler = Rc<dyn Fn(&SharedString, &mut Window, &mut App)>;
 
 /// Menu for the [`super::Sidebar`]
+///
+/// When focused, use `up` / `down` to move between the items,
+/// `right` / `left` to expand / collapse the submenu, and `enter` to click the item.
+///
+/// Give the items a [`SidebarMenuItem::key`] to let the menu track the active item:
+///
+/// ```ignore
+/// SidebarMenu::new()
+///     .selected(self.page.clone())
+///     .on_select(cx.listener(|this, key: &SharedString, _, cx| this.open_page(key, cx)))
+///     .child(SidebarMenuItem::new("Processes").key("processes"))
+///     .child(SidebarMenuItem::new("Performance").key("performance"))
+/// ```
 #[derive(IntoElement)]
 pub struct SidebarMenu {
     style: StyleRefinement,
     collapsed: bool,
     items: Vec<SidebarMenuItem>,
+    selected: Option<SharedString>,
+    on_select: Option<SelectHandler>,
 }
 
 impl SidebarMenu {
@@ -24,9 +62,45 @@ impl SidebarMenu {
             style: StyleRefinement::default(),
             items: Vec::new(),
             collapsed: false,
+            selected: None,
+            on_select: None,
         }
     }
 
+    /// Keep the items whose label contains the `query`, case-insensitive.
+    ///
+    /// An item is also kept if any of its submenu items matches, with only the matched
+    /// submenu items, call this after adding the items. An empty query keeps all the items.
+    pub fn filter(mut self, query: &str) -> Self {
+        let query = query.trim().to_lowercase();
+        if !query.is_empty() {
+            self.items = self
+                .items
+                .into_iter()
+                .filter_map(|item| item.filter(&query))
+                .collect();
+        }
+        self
+    }
+
+    /// Mark the item with the `key` as active, and all the other items as inactive.
+    ///
+    /// This overrides [`SidebarMenuItem::active`] of the items, including the submenu items.
+    pub fn selected(mut self, key: impl Into<SharedString>) -> Self {
+        self.selected = Some(key.into());
+        self
+    }
+
+    /// Set a handler called with the [`SidebarMenuItem::key`] when an item with a key is clicked,
+    /// after the click handler of the item.
+    pub fn on_select(
+        mut self,
+        handler: impl Fn(&SharedString, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
+
     /// Add a [`SidebarMenuItem`] child menu item to the sidebar menu.
     ///
     /// See also [`SidebarMenu::children`].
@@ -62,31 +136,269 @@ impl Styled for SidebarMenu {
     }
 }
 
+/// The keyboard navigation state of [`SidebarMenu`].
+struct SidebarMenuState {
+    focus_handle: FocusHandle,
+    /// The focused item index, and the child index if a submenu item is focused.
+    focused: Option<(usize, Option<usize>)>,
+}
+
+/// An item that can be focused by keyboard, in display order.
+struct MenuEntry {
+    item_ix: usize,
+    child_ix: Option<usize>,
+    handler: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>,
+    /// The open state of the submenu, `None` if the item has no submenu.
+    open_state: Option<Entity<bool>>,
+}
+
+impl MenuEntry {
+    fn key(&self) -> (usize, Option<usize>) {
+        (self.item_ix, self.child_ix)
+    }
+}
+
+impl SidebarMenu {
+    /// Returns the enabled items in display order, including the items of the open submenus.
+    ///
+    /// Only the first level of submenu is supported.
+    fn entries(&self, window: &mut Window, cx: &mut App) -> Vec<MenuEntry> {
+        let mut entries = vec![];
+        for (item_ix, item) in self.items.iter().enumerate() {
+            let open_state =
+                (item.is_submenu() && !self.collapsed).then(|| item.open_state(window, cx));
+            let is_open = open_state.as_ref().is_some_and(|state| *state.read(cx));
+
+            if !item.disabled {
+                entries.push(MenuEntry {
+                    item_ix,
+                    child_ix: None,
+                    handler: item.handler.clone(),
+                    open_state,
+                });
+            }
+
+            if is_open {
+                for (child_ix, child) in item.children.iter().enumerate() {
+                    if !child.disabled {
+                        entries.push(MenuEntry {
+                            item_ix,
+                            child_ix: Some(child_ix),
+                            handler: child.handler.clone(),
+                            open_state: None,
+                        });
+                    }
+                }
+            }
+        }
+        entries
+    }
+}
+
+fn move_focus(
+    state: &Entity<SidebarMenuState>,
+    entries: &[MenuEntry],
+    delta: isize,
+    cx: &mut App,
+) {
+    if entries.is_empty() {
+        return;
+    }
+
+    let focused = state.read(cx).focused;
+    let ix = match entries.iter().position(|entry| Some(entry.key()) == focused) {
+        Some(ix) => (ix as isize + delta).rem_euclid(entries.len() as isize) as usize,
+        None if delta > 0 => 0,
+        None => entries.len() - 1,
+    };
+    let key = entries[ix].key();
+    state.update(cx, |state, cx| {
+        state.focused = Some(key);
+        cx.notify();
+    });
+}
+
+fn focused_entry<'a>(
+    state: &Entity<SidebarMenuState>,
+    entries: &'a [MenuEntry],
+    cx: &App,
+) -> Option<&'a MenuEntry> {
+    let focused = state.read(cx).focused?;
+    entries.iter().find(|entry| entry.key() == focused)
+}
+
+/// Apply the [`SidebarMenu::selected`] and [`SidebarMenu::on_select`] to the item and its submenu.
+fn apply_selection(
+    item: &mut SidebarMenuItem,
+    selected: Option<&SharedString>,
+    on_select: Option<&SelectHandler>,
+) {
+    if let Some(selected) = selected {
+        item.active = item.key.as_ref() == Some(selected);
+    }
+    if let (Some(key), Some(on_select)) = (item.key.clone(), on_select) {
+        let handler = item.handler.clone();
+        let on_select = on_select.clone();
+        item.handler = Rc::new(move |ev, window, cx| {
+            handler(ev, window, cx);
+            on_select(&key, window, cx);
+        });
+    }
+
+    for child in &mut item.children {
+        apply_selection(child, selected, on_select);
+    }
+}
+
 impl RenderOnce for SidebarMenu {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
-        v_flex().gap_2().refine_style(&self.style).children(
-            self.items
-                .into_iter()
-                .enumerate()
-                .map(|(ix, item)| item.id(ix).collapsed(self.collapsed)),
-        )
+    fn render(mut self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let collapsed = self.collapsed;
+        self.items = self
+            .items
+            .into_iter()
+            .enumerate()
+            .map(|(ix, item)| item.id(ix).collapsed(collapsed))
+            .collect();
+        for item in &mut self.items {
+            apply_selection(item, self.selected.as_ref(), self.on_select.as_ref());
+        }
+
+        let state = window.use_keyed_state("sidebar-menu", cx, |_, cx| SidebarMenuState {
+            focus_handle: cx.focus_handle(),
+            focused: None,
+        });
+        let focus_handle = state.read(cx).focus_handle.clone();
+        let entries = Rc::new(self.entries(window, cx));
+
+        if focus_handle.is_focused(window) {
+            if let Some(entry) = focused_entry(&state, &entries, cx) {
+                let item = self.items.get_mut(entry.item_ix);
+                let item = match entry.child_ix {
+                    Some(child_ix) => item.and_then(|item| item.children.get_mut(child_ix)),
+                    None => item,
+                };
+                if let Some(item) = item {
+                    item.focused = true;
+                }
+            }
+        }
+
+        v_flex()
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .on_mouse_down(MouseButton::Left, {
+                let state = state.clone();
+                let focus_handle = focus_handle.clone();
+                move |_, window, cx| {
+                    // Hide the keyboard focus ring when clicking by mouse.
+                    state.update(cx, |state, _| state.focused = None);
+                    focus_handle.focus(window, cx);
+                }
+            })
+            .on_action({
+                let state = state.clone();
+                let entries = entries.clone();
+                move |_: &SelectUp, _, cx| move_focus(&state, &entries, -1, cx)
+            })
+            .on_action({
+                let state = state.clone();
+                let entries = entries.clone();
+                move |_: &SelectDown, _, cx| move_focus(&state, &entries, 1, cx)
+            })
+            .on_action({
+                let state = state.clone();
+                let entries = entries.clone();
+                move |_: &SelectRight, _, cx| {
+                    let Some(open_state) =
+                        focused_entry(&state, &entries, cx).and_then(|e| e.open_state.clone())
+                    else {
+                        return;
+                    };
+
+                    if *open_state.read(cx) {
+                        // Already open, move into the submenu.
+                        move_focus(&state, &entries, 1, cx);
+                    } else {
+                        open_state.update(cx, |is_open, cx| {
+                            *is_open = true;
+                            cx.notify();
+                        });
+                    }
+                }
+            })
+            .on_action({
+                let state = state.clone();
+                let entries = entries.clone();
+                move |_: &SelectLeft, _, cx| {
+                    let Some(entry) = focused_entry(&state, &entries, cx) else {
+                        return;
+                    };
+
+                    if entry.child_ix.is_some() {
+                        // Move back to the parent item.
+                        let item_ix = entry.item_ix;
+                        state.update(cx, |state, cx| {
+                            state.focused = Some((item_ix, None));
+                            cx.notify();
+                        });
+                    } else if let Some(open_state) = &entry.open_state {
+                        open_state.update(cx, |is_open, cx| {
+                            *is_open = false;
+                            cx.notify();
+                        });
+                    }
+                }
+            })
+            .on_action({
+                let state = state.clone();
+                let entries = entries.clone();
+                move |_: &Confirm, window, cx| {
+                    if let Some(entry) = focused_entry(&state, &entries, cx) {
+                        (entry.handler)(&ClickEvent::default(), window, cx);
+                    }
+                }
+            })
+            .gap_2()
+            .refine_style(&self.style)
+            .children(self.items)
     }
 }
 
+/// The expand/collapse animation state of the submenu of [`SidebarMenuItem`].
+struct SubmenuState {
+    /// The measured height of the submenu content, `None` until it has been rendered.
+    content_height: Option<Pixels>,
+    is_open: bool,
+    /// Increased on each open state change, used to restart the animation.
+    ///
+    /// `0` means there is no state change since first render, so no animation.
+    generation: usize,
+    /// The time that the collapse animation ends, keep render the children until then.
+    closing_until: Option<Instant>,
+    /// Refresh the window when the collapse animation ends, to remove the children.
+    _close_task: Option<Task<()>>,
+}
+
 /// Menu item for the [`SidebarMenu`]
 #[derive(IntoElement)]
 pub struct SidebarMenuItem {
     id: ElementId,
+    /// The key to match [`SidebarMenu::selected`].
+    key: Option<SharedString>,
     icon: Option<Icon>,
     label: SharedString,
     handler: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>,
     active: bool,
     default_open: bool,
     click_to_open: bool,
+    reset_key: Option<ElementId>,
     collapsed: bool,
     children: Vec<Self>,
     suffix: Option<AnyElement>,
+    badge: Option<SharedString>,
     disabled: bool,
+    /// Whether the item is focused by the keyboard navigation of [`SidebarMenu`].
+    focused: bool,
 }
 
 impl SidebarMenuItem {
@@ -94,6 +406,7 @@ impl SidebarMenuItem {
     pub fn new(label: impl Into<SharedString>) -> Self {
         Self {
             id: ElementId::Integer(0),
+            key: None,
             icon: None,
             label: label.into(),
             handler: Rc::new(|_, _, _| {}),
@@ -101,9 +414,12 @@ impl SidebarMenuItem {
             collapsed: false,
             default_open: false,
             click_to_open: false,
+            reset_key: None,
             children: Vec::new(),
             suffix: None,
+            badge: None,
             disabled: false,
+            focused: false,
         }
     }
 
@@ -113,6 +429,12 @@ impl SidebarMenuItem {
         self
     }
 
+    /// Set the key of the menu item, to be selected by [`SidebarMenu::selected`].
+    pub fn key(mut self, key: impl Into<SharedString>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
     /// Set the active state of the menu item
     pub fn active(mut self, active: bool) -> Self {
         self.active = active;
@@ -152,6 +474,15 @@ impl SidebarMenuItem {
         self
     }
 
+    /// Set a key to reset the open state of the Submenu when it changes.
+    ///
+    /// The open state is kept by the item id, use this when the same item position
+    /// is reused for a different item, then `default_open` will be applied again.
+    pub fn reset_key(mut self, key: impl Into<ElementId>) -> Self {
+        self.reset_key = Some(key.into());
+        self
+    }
+
     pub fn children(mut self, children: impl IntoIterator<Item = impl Into<Self>>) -> Self {
         self.children = children.into_iter().map(Into::into).collect();
         self
@@ -163,6 +494,21 @@ impl SidebarMenuItem {
         self
     }
 
+    /// Set a badge for the menu item, e.g. an unread count.
+    ///
+    /// The badge is shown as a pill after the label, before the suffix,
+    /// or as a dot on the icon when the sidebar is collapsed.
+    ///
+    /// ```ignore
+    /// SidebarMenuItem::new("Processes")
+    ///     .icon(IconName::SquareTerminal)
+    ///     .badge(processes.len().to_string())
+    /// ```
+    pub fn badge(mut self, badge: impl Into<SharedString>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
     /// Set disabled flat for menu item.
     pub fn disable(mut self, disable: bool) -> Self {
         self.disabled = disable;
@@ -175,16 +521,39 @@ impl SidebarMenuItem {
         self
     }
 
+    /// Returns the item if its label contains the lowercased `query`,
+    /// or with the submenu items that match.
+    fn filter(mut self, query: &str) -> Option<Self> {
+        if self.label.to_lowercase().contains(query) {
+            return Some(self);
+        }
+
+        self.children = self
+            .children
+            .into_iter()
+            .filter_map(|child| child.filter(query))
+            .collect();
+        (!self.children.is_empty()).then_some(self)
+    }
+
     fn is_submenu(&self) -> bool {
         self.children.len() > 0
     }
+
+    /// Returns the open state of the submenu, shared by [`SidebarMenu`] for keyboard navigation.
+    fn open_state(&self, window: &mut Window, cx: &mut App) -> Entity<bool> {
+        let default_open = self.default_open;
+        window.use_keyed_state_with_reset(self.id.clone(), self.reset_key.clone(), cx, |_, _| {
+            default_open
+        })
+    }
 }
 
 impl RenderOnce for SidebarMenuItem {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let click_to_open = self.click_to_open;
-        let default_open = self.default_open;
-        let open_state = window.use_keyed_state(self.id.clone(), cx, |_, _| default_open);
+        let open_state = self.open_state(window, cx);
+        let is_focused = self.focused;
 
         let handler = self.handler.clone();
         let is_collapsed = self.collapsed;
@@ -194,6 +563,47 @@ impl RenderOnce for SidebarMenuItem {
         let is_submenu = self.is_submenu();
         let is_open = is_submenu && !is_collapsed && *open_state.read(cx);
 
+        let submenu_state = window.use_keyed_state(
+            SharedString::from(format!("{}-submenu", self.id)),
+            cx,
+            |_, _| SubmenuState {
+                content_height: None,
+                is_open,
+                generation: 0,
+                closing_until: None,
+                _close_task: None,
+            },
+        );
+        if submenu_state.read(cx).is_open != is_open {
+            submenu_state.update(cx, |state, cx| {
+                state.is_open = is_open;
+                state.generation += 1;
+                if is_open {
+                    state.closing_until = None;
+                    state._close_task = None;
+                } else {
+                    state.closing_until = Some(Instant::now() + SUBMENU_ANIMATION_DURATION);
+                    state._close_task = Some(cx.spawn_in(window, async move |_, cx| {
+                        cx.background_executor()
+                            .timer(SUBMENU_ANIMATION_DURATION)
+                            .await;
+                        _ = cx.update(|window, _| window.refresh());
+                    }));
+                }
+            });
+        }
+        let (content_height, generation, is_closing) = {
+            let state = submenu_state.read(cx);
+            (
+                state.content_height,
+                state.generation,
+                state
+                    .closing_until
+                    .map_or(false, |until| Instant::now() < until),
+            )
+        };
+        let show_submenu = is_submenu && !is_collapsed && (is_open || is_closing);
+
         div()
             .id(self.id.clone())
             .w_full()
@@ -218,12 +628,39 @@ impl RenderOnce for SidebarMenuItem {
                             .bg(cx.theme().sidebar_accent)
                             .text_color(cx.theme().sidebar_accent_foreground)
                     })
-                    .when_some(self.icon.clone(), |this, icon| this.child(icon))
+                    .when_some(self.icon.clone(), |this, icon| {
+                        this.child(
+                            div()
+                                .relative()
+                                .child(icon)
+                                .when(is_collapsed && self.badge.is_some(), |this| {
+                                    this.child(
+                                        div()
+                                            .absolute()
+                                            .top(px(-2.))
+                                            .right(px(-2.))
+                                            .size_1p5()
+                                            .rounded_full()
+                                            .bg(cx.theme().sidebar_primary),
+                                    )
+                                }),
+                        )
+                    })
                     .when(is_collapsed, |this| {
-                        this.justify_center().when(is_active, |this| {
-                            this.bg(cx.theme().sidebar_accent)
-                                .text_color(cx.theme().sidebar_accent_foreground)
-                        })
+                        let label = self.label.clone();
+                        this.justify_center()
+                            .when(is_active, |this| {
+                                this.bg(cx.theme().sidebar_accent)
+                                    .text_color(cx.theme().sidebar_accent_foreground)
+                            })
+                            // Only the icon is visible, show the label in a tooltip.
+                            .tooltip(move |window, cx| {
+                                Tooltip::new(label.clone())
+                                    .when(is_disabled, |this| {
+                                        this.text_color(cx.theme().muted_foreground)
+                                    })
+                                    .build(window, cx)
+                            })
                     })
                     .when(!is_collapsed, |this| {
                         this.h_7()
@@ -239,6 +676,18 @@ impl RenderOnce for SidebarMenuItem {
                                             .overflow_x_hidden()
                                             .child(self.label.clone()),
                                     )
+                                    .when_some(self.badge, |this, badge| {
+                                        this.child(
+                                            div()
+                                                .flex_shrink_0()
+                                                .px_1p5()
+                                                .rounded_full()
+                                                .text_xs()
+                                                .bg(cx.theme().sidebar_primary)
+                                                .text_color(cx.theme().sidebar_primary_foreground)
+                                                .child(badge),
+                                        )
+                                    })
                                     .when_some(self.suffix, |this, suffix| this.child(suffix)),
                             )
                             .when(is_submenu, |this| {
@@ -249,9 +698,10 @@ impl RenderOnce for SidebarMenuItem {
                                         .icon(
                                             Icon::new(IconName::ChevronRight)
                                                 .size_4()
-                                                .when(is_open, |this| {
-                                                    this.rotate(percentage(90. / 360.))
-                                                }),
+                                                .rotate_to(
+                                                    "caret-rotation",
+                                                    if is_open { 90. } else { 0. },
+                                                ),
                                         )
                                         .on_click({
                                             let open_state = open_state.clone();
@@ -270,6 +720,7 @@ impl RenderOnce for SidebarMenuItem {
                     .when(is_disabled, |this| {
                         this.text_color(cx.theme().muted_foreground)
                     })
+                    .focus_ring(is_focused, px(0.), window, cx)
                     .when(!is_disabled, |this| {
                         this.on_click({
                             let open_state = open_state.clone();
@@ -286,21 +737,70 @@ impl RenderOnce for SidebarMenuItem {
                         })
                     }),
             )
-            .when(is_open, |this| {
+            .when(show_submenu, |this| {
+                let submenu = v_flex()
+                    .id("submenu")
+                    .border_l_1()
+                    .border_color(cx.theme().sidebar_border)
+                    .gap_1()
+                    .ml_3p5()
+                    .pl_2p5()
+                    .py_0p5()
+                    .flex_shrink_0()
+                    .children(
+                        self.children
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ix, item)| item.id(ix)),
+                    )
+                    .on_prepaint({
+                        let submenu_state = submenu_state.clone();
+                        move |bounds, window, cx| {
+                            let is_first_measure = submenu_state.update(cx, |state, _| {
+                                state.content_height.replace(bounds.size.height).is_none()
+                            });
+                            // Render again to start the expand animation with the height.
+                            if is_first_measure {
+                                window.refresh();
+                            }
+                        }
+                    });
+
+                if generation == 0 {
+                    return this.child(submenu);
+                }
+
+                // The first expand, measure the content in a hidden container before animating.
+                let Some(content_height) = content_height else {
+                    return this.child(
+                        div()
+                            .id("submenu-container")
+                            .h_0()
+                            .overflow_hidden()
+                            .invisible()
+                            .child(submenu),
+                    );
+                };
+
+                // Animate the height from 0 to the measured content height on expand,
+                // and reverse on collapse.
                 this.child(
-                    v_flex()
-                        .id("submenu")
-                        .border_l_1()
-                        .border_color(cx.theme().sidebar_border)
-                        .gap_1()
-                        .ml_3p5()
-                        .pl_2p5()
-                        .py_0p5()
-                        .children(
-                            self.children
-                                .into_iter()
-                                .enumerate()
-                                .map(|(ix, item)| item.id(ix)),
+                    div()
+                        .id("submenu-container")
+                        .overflow_hidden()
+                        .child(submenu)
+                        .with_animation(
+                            SharedString::from(format!("submenu-{}", generation)),
+                            Animation::new(SUBMENU_ANIMATION_DURATION).with_easing(ease_in_out),
+                            move |this, delta| {
+                                let progress = if is_open { delta } else { 1. - delta };
+                                if is_open && delta >= 1. {
+                                    // Keep the natural height, to allow nested submenu to expand.
+                                    this
+                                } else {
+                                    this.h(content_height * progress).opacity(progress)
+                                }
+                            },
                         ),
                 )
             })
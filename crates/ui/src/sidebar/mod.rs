@@ -6,8 +6,9 @@ use crate::{
     v_flex,
 };
 use gpui::{
-    AnyElement, App, ClickEvent, EdgesRefinement, InteractiveElement as _, IntoElement,
-    ParentElement, Pixels, RenderOnce, StyleRefinement, Styled, Window, div,
+    AnyElement, App, AppContext as _, ClickEvent, Context, DragMoveEvent, EdgesRefinement, Empty,
+    EntityId, InteractiveElement as _, IntoElement, ParentElement, Pixels, Render, RenderOnce,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Window, div,
     prelude::FluentBuilder, px,
 };
 use std::rc::Rc;
@@ -21,10 +22,37 @@ pub use group::*;
 pub use header::*;
 pub use menu::*;
 
+pub(crate) fn init(cx: &mut App) {
+    menu::init(cx);
+}
+
 const DEFAULT_WIDTH: Pixels = px(255.);
 const COLLAPSED_WIDTH: Pixels = px(48.);
+const DEFAULT_MAX_WIDTH: Pixels = px(480.);
+/// Dragging the border below this width collapses the sidebar.
+const COLLAPSE_THRESHOLD: Pixels = px(120.);
+
+#[derive(Clone)]
+struct DragSidebarBorder(EntityId);
+
+impl Render for DragSidebarBorder {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
 
 /// A Sidebar element that can contain collapsible child elements.
+///
+/// The Sidebar does not persist its state, to restore it across sessions,
+/// read the stored value into [`Sidebar::collapsed`] and [`Sidebar::width`] on construction,
+/// and save the changes from [`Sidebar::on_collapse_change`].
+///
+/// ```ignore
+/// Sidebar::left()
+///     .collapsed(settings.sidebar_collapsed)
+///     .width(px(settings.sidebar_width))
+///     .on_collapse_change(|collapsed, cx| save_sidebar_collapsed(collapsed, cx))
+/// ```
 #[derive(IntoElement)]
 pub struct Sidebar<E: Collapsible + IntoElement + 'static> {
     style: StyleRefinement,
@@ -37,6 +65,10 @@ pub struct Sidebar<E: Collapsible + IntoElement + 'static> {
     side: Side,
     collapsible: bool,
     collapsed: bool,
+    width: Option<Pixels>,
+    max_width: Pixels,
+    on_collapse_change: Option<Rc<dyn Fn(bool, &mut App)>>,
+    on_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
 }
 
 impl<E: Collapsible + IntoElement> Sidebar<E> {
@@ -50,6 +82,10 @@ impl<E: Collapsible + IntoElement> Sidebar<E> {
             side,
             collapsible: true,
             collapsed: false,
+            width: None,
+            max_width: DEFAULT_MAX_WIDTH,
+            on_collapse_change: None,
+            on_resize: None,
         }
     }
 
@@ -75,6 +111,39 @@ impl<E: Collapsible + IntoElement> Sidebar<E> {
         self
     }
 
+    /// Set the expanded width of the sidebar, default is 255px.
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = Some(width.into());
+        self
+    }
+
+    /// Set a callback to be called when the collapsed state has changed.
+    ///
+    /// The callback is not called on the first render.
+    pub fn on_collapse_change(mut self, f: impl Fn(bool, &mut App) + 'static) -> Self {
+        self.on_collapse_change = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the max width when resizing by dragging the border, default is 480px.
+    pub fn max_width(mut self, max_width: impl Into<Pixels>) -> Self {
+        self.max_width = max_width.into();
+        self
+    }
+
+    /// Make the sidebar resizable by dragging its border.
+    ///
+    /// The callback receives the new width, clamped between the collapsed width and
+    /// [`Sidebar::max_width`], the app should store it and pass it back to [`Sidebar::width`].
+    ///
+    /// If the sidebar is `collapsible`, dragging the border below 120px will collapse it,
+    /// and dragging a collapsed sidebar above that will expand it,
+    /// the change is notified by [`Sidebar::on_collapse_change`].
+    pub fn on_resize(mut self, f: impl Fn(Pixels, &mut Window, &mut App) + 'static) -> Self {
+        self.on_resize = Some(Rc::new(f));
+        self
+    }
+
     /// Set the header of the sidebar.
     pub fn header(mut self, header: impl IntoElement) -> Self {
         self.header = Some(header.into_any_element());
@@ -88,6 +157,8 @@ impl<E: Collapsible + IntoElement> Sidebar<E> {
     }
 
     /// Add a child element to the sidebar, the child must implement `Collapsible`
+    ///
+    /// Use [`crate::AnyCollapsible`] or [`crate::collapsible_any`] to mix different kinds of children.
     pub fn child(mut self, child: E) -> Self {
         self.content.push(child);
         self
@@ -186,13 +257,34 @@ impl<E: Collapsible + IntoElement> Styled for Sidebar<E> {
     }
 }
 
+struct SidebarState {
+    collapsed: bool,
+}
+
 impl<E: Collapsible + IntoElement> RenderOnce for Sidebar<E> {
-    fn render(mut self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(mut self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         self.style.padding = EdgesRefinement::default();
 
+        let collapsed = self.collapsed;
+        let state = window.use_keyed_state("sidebar-state", cx, |_, _| SidebarState { collapsed });
+        if state.read(cx).collapsed != collapsed {
+            state.update(cx, |state, _| state.collapsed = collapsed);
+            if let Some(on_collapse_change) = self.on_collapse_change.clone() {
+                // Defer to avoid updating the view that is rendering the sidebar.
+                window.defer(cx, move |_, cx| on_collapse_change(collapsed, cx));
+            }
+        }
+
+        let side = self.side;
+        let collapsible = self.collapsible;
+        let max_width = self.max_width;
+        let state_id = state.entity_id();
+        let on_collapse_change = self.on_collapse_change.clone();
+        let on_resize = self.on_resize.clone();
+
         v_flex()
             .id("sidebar")
-            .w(DEFAULT_WIDTH)
+            .w(self.width.unwrap_or(DEFAULT_WIDTH))
             .flex_shrink_0()
             .h_full()
             .overflow_hidden()
@@ -248,5 +340,50 @@ impl<E: Collapsible + IntoElement> RenderOnce for Sidebar<E> {
                         .child(footer),
                 )
             })
+            .when_some(on_resize, |this, on_resize| {
+                this.child(
+                    div()
+                        .id("resize-handle")
+                        .absolute()
+                        .top_0()
+                        .h_full()
+                        .w(px(4.))
+                        .cursor_col_resize()
+                        .hover(|this| this.bg(cx.theme().drag_border))
+                        .map(|this| match side {
+                            Side::Left => this.right_0(),
+                            Side::Right => this.left_0(),
+                        })
+                        .on_drag(DragSidebarBorder(state_id), |drag, _, _, cx| {
+                            cx.stop_propagation();
+                            cx.new(|_| drag.clone())
+                        }),
+                )
+                .on_drag_move(move |e: &DragMoveEvent<DragSidebarBorder>, window, cx| {
+                    if e.drag(cx).0 != state_id {
+                        return;
+                    }
+
+                    let width = match side {
+                        Side::Left => e.event.position.x - e.bounds.left(),
+                        Side::Right => e.bounds.right() - e.event.position.x,
+                    };
+
+                    if collapsible {
+                        let collapsed = width < COLLAPSE_THRESHOLD;
+                        if state.read(cx).collapsed != collapsed {
+                            state.update(cx, |state, _| state.collapsed = collapsed);
+                            if let Some(on_collapse_change) = &on_collapse_change {
+                                on_collapse_change(collapsed, cx);
+                            }
+                        }
+                        if collapsed {
+                            return;
+                        }
+                    }
+
+                    on_resize(width.clamp(COLLAPSED_WIDTH, max_width), window, cx);
+                })
+            })
     }
 }
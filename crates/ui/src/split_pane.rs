@@ -0,0 +1,219 @@
+use gpui::{
+    AnyElement, App, AppContext as _, Along as _, Axis, Context, DragMoveEvent, ElementId, Empty,
+    EntityId, InteractiveElement as _, IntoElement, MouseButton, ParentElement, Pixels, Render,
+    RenderOnce, StatefulInteractiveElement as _, StyleRefinement, Styled, Window, div,
+    prelude::FluentBuilder as _, px, relative,
+};
+use std::rc::Rc;
+
+use crate::{ActiveTheme as _, StyledExt as _};
+
+const DEFAULT_RATIO: f32 = 0.5;
+const DEFAULT_MIN_SIZE: Pixels = px(100.);
+const DIVIDER_SIZE: Pixels = px(4.);
+
+#[derive(Clone)]
+struct DragSplitDivider(EntityId);
+
+impl Render for DragSplitDivider {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
+struct SplitPaneState {
+    ratio: f32,
+    dragging: bool,
+}
+
+/// A container with two panes split by a draggable divider.
+///
+/// The split is kept as the ratio of the first pane to the container size, so it
+/// follows the container when the window is resized. The SplitPane does not persist
+/// the ratio, read the stored value into [`SplitPane::ratio`] and save it from
+/// [`SplitPane::on_resize`].
+///
+/// ```ignore
+/// SplitPane::horizontal("master-detail")
+///     .ratio(settings.split_ratio)
+///     .on_resize(|ratio, _, cx| save_split_ratio(*ratio, cx))
+///     .first(list)
+///     .second(details)
+/// ```
+#[derive(IntoElement)]
+pub struct SplitPane {
+    id: ElementId,
+    style: StyleRefinement,
+    axis: Axis,
+    ratio: f32,
+    min_size: Pixels,
+    first: Option<AnyElement>,
+    second: Option<AnyElement>,
+    on_resize: Option<Rc<dyn Fn(&f32, &mut Window, &mut App)>>,
+}
+
+impl SplitPane {
+    fn new(id: impl Into<ElementId>, axis: Axis) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            axis,
+            ratio: DEFAULT_RATIO,
+            min_size: DEFAULT_MIN_SIZE,
+            first: None,
+            second: None,
+            on_resize: None,
+        }
+    }
+
+    /// Create a SplitPane with the panes side by side.
+    pub fn horizontal(id: impl Into<ElementId>) -> Self {
+        Self::new(id, Axis::Horizontal)
+    }
+
+    /// Create a SplitPane with the panes stacked.
+    pub fn vertical(id: impl Into<ElementId>) -> Self {
+        Self::new(id, Axis::Vertical)
+    }
+
+    /// Set the size of the first pane as a ratio of the container size, default is 0.5.
+    pub fn ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio.clamp(0., 1.);
+        self
+    }
+
+    /// Set the min size of each pane when dragging the divider, default is 100px.
+    pub fn min_size(mut self, min_size: impl Into<Pixels>) -> Self {
+        self.min_size = min_size.into();
+        self
+    }
+
+    /// Set the first pane, left for horizontal and top for vertical.
+    pub fn first(mut self, first: impl IntoElement) -> Self {
+        self.first = Some(first.into_any_element());
+        self
+    }
+
+    /// Set the second pane, right for horizontal and bottom for vertical.
+    pub fn second(mut self, second: impl IntoElement) -> Self {
+        self.second = Some(second.into_any_element());
+        self
+    }
+
+    /// Set a callback to be called with the new ratio when the divider has been dragged.
+    ///
+    /// The callback is called once the drag ends, the app should store the ratio
+    /// and pass it back to [`SplitPane::ratio`].
+    pub fn on_resize(mut self, f: impl Fn(&f32, &mut Window, &mut App) + 'static) -> Self {
+        self.on_resize = Some(Rc::new(f));
+        self
+    }
+}
+
+impl Styled for SplitPane {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for SplitPane {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let ratio = self.ratio;
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| SplitPaneState {
+            ratio,
+            dragging: false,
+        });
+        if !state.read(cx).dragging && state.read(cx).ratio != ratio {
+            state.update(cx, |state, _| state.ratio = ratio);
+        }
+
+        let axis = self.axis;
+        let min_size = self.min_size;
+        let state_id = state.entity_id();
+        let ratio = state.read(cx).ratio;
+        let dragging = state.read(cx).dragging;
+        let on_resize = self.on_resize.clone();
+
+        let end_drag = {
+            let state = state.clone();
+            move |window: &mut Window, cx: &mut App| {
+                if !state.read(cx).dragging {
+                    return;
+                }
+
+                state.update(cx, |state, _| state.dragging = false);
+                if let Some(on_resize) = &on_resize {
+                    let ratio = state.read(cx).ratio;
+                    on_resize(&ratio, window, cx);
+                }
+                window.refresh();
+            }
+        };
+        let end_drag_out = end_drag.clone();
+
+        div()
+            .id(self.id)
+            .flex()
+            .size_full()
+            .map(|this| match axis {
+                Axis::Horizontal => this.flex_row(),
+                Axis::Vertical => this.flex_col(),
+            })
+            .refine_style(&self.style)
+            .child(
+                div()
+                    .flex_none()
+                    .overflow_hidden()
+                    .map(|this| match axis {
+                        Axis::Horizontal => this.h_full().w(relative(ratio)),
+                        Axis::Vertical => this.w_full().h(relative(ratio)),
+                    })
+                    .children(self.first),
+            )
+            .child(
+                div()
+                    .id("divider")
+                    .flex_none()
+                    .when(dragging, |this| this.bg(cx.theme().drag_border))
+                    .hover(|this| this.bg(cx.theme().drag_border))
+                    .map(|this| match axis {
+                        Axis::Horizontal => this.h_full().w(DIVIDER_SIZE).cursor_col_resize(),
+                        Axis::Vertical => this.w_full().h(DIVIDER_SIZE).cursor_row_resize(),
+                    })
+                    .on_drag(DragSplitDivider(state_id), |drag, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| drag.clone())
+                    }),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .map(|this| match axis {
+                        Axis::Horizontal => this.h_full().min_w_0(),
+                        Axis::Vertical => this.w_full().min_h_0(),
+                    })
+                    .children(self.second),
+            )
+            .on_drag_move(move |e: &DragMoveEvent<DragSplitDivider>, window, cx| {
+                if e.drag(cx).0 != state_id {
+                    return;
+                }
+
+                let total = e.bounds.size.along(axis);
+                if total <= min_size * 2. {
+                    return;
+                }
+
+                let size = (e.event.position.along(axis) - e.bounds.origin.along(axis))
+                    .clamp(min_size, total - min_size);
+                state.update(cx, |state, _| {
+                    state.ratio = size / total;
+                    state.dragging = true;
+                });
+                window.refresh();
+            })
+            .on_mouse_up(MouseButton::Left, move |_, window, cx| end_drag(window, cx))
+            .on_mouse_up_out(MouseButton::Left, move |_, window, cx| end_drag_out(window, cx))
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{ActiveTheme, PixelsExt as _};
 use gpui::{
-    App, BoxShadow, Corners, DefiniteLength, Div, Edges, FocusHandle, Hsla, ParentElement, Pixels,
-    Refineable, StyleRefinement, Styled, Window, div, point, px,
+    AnyElement, App, BoxShadow, Corners, DefiniteLength, Div, Edges, FocusHandle, Hsla,
+    IntoElement, ParentElement, Pixels, Refineable, RenderOnce, StyleRefinement, Styled, Window,
+    div, point, px,
 };
 use serde::{Deserialize, Serialize};
 
@@ -162,6 +163,21 @@ pub trait StyledExt: Styled + Sized {
         self.border_1().border_color(cx.theme().ring)
     }
 
+    /// Render the [`StyledExt::focused_border`] when the `focus_handle` or its descendants
+    /// is focused, e.g. to keep a picker highlighted while its popup has the focus.
+    ///
+    /// This colors the own border of a bordered field, like a focused
+    /// [`Input`](crate::input::Input). It is not the translucent outer ring drawn around
+    /// buttons, checkboxes and radios, which is internal to the crate.
+    #[inline]
+    fn focused_border_for(self, focus_handle: &FocusHandle, window: &Window, cx: &App) -> Self {
+        if focus_handle.contains_focused(window, cx) {
+            self.focused_border(cx)
+        } else {
+            self
+        }
+    }
+
     font_weight!(font_thin, THIN);
     font_weight!(font_extralight, EXTRA_LIGHT);
     font_weight!(font_light, LIGHT);
@@ -628,6 +644,61 @@ pub trait Collapsible {
     fn is_collapsed(&self) -> bool;
 }
 
+/// A type-erased [`Collapsible`] element.
+///
+/// This allows mixing different kinds of children in a container that requires
+/// a single `E: Collapsible` type, e.g. a custom panel next to `SidebarMenu`s in a `Sidebar`.
+#[derive(IntoElement)]
+pub struct AnyCollapsible {
+    collapsed: bool,
+    render: Box<dyn FnOnce(bool) -> AnyElement>,
+}
+
+impl AnyCollapsible {
+    /// Wrap a [`Collapsible`] element, the collapsed state will be forwarded to it.
+    pub fn new<E>(element: E) -> Self
+    where
+        E: Collapsible + IntoElement + 'static,
+    {
+        Self {
+            collapsed: element.is_collapsed(),
+            render: Box::new(move |collapsed| element.collapsed(collapsed).into_any_element()),
+        }
+    }
+}
+
+/// Wrap any element as [`AnyCollapsible`], the collapsed state will be ignored.
+///
+/// ```ignore
+/// Sidebar::left()
+///     .child(AnyCollapsible::new(SidebarMenu::new()))
+///     .child(collapsible_any(div().child("Custom panel")))
+/// ```
+pub fn collapsible_any(element: impl IntoElement) -> AnyCollapsible {
+    let element = element.into_any_element();
+    AnyCollapsible {
+        collapsed: false,
+        render: Box::new(move |_| element),
+    }
+}
+
+impl Collapsible for AnyCollapsible {
+    fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+}
+
+impl RenderOnce for AnyCollapsible {
+    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+        (self.render)(self.collapsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use gpui::px;
@@ -132,6 +132,9 @@ impl Column {
     }
 
     /// Set whether the column is fixed on left side, default is false.
+    ///
+    /// The fixed columns stay visible when scrolling horizontally, with a shadow
+    /// over the columns scrolled under them, they should be the first columns.
     pub fn fixed_left(mut self) -> Self {
         self.fixed = Some(ColumnFixed::Left);
         self
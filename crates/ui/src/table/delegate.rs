@@ -1,8 +1,8 @@
 use std::ops::Range;
 
 use gpui::{
-    App, Context, Div, InteractiveElement as _, IntoElement, ParentElement as _, Stateful,
-    Styled as _, Window, div,
+    App, Context, Div, InteractiveElement as _, IntoElement, ParentElement as _, SharedString,
+    Stateful, Styled as _, Window, div,
 };
 
 use crate::{
@@ -26,6 +26,9 @@ pub trait TableDelegate: Sized + 'static {
     fn column(&self, col_ix: usize, cx: &App) -> Column;
 
     /// Perform sort on the column at the given index.
+    ///
+    /// Clicking the sort icon of a column cycles its sort through [`ColumnSort::Default`],
+    /// [`ColumnSort::Ascending`] and [`ColumnSort::Descending`], the default means unsorted.
     fn perform_sort(
         &mut self,
         col_ix: usize,
@@ -88,6 +91,14 @@ pub trait TableDelegate: Sized + 'static {
         cx: &mut Context<TableState<Self>>,
     ) -> impl IntoElement;
 
+    /// Return the plain text of the cell at the given row and column.
+    ///
+    /// This is used to export the table as text, see [`TableState::export_csv`],
+    /// default is empty.
+    fn cell_text(&self, row_ix: usize, col_ix: usize, cx: &App) -> SharedString {
+        SharedString::default()
+    }
+
     /// Move the column at the given `col_ix` to insert before the column at the given `to_ix`.
     fn move_column(
         &mut self,
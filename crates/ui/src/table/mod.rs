@@ -3,17 +3,19 @@ use crate::{
     ActiveTheme, Sizable, Size,
 };
 use gpui::{
-    actions, div, prelude::FluentBuilder, App, Edges, Entity, Focusable, InteractiveElement,
-    IntoElement, KeyBinding, ParentElement, RenderOnce, Styled, Window,
+    actions, div, prelude::FluentBuilder, App, Edges, Entity, Focusable, Hsla, InteractiveElement,
+    IntoElement, KeyBinding, ParentElement, Pixels, RenderOnce, Styled, Window,
 };
 
 mod column;
 mod delegate;
 mod loading;
+mod sortable;
 mod state;
 
 pub use column::*;
 pub use delegate::*;
+pub use sortable::*;
 pub use state::*;
 
 actions!(table, [SelectPrevColumn, SelectNextColumn]);
@@ -33,10 +35,30 @@ struct TableOptions {
     scrollbar_visible: Edges<bool>,
     /// Set stripe style of the table.
     stripe: bool,
+    /// The background of the stripe rows, default is the `table_even` theme color.
+    stripe_color: Option<Hsla>,
+    /// Whether to highlight the hovered row.
+    hover_highlight: bool,
+    /// The background of the hovered row, default is the `accent` theme color.
+    hover_color: Option<Hsla>,
     /// Set to use border style of the table.
     bordered: bool,
     /// The cell size of the table.
     size: Size,
+    /// The height of the body rows, default is the row height of the `size`.
+    row_height: Option<Pixels>,
+    /// The height of the header row, default is the row height of the `size`.
+    header_height: Option<Pixels>,
+}
+
+impl TableOptions {
+    fn row_height(&self) -> Pixels {
+        self.row_height.unwrap_or_else(|| self.size.table_row_height())
+    }
+
+    fn header_height(&self) -> Pixels {
+        self.header_height.unwrap_or_else(|| self.size.table_row_height())
+    }
 }
 
 impl Default for TableOptions {
@@ -44,13 +66,22 @@ impl Default for TableOptions {
         Self {
             scrollbar_visible: Edges::all(true),
             stripe: false,
+            stripe_color: None,
+            hover_highlight: true,
+            hover_color: None,
             bordered: true,
             size: Size::default(),
+            row_height: None,
+            header_height: None,
         }
     }
 }
 
 /// A table element.
+///
+/// The rows and columns are virtualized, only the cells in the visible range are rendered
+/// by [`TableDelegate::render_td`], so all the rows have the same height,
+/// see [`Table::row_height`].
 #[derive(IntoElement)]
 pub struct Table<D: TableDelegate> {
     state: Entity<TableState<D>>,
@@ -75,12 +106,50 @@ where
         self
     }
 
+    /// Set the background of the stripe rows, default is the `table_even` theme color.
+    ///
+    /// Only used when [`Table::stripe`] is enabled.
+    pub fn stripe_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.options.stripe_color = Some(color.into());
+        self
+    }
+
+    /// Set whether to highlight the row under the mouse, default to true.
+    ///
+    /// The selected rows keep their highlight either way.
+    pub fn hover_highlight(mut self, hover_highlight: bool) -> Self {
+        self.options.hover_highlight = hover_highlight;
+        self
+    }
+
+    /// Set the background of the row under the mouse, default is the `accent` theme color.
+    ///
+    /// Only used when [`Table::hover_highlight`] is enabled.
+    pub fn hover_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.options.hover_color = Some(color.into());
+        self
+    }
+
     /// Set to use border style of the table, default to true.
     pub fn bordered(mut self, bordered: bool) -> Self {
         self.options.bordered = bordered;
         self
     }
 
+    /// Set the height of the body rows, default is the row height of the table size.
+    ///
+    /// All rows have this height, to calculate the visible rows by the scroll offset.
+    pub fn row_height(mut self, row_height: impl Into<Pixels>) -> Self {
+        self.options.row_height = Some(row_height.into());
+        self
+    }
+
+    /// Set the height of the header row, default is the row height of the table size.
+    pub fn header_height(mut self, header_height: impl Into<Pixels>) -> Self {
+        self.options.header_height = Some(header_height.into());
+        self
+    }
+
     /// Set scrollbar visibility.
     pub fn scrollbar_visible(mut self, vertical: bool, horizontal: bool) -> Self {
         self.options.scrollbar_visible = Edges {
@@ -0,0 +1,378 @@
+use std::{cmp::Ordering, collections::HashMap, hash::Hash, rc::Rc};
+
+use gpui::SharedString;
+
+use crate::table::ColumnSort;
+
+type Comparator<T> = Rc<dyn Fn(&T, &T) -> Ordering>;
+
+/// A helper to keep the rows of a [`super::TableDelegate`] filtered and sorted by column.
+///
+/// Register a comparator for each sortable column by the [`super::Column::key`],
+/// and a filter for the search query, then the visible rows will be updated
+/// when the rows, query or sort changed.
+///
+/// ```ignore
+/// let rows = SortableFilterable::new(processes)
+///     .sort_by_key("name", |p: &ProcessInfo| p.name.clone())
+///     .sort_by_float("cpu", |p| p.cpu_usage as f64)
+///     .filter(|p, query| p.name.to_lowercase().contains(query));
+/// ```
+pub struct SortableFilterable<T> {
+    rows: Vec<T>,
+    /// The indices of `rows` that are visible, in sorted order.
+    visible: Vec<usize>,
+    comparators: HashMap<SharedString, Comparator<T>>,
+    filter: Option<Rc<dyn Fn(&T, &str) -> bool>>,
+    predicate: Option<Rc<dyn Fn(&T) -> bool>>,
+    pinned: Option<Rc<dyn Fn(&T) -> bool>>,
+    query: String,
+    sort: Option<(SharedString, ColumnSort)>,
+}
+
+impl<T> SortableFilterable<T> {
+    /// Create a new helper with the given rows, all rows are visible in the original order.
+    pub fn new(rows: Vec<T>) -> Self {
+        let visible = (0..rows.len()).collect();
+        Self {
+            rows,
+            visible,
+            comparators: HashMap::new(),
+            filter: None,
+            predicate: None,
+            pinned: None,
+            query: String::new(),
+            sort: None,
+        }
+    }
+
+    /// Register a comparator for the column with the given `key`.
+    pub fn sort_by(
+        mut self,
+        key: impl Into<SharedString>,
+        compare: impl Fn(&T, &T) -> Ordering + 'static,
+    ) -> Self {
+        self.comparators.insert(key.into(), Rc::new(compare));
+        self
+    }
+
+    /// Register a key extractor for the column with the given `key`, the values are compared by [`Ord`].
+    pub fn sort_by_key<K: Ord>(
+        self,
+        key: impl Into<SharedString>,
+        f: impl Fn(&T) -> K + 'static,
+    ) -> Self {
+        self.sort_by(key, move |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Register a float key extractor for the column with the given `key`.
+    ///
+    /// The values are compared by [`f64::total_cmp`], so `NaN` will not panic.
+    pub fn sort_by_float(
+        self,
+        key: impl Into<SharedString>,
+        f: impl Fn(&T) -> f64 + 'static,
+    ) -> Self {
+        self.sort_by(key, move |a, b| f(a).total_cmp(&f(b)))
+    }
+
+    /// Set the filter, the row is visible if it returns true.
+    ///
+    /// The second argument is the lowercased query, the filter is skipped when the query is empty.
+    pub fn filter(mut self, filter: impl Fn(&T, &str) -> bool + 'static) -> Self {
+        self.filter = Some(Rc::new(filter));
+        self
+    }
+
+    /// Replace the filter, e.g. with a filter parsed from the query.
+    ///
+    /// Takes effect on the next [`Self::set_query`] or [`Self::refresh`].
+    pub fn set_filter(&mut self, filter: impl Fn(&T, &str) -> bool + 'static) {
+        self.filter = Some(Rc::new(filter));
+    }
+
+    /// Replace the rows, and keep the current query and sort.
+    pub fn set_rows(&mut self, rows: Vec<T>) {
+        self.rows = rows;
+        self.refresh();
+    }
+
+    /// Replace the rows, and keep the visible rows in place by matching them with `id`.
+    ///
+    /// The rows that are still visible keep their position, the removed or filtered out rows
+    /// drop out, and the new rows are appended in sorted order. So the rows don't jump around
+    /// on every update, call [`Self::refresh`] to sort all the rows again.
+    pub fn merge_rows<K: Eq + Hash>(&mut self, rows: Vec<T>, id: impl Fn(&T) -> K) {
+        let mut new_rows: HashMap<K, usize> =
+            rows.iter().enumerate().map(|(ix, row)| (id(row), ix)).collect();
+
+        let mut visible: Vec<usize> = self
+            .visible
+            .iter()
+            .filter_map(|&ix| new_rows.remove(&id(&self.rows[ix])))
+            .filter(|&ix| self.is_visible(&rows[ix]))
+            .collect();
+        let mut added: Vec<usize> = new_rows
+            .into_values()
+            .filter(|&ix| self.is_visible(&rows[ix]))
+            .collect();
+        added.sort_by(|&a, &b| self.compare(&rows[a], &rows[b]).then(a.cmp(&b)));
+        visible.extend(added);
+
+        if let Some(pinned) = &self.pinned {
+            visible.sort_by_key(|&ix| !pinned(&rows[ix]));
+        }
+
+        self.rows = rows;
+        self.visible = visible;
+    }
+
+    /// Set the search query to filter the rows.
+    pub fn set_query(&mut self, query: impl AsRef<str>) {
+        self.query = query.as_ref().to_lowercase();
+        self.refresh();
+    }
+
+    /// Set a predicate the rows must also match, e.g. a minimum value.
+    ///
+    /// Unlike the filter it applies even when the query is empty.
+    pub fn set_predicate(&mut self, predicate: impl Fn(&T) -> bool + 'static) {
+        self.predicate = Some(Rc::new(predicate));
+        self.refresh();
+    }
+
+    /// Keep the rows that the `pinned` returns true at the top, regardless of the sort.
+    ///
+    /// The pinned rows are still filtered by the query, and sorted among themselves.
+    pub fn set_pinned(&mut self, pinned: impl Fn(&T) -> bool + 'static) {
+        self.pinned = Some(Rc::new(pinned));
+        self.refresh();
+    }
+
+    /// Returns the current (lowercased) query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Sort by the column with the given `key`.
+    ///
+    /// [`ColumnSort::Default`] means keep the original order of the rows.
+    pub fn set_sort(&mut self, key: impl Into<SharedString>, sort: ColumnSort) {
+        self.sort = Some((key.into(), sort));
+        self.refresh();
+    }
+
+    /// Returns the current sort column key and sort order.
+    pub fn sort(&self) -> Option<(&SharedString, ColumnSort)> {
+        self.sort.as_ref().map(|(key, sort)| (key, *sort))
+    }
+
+    /// Compare two rows by the pinned rows and the current sort,
+    /// e.g. to sort the rows aggregated from the visible rows.
+    ///
+    /// Returns [`Ordering::Equal`] if the rows are not sorted by a registered column.
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        if let Some(pinned) = &self.pinned {
+            let ordering = pinned(b).cmp(&pinned(a));
+            if ordering.is_ne() {
+                return ordering;
+            }
+        }
+
+        let compare = self
+            .sort
+            .as_ref()
+            .and_then(|(key, sort)| self.comparators.get(key).map(|compare| (compare, sort)));
+        match compare {
+            Some((compare, ColumnSort::Ascending)) => compare(a, b),
+            Some((compare, ColumnSort::Descending)) => compare(b, a),
+            _ => Ordering::Equal,
+        }
+    }
+
+    /// Returns the number of visible rows.
+    pub fn len(&self) -> usize {
+        self.visible.len()
+    }
+
+    /// Returns true if there is no visible rows.
+    pub fn is_empty(&self) -> bool {
+        self.visible.is_empty()
+    }
+
+    /// Returns the visible row at the given index.
+    pub fn get(&self, ix: usize) -> Option<&T> {
+        self.visible.get(ix).and_then(|&ix| self.rows.get(ix))
+    }
+
+    /// Returns an iterator over the visible rows in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.visible.iter().filter_map(|&ix| self.rows.get(ix))
+    }
+
+    /// Returns all the rows (include the filtered out rows) in the original order.
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    /// Returns true if the row passes the filter of the current query.
+    fn is_visible(&self, row: &T) -> bool {
+        let matches_query = match (&self.filter, self.query.is_empty()) {
+            (Some(filter), false) => filter(row, &self.query),
+            _ => true,
+        };
+        matches_query && self.predicate.as_ref().is_none_or(|predicate| predicate(row))
+    }
+
+    /// Re-apply the filter and sort.
+    pub fn refresh(&mut self) {
+        let rows = &self.rows;
+        self.visible = (0..rows.len())
+            .filter(|&ix| self.is_visible(&rows[ix]))
+            .collect();
+
+        let compare = self
+            .sort
+            .as_ref()
+            .and_then(|(key, sort)| self.comparators.get(key).map(|compare| (compare, sort)));
+        match compare {
+            Some((compare, ColumnSort::Ascending)) => self
+                .visible
+                .sort_by(|&a, &b| compare(&rows[a], &rows[b])),
+            Some((compare, ColumnSort::Descending)) => self
+                .visible
+                .sort_by(|&a, &b| compare(&rows[b], &rows[a])),
+            _ => {}
+        }
+
+        // Stable sort, so the order is kept within the pinned and unpinned rows.
+        if let Some(pinned) = &self.pinned {
+            self.visible.sort_by_key(|&ix| !pinned(&rows[ix]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::SortableFilterable;
+    use crate::table::ColumnSort;
+
+    fn rows() -> SortableFilterable<(&'static str, f32)> {
+        SortableFilterable::new(vec![("foo", 1.5), ("Bar", f32::NAN), ("baz", 3.0)])
+            .sort_by_key("name", |r| r.0.to_lowercase())
+            .sort_by_float("value", |r| r.1 as f64)
+            .filter(|r, query| r.0.to_lowercase().contains(query))
+    }
+
+    fn names(rows: &SortableFilterable<(&'static str, f32)>) -> Vec<&'static str> {
+        rows.iter().map(|r| r.0).collect()
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut rows = rows();
+        assert_eq!(names(&rows), vec!["foo", "Bar", "baz"]);
+
+        rows.set_sort("name", ColumnSort::Ascending);
+        assert_eq!(names(&rows), vec!["Bar", "baz", "foo"]);
+        rows.set_sort("name", ColumnSort::Descending);
+        assert_eq!(names(&rows), vec!["foo", "baz", "Bar"]);
+
+        // NaN is sorted after the numbers, without panic.
+        rows.set_sort("value", ColumnSort::Ascending);
+        assert_eq!(names(&rows), vec!["foo", "baz", "Bar"]);
+
+        rows.set_sort("value", ColumnSort::Default);
+        assert_eq!(names(&rows), vec!["foo", "Bar", "baz"]);
+
+        // Unknown column keeps the original order.
+        rows.set_sort("unknown", ColumnSort::Descending);
+        assert_eq!(names(&rows), vec!["foo", "Bar", "baz"]);
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut rows = rows();
+        rows.set_sort("name", ColumnSort::Descending);
+        rows.set_query("BA");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(names(&rows), vec!["baz", "Bar"]);
+        assert_eq!(rows.get(1).map(|r| r.0), Some("Bar"));
+        assert_eq!(rows.get(2), None);
+
+        // Keep query and sort after rows changed.
+        rows.set_rows(vec![("bay", 0.), ("qux", 0.), ("bax", 0.)]);
+        assert_eq!(names(&rows), vec!["bay", "bax"]);
+
+        rows.set_query("");
+        assert_eq!(names(&rows), vec!["qux", "bay", "bax"]);
+        assert_eq!(rows.rows().len(), 3);
+    }
+
+    #[test]
+    fn test_predicate() {
+        let mut rows = rows();
+        rows.set_predicate(|r| r.1 > 1.);
+        assert_eq!(names(&rows), vec!["baz"]);
+
+        // Combined with the query.
+        rows.set_query("f");
+        assert_eq!(names(&rows), Vec::<&str>::new());
+        rows.set_rows(vec![("foo", 2.), ("fig", 0.5)]);
+        assert_eq!(names(&rows), vec!["foo"]);
+    }
+
+    #[test]
+    fn test_merge_rows() {
+        let mut rows = rows();
+        rows.set_sort("value", ColumnSort::Descending);
+        assert_eq!(names(&rows), vec!["Bar", "baz", "foo"]);
+
+        // Keep the position of the existing rows, drop "Bar" and append the new rows in order.
+        rows.merge_rows(
+            vec![("qux", 1.), ("foo", 9.), ("baz", 0.), ("quux", 2.)],
+            |r| r.0,
+        );
+        assert_eq!(names(&rows), vec!["baz", "foo", "quux", "qux"]);
+        assert_eq!(rows.get(1), Some(&("foo", 9.)));
+
+        // The filtered out rows drop out, and come back when they match again.
+        rows.set_query("ba");
+        rows.merge_rows(vec![("foo", 0.), ("baz", 1.), ("bar", 2.)], |r| r.0);
+        assert_eq!(names(&rows), vec!["baz", "bar"]);
+
+        rows.refresh();
+        assert_eq!(names(&rows), vec!["bar", "baz"]);
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut rows = rows();
+        assert_eq!(rows.compare(&("a", 1.), &("b", 0.)), Ordering::Equal);
+
+        rows.set_sort("value", ColumnSort::Descending);
+        assert_eq!(rows.compare(&("a", 1.), &("b", 0.)), Ordering::Less);
+        rows.set_sort("value", ColumnSort::Ascending);
+        assert_eq!(rows.compare(&("a", 1.), &("b", 0.)), Ordering::Greater);
+
+        rows.set_pinned(|r| r.0 == "a");
+        assert_eq!(rows.compare(&("a", 1.), &("b", 0.)), Ordering::Less);
+    }
+
+    #[test]
+    fn test_pinned() {
+        let mut rows = rows();
+        rows.set_sort("name", ColumnSort::Ascending);
+        rows.set_pinned(|r| r.0 == "foo" || r.0 == "baz");
+        assert_eq!(names(&rows), vec!["baz", "foo", "Bar"]);
+
+        rows.set_sort("name", ColumnSort::Descending);
+        assert_eq!(names(&rows), vec!["foo", "baz", "Bar"]);
+
+        // Pinned rows are still filtered.
+        rows.set_query("ba");
+        assert_eq!(names(&rows), vec!["baz", "Bar"]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{ops::Range, rc::Rc, time::Duration};
+use std::{collections::HashMap, ops::Range, rc::Rc, time::Duration};
 
 use crate::{
     ActiveTheme, ElementExt, Icon, IconName, StyleSized as _, StyledExt, VirtualListScrollHandle,
@@ -9,15 +9,18 @@ use crate::{
     v_flex,
 };
 use gpui::{
-    AppContext, Axis, Bounds, ClickEvent, Context, Div, DragMoveEvent, EventEmitter, FocusHandle,
-    Focusable, InteractiveElement, IntoElement, ListSizingBehavior, MouseButton, MouseDownEvent,
-    ParentElement, Pixels, Point, Render, ScrollStrategy, SharedString, Stateful,
+    App, AppContext, Axis, Bounds, ClickEvent, Context, Div, DragMoveEvent, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement, IntoElement, ListSizingBehavior, MouseButton,
+    MouseDownEvent, ParentElement, Pixels, Point, Render, ScrollStrategy, SharedString, Stateful,
     StatefulInteractiveElement as _, Styled, Task, UniformListScrollHandle, Window, div,
-    prelude::FluentBuilder, px, uniform_list,
+    linear_color_stop, linear_gradient, prelude::FluentBuilder, px, uniform_list,
 };
 
 use super::*;
 
+/// The width of the shadow after the fixed columns.
+const FIXED_SHADOW_WIDTH: Pixels = px(6.);
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum SelectionState {
     Column,
@@ -87,6 +90,8 @@ pub struct TableState<D: TableDelegate> {
     pub col_selectable: bool,
     /// Whether the table can select row.
     pub row_selectable: bool,
+    /// Whether the table can select multiple rows by ctrl/cmd and shift click, default is false.
+    pub multi_row_selectable: bool,
     /// Whether the table can sort.
     pub sortable: bool,
     /// Whether the table can resize columns.
@@ -100,12 +105,18 @@ pub struct TableState<D: TableDelegate> {
     pub horizontal_scroll_handle: VirtualListScrollHandle,
 
     selected_row: Option<usize>,
+    /// The selected rows, including the `selected_row`, in the order of selection.
+    selected_rows: Vec<usize>,
+    on_selection_change: Option<Rc<dyn Fn(&[usize], &mut App)>>,
     selection_state: SelectionState,
     right_clicked_row: Option<usize>,
     selected_col: Option<usize>,
 
     /// The column index that is being resized.
     resizing_col: Option<usize>,
+    /// The resized widths of the columns by key, kept when the columns are refreshed.
+    col_widths: HashMap<SharedString, Pixels>,
+    on_column_resize: Option<Rc<dyn Fn(&SharedString, Pixels, &mut Window, &mut App)>>,
 
     /// The visible range of the rows and columns.
     visible_range: TableVisibleRange,
@@ -129,15 +140,20 @@ where
             vertical_scroll_handle: UniformListScrollHandle::new(),
             selection_state: SelectionState::Row,
             selected_row: None,
+            selected_rows: Vec::new(),
+            on_selection_change: None,
             right_clicked_row: None,
             selected_col: None,
             resizing_col: None,
+            col_widths: HashMap::new(),
+            on_column_resize: None,
             bounds: Bounds::default(),
             fixed_head_cols_bounds: Bounds::default(),
             visible_range: TableVisibleRange::default(),
             loop_selection: true,
             col_selectable: true,
             row_selectable: true,
+            multi_row_selectable: false,
             sortable: true,
             col_movable: true,
             col_resizable: true,
@@ -178,6 +194,18 @@ where
         self
     }
 
+    /// Set a callback to be called when a column has been resized by dragging its border.
+    ///
+    /// The arguments are the key and the new width of the column,
+    /// this can be used to save the layout and restore it by [`TableState::set_column_width`].
+    pub fn on_column_resize(
+        mut self,
+        on_column_resize: impl Fn(&SharedString, Pixels, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_column_resize = Some(Rc::new(on_column_resize));
+        self
+    }
+
     /// Set to enable/disable column sortable, default true
     pub fn sortable(mut self, sortable: bool) -> Self {
         self.sortable = sortable;
@@ -190,6 +218,26 @@ where
         self
     }
 
+    /// Set to enable/disable multiple rows selection, default false.
+    ///
+    /// When enabled, ctrl/cmd click toggles a row and shift click selects the rows
+    /// from the last selected row.
+    pub fn multi_row_selectable(mut self, multi_row_selectable: bool) -> Self {
+        self.multi_row_selectable = multi_row_selectable;
+        self
+    }
+
+    /// Set a callback to be called when the selected rows have changed.
+    ///
+    /// The argument is the selected rows, see [`TableState::selected_rows`].
+    pub fn on_selection_change(
+        mut self,
+        on_selection_change: impl Fn(&[usize], &mut App) + 'static,
+    ) -> Self {
+        self.on_selection_change = Some(Rc::new(on_selection_change));
+        self
+    }
+
     /// Set to enable/disable column selectable, default true
     pub fn col_selectable(mut self, col_selectable: bool) -> Self {
         self.col_selectable = col_selectable;
@@ -201,6 +249,14 @@ where
         self.prepare_col_groups(cx);
     }
 
+    /// Returns the key and the order of the sorted column, `None` if no column is sorted.
+    pub fn sort(&self) -> Option<(SharedString, ColumnSort)> {
+        self.col_groups.iter().find_map(|col_group| match col_group.column.sort {
+            Some(sort) if sort != ColumnSort::Default => Some((col_group.column.key.clone(), sort)),
+            _ => None,
+        })
+    }
+
     /// Scroll to the row at the given index.
     pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
         self.vertical_scroll_handle
@@ -232,6 +288,7 @@ where
         self.selection_state = SelectionState::Row;
         self.right_clicked_row = None;
         self.selected_row = Some(row_ix);
+        self.update_selected_rows(vec![row_ix], cx);
         if let Some(row_ix) = self.selected_row {
             self.vertical_scroll_handle.scroll_to_item(
                 row_ix,
@@ -247,6 +304,75 @@ where
         cx.notify();
     }
 
+    /// Returns the selected rows in the order of selection.
+    ///
+    /// This contains at most one row unless [`TableState::multi_row_selectable`] is enabled,
+    /// the last one is the [`TableState::selected_row`].
+    pub fn selected_rows(&self) -> &[usize] {
+        &self.selected_rows
+    }
+
+    /// Replace the selected rows, e.g. to follow the selected items after the rows changed.
+    ///
+    /// Unlike [`TableState::set_selected_row`], this does not scroll to the selected row,
+    /// the last one of `selected_rows` becomes the [`TableState::selected_row`].
+    pub fn set_selected_rows(&mut self, selected_rows: Vec<usize>, cx: &mut Context<Self>) {
+        self.selected_row = selected_rows.last().copied();
+        self.update_selected_rows(selected_rows, cx);
+        cx.notify();
+    }
+
+    /// Toggle the selection of the row, keeping the other selected rows.
+    fn toggle_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let mut selected_rows = self.selected_rows.clone();
+        if let Some(ix) = selected_rows.iter().position(|&ix| ix == row_ix) {
+            selected_rows.remove(ix);
+            self.selected_row = selected_rows.last().copied();
+        } else {
+            selected_rows.push(row_ix);
+            self.selected_row = Some(row_ix);
+        }
+
+        self.selection_state = SelectionState::Row;
+        self.right_clicked_row = None;
+        self.update_selected_rows(selected_rows, cx);
+        if let Some(row_ix) = self.selected_row {
+            cx.emit(TableEvent::SelectRow(row_ix));
+        }
+        cx.notify();
+    }
+
+    /// Select the rows from the last selected row to the given row.
+    fn select_rows_to(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let Some(anchor) = self.selected_row else {
+            self.set_selected_row(row_ix, cx);
+            return;
+        };
+
+        // Keep the anchor as the last selected row, to extend the range from it.
+        let mut selected_rows: Vec<usize> = (anchor.min(row_ix)..=anchor.max(row_ix))
+            .filter(|&ix| ix != anchor)
+            .collect();
+        selected_rows.push(anchor);
+
+        self.selection_state = SelectionState::Row;
+        self.right_clicked_row = None;
+        self.update_selected_rows(selected_rows, cx);
+        cx.emit(TableEvent::SelectRow(row_ix));
+        cx.notify();
+    }
+
+    fn update_selected_rows(&mut self, selected_rows: Vec<usize>, cx: &mut Context<Self>) {
+        if self.selected_rows == selected_rows {
+            return;
+        }
+
+        self.selected_rows = selected_rows;
+        if let Some(on_selection_change) = self.on_selection_change.clone() {
+            on_selection_change(&self.selected_rows, cx);
+        }
+    }
+
     /// Returns the row that has been right clicked.
     pub fn right_clicked_row(&self) -> Option<usize> {
         self.right_clicked_row
@@ -273,9 +399,49 @@ where
         self.selection_state = SelectionState::Row;
         self.selected_row = None;
         self.selected_col = None;
+        self.update_selected_rows(vec![], cx);
+        cx.notify();
+    }
+
+    /// Set the width of the column with the given key, clamped to its min and max width.
+    ///
+    /// The width is kept when the columns are refreshed, until the column is resized again.
+    pub fn set_column_width(
+        &mut self,
+        key: impl Into<SharedString>,
+        width: Pixels,
+        cx: &mut Context<Self>,
+    ) {
+        let key = key.into();
+        if let Some(col_group) = self.col_groups.iter_mut().find(|g| g.column.key == key) {
+            col_group.width = width.clamp(col_group.column.min_width, col_group.column.max_width);
+        }
+        self.col_widths.insert(key, width);
         cx.notify();
     }
 
+    /// Export the table as CSV, with the column names as the header row.
+    ///
+    /// The cells are from [`TableDelegate::cell_text`], all the fields are quoted.
+    pub fn export_csv(&self, cx: &App) -> String {
+        let columns_count = self.delegate.columns_count(cx);
+        let header = (0..columns_count)
+            .map(|col_ix| csv_field(&self.delegate.column(col_ix, cx).name))
+            .collect::<Vec<_>>();
+
+        let mut csv = header.join(",");
+        csv.push('\n');
+        for row_ix in 0..self.delegate.rows_count(cx) {
+            let cells = (0..columns_count)
+                .map(|col_ix| csv_field(&self.delegate.cell_text(row_ix, col_ix, cx)))
+                .collect::<Vec<_>>();
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
     /// Returns the visible range of the rows and columns.
     ///
     /// See [`TableVisibleRange`].
@@ -284,11 +450,22 @@ where
     }
 
     fn prepare_col_groups(&mut self, cx: &mut Context<Self>) {
+        let prev_sort = self.sort();
         self.col_groups = (0..self.delegate.columns_count(cx))
             .map(|col_ix| {
-                let column = self.delegate().column(col_ix, cx);
+                let mut column = self.delegate().column(col_ix, cx);
+                // Keep the sort picked in the header, the columns are usually created unsorted.
+                if let Some((key, sort)) = &prev_sort {
+                    if column.key == *key && column.sort == Some(ColumnSort::Default) {
+                        column.sort = Some(*sort);
+                    }
+                }
+                let width = match self.col_widths.get(&column.key) {
+                    Some(width) => width.clamp(column.min_width, column.max_width),
+                    None => column.width,
+                };
                 ColGroup {
-                    width: column.width,
+                    width,
                     bounds: Bounds::default(),
                     column,
                 }
@@ -330,6 +507,15 @@ where
             return;
         }
 
+        let modifiers = e.modifiers();
+        if self.multi_row_selectable && modifiers.shift {
+            self.select_rows_to(row_ix, cx);
+            return;
+        } else if self.multi_row_selectable && modifiers.secondary() {
+            self.toggle_selected_row(row_ix, cx);
+            return;
+        }
+
         self.set_selected_row(row_ix, cx);
 
         if e.click_count() == 2 {
@@ -497,6 +683,7 @@ where
         // Only update if it actually changed
         if col_group.width != new_width {
             col_group.width = new_width;
+            self.col_widths.insert(col_group.column.key.clone(), new_width);
             cx.notify();
         }
     }
@@ -512,10 +699,11 @@ where
         }
 
         let sort = sort.unwrap();
+        // Cycle through the unsorted, ascending and descending order.
         let sort = match sort {
-            ColumnSort::Ascending => ColumnSort::Default,
-            ColumnSort::Descending => ColumnSort::Ascending,
-            ColumnSort::Default => ColumnSort::Descending,
+            ColumnSort::Default => ColumnSort::Ascending,
+            ColumnSort::Ascending => ColumnSort::Descending,
+            ColumnSort::Descending => ColumnSort::Default,
         };
 
         for (ix, col_group) in self.col_groups.iter_mut().enumerate() {
@@ -729,12 +917,16 @@ where
             })
             .on_mouse_up_out(
                 MouseButton::Left,
-                cx.listener(|view, _, _, cx| {
-                    if view.resizing_col.is_none() {
+                cx.listener(|view, _, window, cx| {
+                    let Some(ix) = view.resizing_col.take() else {
                         return;
-                    }
+                    };
 
-                    view.resizing_col = None;
+                    if let (Some(on_column_resize), Some(col_group)) =
+                        (view.on_column_resize.clone(), view.col_groups.get(ix))
+                    {
+                        on_column_resize(&col_group.column.key, col_group.width, window, cx);
+                    }
 
                     let new_widths = view.col_groups.iter().map(|g| g.width).collect();
                     cx.emit(TableEvent::ColumnWidthsChanged(new_widths));
@@ -861,6 +1053,27 @@ where
             })
     }
 
+    /// Render the shadow after the fixed columns, when the other columns are scrolled under them.
+    fn render_fixed_shadow(&self, left_columns_count: usize, cx: &App) -> Option<Div> {
+        if left_columns_count == 0 || self.horizontal_scroll_handle.offset().x >= px(0.) {
+            return None;
+        }
+
+        Some(
+            div()
+                .absolute()
+                .top_0()
+                .bottom_0()
+                .left(self.fixed_head_cols_bounds.size.width)
+                .w(FIXED_SHADOW_WIDTH)
+                .bg(linear_gradient(
+                    90.,
+                    linear_color_stop(cx.theme().border.opacity(0.8), 0.),
+                    linear_color_stop(cx.theme().border.opacity(0.), 1.),
+                )),
+        )
+    }
+
     fn render_table_header(
         &mut self,
         left_columns_count: usize,
@@ -878,10 +1091,13 @@ where
         let mut header = self.delegate_mut().render_header(window, cx);
         let style = header.style().clone();
 
+        let fixed_shadow = self.render_fixed_shadow(left_columns_count, cx);
+
         header
             .h_flex()
+            .relative()
             .w_full()
-            .h(self.options.size.table_row_height())
+            .h(self.options.header_height())
             .flex_shrink_0()
             .border_b_1()
             .border_color(cx.theme().border)
@@ -945,6 +1161,7 @@ where
                             .child(self.delegate.render_last_empty_col(window, cx)),
                     ),
             )
+            .children(fixed_shadow)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -962,8 +1179,12 @@ where
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
         let is_stripe_row = self.options.stripe && row_ix % 2 != 0;
         let is_selected = self.selected_row == Some(row_ix);
+        let is_multi_selected = !is_selected && self.selected_rows.contains(&row_ix);
         let view = cx.entity().clone();
-        let row_height = self.options.size.table_row_height();
+        let row_height = self.options.row_height();
+        let stripe_color = self.options.stripe_color.unwrap_or(cx.theme().table_even);
+        let hover_highlight = self.options.hover_highlight;
+        let hover_color = self.options.hover_color.unwrap_or(cx.theme().accent);
 
         if row_ix < rows_count {
             let is_last_row = row_ix + 1 == rows_count;
@@ -978,13 +1199,21 @@ where
                 .when(need_render_border, |this| {
                     this.border_b_1().border_color(cx.theme().table_row_border)
                 })
-                .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
+                .when(is_stripe_row, |this| this.bg(stripe_color))
                 .refine_style(&style)
+                .when(
+                    is_multi_selected && self.selection_state == SelectionState::Row,
+                    |this| this.bg(cx.theme().accent),
+                )
                 .hover(|this| {
-                    if is_selected || self.right_clicked_row == Some(row_ix) {
+                    if !hover_highlight
+                        || is_selected
+                        || is_multi_selected
+                        || self.right_clicked_row == Some(row_ix)
+                    {
                         this
                     } else {
-                        this.bg(cx.theme().table_hover)
+                        this.bg(hover_color)
                     }
                 })
                 .when(left_columns_count > 0, |this| {
@@ -1067,6 +1296,7 @@ where
                         )
                         .child(self.delegate.render_last_empty_col(window, cx)),
                 )
+                .children(self.render_fixed_shadow(left_columns_count, cx))
                 // Row selected style
                 .when_some(self.selected_row, |this, _| {
                     this.when(
@@ -1123,7 +1353,7 @@ where
                 .h(row_height)
                 .border_b_1()
                 .border_color(cx.theme().table_row_border)
-                .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
+                .when(is_stripe_row, |this| this.bg(stripe_color))
                 .children((0..columns_count).map(|col_ix| {
                     h_flex()
                         .left(horizontal_scroll_handle.offset().x)
@@ -1203,7 +1433,7 @@ where
             div()
                 .occlude()
                 .absolute()
-                .top(self.options.size.table_row_height())
+                .top(self.options.header_height())
                 .right_0()
                 .bottom_0()
                 .w(Scrollbar::width())
@@ -1227,6 +1457,11 @@ where
     }
 }
 
+/// Quote the field for CSV, the quotes in the field are escaped by doubling them.
+fn csv_field(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
 impl<D> Focusable for TableState<D>
 where
     D: TableDelegate,
@@ -1253,7 +1488,7 @@ where
         let rows_count = self.delegate.rows_count(cx);
         let loading = self.delegate.loading(cx);
 
-        let row_height = self.options.size.table_row_height();
+        let row_height = self.options.row_height();
         let total_height = self
             .vertical_scroll_handle
             .0
@@ -1426,3 +1661,17 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::csv_field;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field(""), r#""""#);
+        assert_eq!(csv_field("chrome"), r#""chrome""#);
+        assert_eq!(csv_field("a, b"), r#""a, b""#);
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+}
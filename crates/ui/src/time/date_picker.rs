@@ -355,7 +355,7 @@ impl RenderOnce for DatePicker {
         });
 
         // This for keep focus border style, when click on the popup.
-        let is_focused = self.focus_handle(cx).contains_focused(window, cx);
+        let focus_handle = self.focus_handle(cx);
         let state = self.state.read(cx);
         let show_clean = self.cleanable && state.date.is_some();
         let placeholder = self
@@ -394,7 +394,7 @@ impl RenderOnce for DatePicker {
                             .border_color(cx.theme().input)
                             .rounded(cx.theme().radius)
                             .when(cx.theme().shadow, |this| this.shadow_xs())
-                            .when(is_focused, |this| this.focused_border(cx))
+                            .focused_border_for(&focus_handle, window, cx)
                             .when(self.disabled, |this| {
                                 this.bg(cx.theme().muted)
                                     .text_color(cx.theme().muted_foreground)
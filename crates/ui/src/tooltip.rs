@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use gpui::{
     div, prelude::FluentBuilder, px, Action, AnyElement, AnyView, App, AppContext, Context,
-    IntoElement, ParentElement, Render, SharedString, StyleRefinement, Styled, Window,
+    IntoElement, ParentElement, Pixels, Point, Render, SharedString, StyleRefinement, Styled,
+    Task, Window,
 };
 
 use crate::{h_flex, kbd::Kbd, text::Text, ActiveTheme, StyledExt};
@@ -17,6 +20,13 @@ pub struct Tooltip {
     content: TooltipContext,
     key_binding: Option<Kbd>,
     action: Option<(Box<dyn Action>, Option<SharedString>)>,
+    delay: Option<Duration>,
+    follow_cursor: bool,
+    /// False until the delay has elapsed.
+    visible: bool,
+    /// The mouse position when the tooltip was built, the offset to follow the cursor from.
+    mouse_position: Point<Pixels>,
+    _delay_task: Option<Task<()>>,
 }
 
 impl Tooltip {
@@ -27,6 +37,11 @@ impl Tooltip {
             content: TooltipContext::Text(text.into()),
             key_binding: None,
             action: None,
+            delay: None,
+            follow_cursor: false,
+            visible: true,
+            mouse_position: Point::default(),
+            _delay_task: None,
         }
     }
 
@@ -40,6 +55,11 @@ impl Tooltip {
             style: StyleRefinement::default(),
             key_binding: None,
             action: None,
+            delay: None,
+            follow_cursor: false,
+            visible: true,
+            mouse_position: Point::default(),
+            _delay_task: None,
             content: TooltipContext::Element(Box::new(move |window, cx| {
                 builder(window, cx).into_any_element()
             })),
@@ -58,9 +78,38 @@ impl Tooltip {
         self
     }
 
+    /// Set the delay before the tooltip shows, in addition to the hover delay of the element.
+    ///
+    /// If the pointer leaves before the delay, the tooltip is dropped without showing.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Move the tooltip with the pointer, e.g. for charts and dense tables, default is false.
+    pub fn follow_cursor(mut self, follow_cursor: bool) -> Self {
+        self.follow_cursor = follow_cursor;
+        self
+    }
+
     /// Build the tooltip and return it as an `AnyView`.
-    pub fn build(self, _: &mut Window, cx: &mut App) -> AnyView {
-        cx.new(|_| self).into()
+    pub fn build(mut self, window: &mut Window, cx: &mut App) -> AnyView {
+        self.mouse_position = window.mouse_position();
+        cx.new(|cx| {
+            if let Some(delay) = self.delay {
+                self.visible = false;
+                // Dropping the tooltip when the pointer leaves also drops the task, cancelling the timer.
+                self._delay_task = Some(cx.spawn(async move |this, cx| {
+                    cx.background_executor().timer(delay).await;
+                    _ = this.update(cx, |this, cx| {
+                        this.visible = true;
+                        cx.notify();
+                    });
+                }));
+            }
+            self
+        })
+        .into()
     }
 }
 
@@ -72,6 +121,18 @@ impl Styled for Tooltip {
 }
 impl Render for Tooltip {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !self.visible {
+            return div();
+        }
+
+        let offset = if self.follow_cursor {
+            // Keep rendering while visible, to track the pointer.
+            window.request_animation_frame();
+            window.mouse_position() - self.mouse_position
+        } else {
+            Point::default()
+        };
+
         let key_binding = if let Some(key_binding) = &self.key_binding {
             Some(key_binding.clone())
         } else {
@@ -86,39 +147,43 @@ impl Render for Tooltip {
             }
         };
 
-        div().child(
-            // Wrap in a child, to ensure the left margin is applied to the tooltip
-            h_flex()
-                .font_family(cx.theme().font_family.clone())
-                .m_3()
-                .bg(cx.theme().popover)
-                .text_color(cx.theme().popover_foreground)
-                .bg(cx.theme().popover)
-                .border_1()
-                .border_color(cx.theme().border)
-                .shadow_md()
-                .rounded(px(6.))
-                .justify_between()
-                .py_0p5()
-                .px_2()
-                .text_sm()
-                .gap_3()
-                .refine_style(&self.style)
-                .map(|this| {
-                    this.child(div().map(|this| match self.content {
-                        TooltipContext::Text(ref text) => this.child(text.clone()),
-                        TooltipContext::Element(ref builder) => this.child(builder(window, cx)),
-                    }))
-                })
-                .when_some(key_binding, |this, kbd| {
-                    this.child(
-                        div()
-                            .text_xs()
-                            .flex_shrink_0()
-                            .text_color(cx.theme().muted_foreground)
-                            .child(kbd.appearance(false)),
-                    )
-                }),
-        )
+        div()
+            .when(self.follow_cursor, |this| {
+                this.relative().left(offset.x).top(offset.y)
+            })
+            .child(
+                // Wrap in a child, to ensure the left margin is applied to the tooltip
+                h_flex()
+                    .font_family(cx.theme().font_family.clone())
+                    .m_3()
+                    .bg(cx.theme().popover)
+                    .text_color(cx.theme().popover_foreground)
+                    .bg(cx.theme().popover)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .shadow_md()
+                    .rounded(px(6.))
+                    .justify_between()
+                    .py_0p5()
+                    .px_2()
+                    .text_sm()
+                    .gap_3()
+                    .refine_style(&self.style)
+                    .map(|this| {
+                        this.child(div().map(|this| match self.content {
+                            TooltipContext::Text(ref text) => this.child(text.clone()),
+                            TooltipContext::Element(ref builder) => this.child(builder(window, cx)),
+                        }))
+                    })
+                    .when_some(key_binding, |this, kbd| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .flex_shrink_0()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(kbd.appearance(false)),
+                        )
+                    }),
+            )
     }
 }
@@ -1,9 +1,16 @@
 use crate::{
     Placement, Root, dialog::Dialog, input::InputState, notification::Notification, sheet::Sheet,
 };
-use gpui::{App, Entity, Window};
+use gpui::{App, AppContext as _, Context, ElementId, Entity, Subscription, Window};
 use std::rc::Rc;
 
+/// The keyed state of [`WindowExt::use_keyed_state_with_reset`].
+struct ResettableState<K, S> {
+    reset_key: K,
+    state: Entity<S>,
+    _subscription: Subscription,
+}
+
 /// Extension trait for [`Window`] to add dialog, sheet .. functionality.
 pub trait WindowExt: Sized {
     /// Opens a Sheet at right placement.
@@ -52,6 +59,23 @@ pub trait WindowExt: Sized {
     fn focused_input(&mut self, cx: &mut App) -> Option<Entity<InputState>>;
     /// Returns true if there is a focused Input entity.
     fn has_focused_input(&mut self, cx: &mut App) -> bool;
+
+    /// Like [`Window::use_keyed_state`], but the state will be re-created by `init`
+    /// when the `reset_key` is different from the previous render.
+    ///
+    /// This is useful when the same `key` is reused for different logical content,
+    /// e.g. a recycled row of a virtual list, use the row's data id as the `reset_key`
+    /// to avoid the stale state leaking into the new content.
+    fn use_keyed_state_with_reset<K, S>(
+        &mut self,
+        key: impl Into<ElementId>,
+        reset_key: K,
+        cx: &mut App,
+        init: impl FnOnce(&mut Window, &mut Context<S>) -> S,
+    ) -> Entity<S>
+    where
+        K: PartialEq + 'static,
+        S: 'static;
 }
 
 impl WindowExt for Window {
@@ -150,4 +174,35 @@ impl WindowExt for Window {
     fn focused_input(&mut self, cx: &mut App) -> Option<Entity<InputState>> {
         Root::read(self, cx).focused_input.clone()
     }
+
+    fn use_keyed_state_with_reset<K, S>(
+        &mut self,
+        key: impl Into<ElementId>,
+        reset_key: K,
+        cx: &mut App,
+        init: impl FnOnce(&mut Window, &mut Context<S>) -> S,
+    ) -> Entity<S>
+    where
+        K: PartialEq + 'static,
+        S: 'static,
+    {
+        let holder = self.use_keyed_state(key, cx, |_, _| None::<ResettableState<K, S>>);
+        if let Some(this) = holder.read(cx).as_ref() {
+            if this.reset_key == reset_key {
+                return this.state.clone();
+            }
+        }
+
+        holder.update(cx, |holder, cx| {
+            let state = cx.new(|cx| init(self, cx));
+            // Forward the notify of the inner state to re-render the view.
+            let _subscription = cx.observe(&state, |_, _, cx| cx.notify());
+            *holder = Some(ResettableState {
+                reset_key,
+                state: state.clone(),
+                _subscription,
+            });
+            state
+        })
+    }
 }